@@ -14,9 +14,10 @@ use log::*;
 use serde_json::Value;
 
 use pact_cli::{glob_value, setup_loggers};
-use pact_cli::verification::{display_results, VerificationResult, verify_json};
+use pact_cli::verification::{display_results, lint_matching_rules, validate_examples, VerificationResult, verify_json};
 use pact_models::http_utils::{self, HttpAuth};
 use pact_models::PactSpecification;
+use pact_models::verify_json::ResultLevel;
 
 fn setup_app<'a, 'b>(program: &str, version: &'b str) -> App<'a, 'b> {
   App::new(program)
@@ -176,8 +177,19 @@ fn handle_matches(args: &ArgMatches) -> Result<(), i32> {
 
   let files = load_files(args).map_err(|_| 1)?;
 
+  let runtime = tokio::runtime::Builder::new_multi_thread()
+    .enable_all()
+    .build()
+    .map_err(|err| {
+      error!("Could not start a Tokio runtime for validating the pact examples - {}", err);
+      3
+    })?;
   let results = files.iter().map(|(source, pact_json)| {
-    let results = verify_json(pact_json, spec_version, source, args.is_present("strict"));
+    let mut results = verify_json(pact_json, spec_version, source, args.is_present("strict"));
+    if !results.iter().any(|result| result.level == ResultLevel::ERROR) {
+      results.extend(runtime.block_on(validate_examples(pact_json, source)));
+      results.extend(lint_matching_rules(pact_json, source));
+    }
     VerificationResult::new(source, results)
   }).collect();
 
@@ -2,17 +2,20 @@
 
 #![warn(missing_docs)]
 
-use std::env;
+use std::{env, fs};
 use std::fs::File;
 
 use anyhow::{anyhow, Context};
 use clap::{App, AppSettings, Arg, ArgMatches, ErrorKind};
+use glob::glob;
 use log::*;
 use serde_json::Value;
 
 use pact_cli::{glob_value, setup_loggers};
-use pact_models::http_utils;
+use pact_cli::verification::{display_results, VerificationResult, verify_json};
 use pact_models::http_utils::HttpAuth;
+use pact_models::PactSpecification;
+use pact_verifier::pact_broker::publish_pacts;
 
 fn setup_app<'a, 'b>(program: &str, version: &'b str) -> App<'a, 'b> {
   App::new(program)
@@ -81,6 +84,43 @@ fn setup_app<'a, 'b>(program: &str, version: &'b str) -> App<'a, 'b> {
       .long("validate")
       .short("v")
       .help("Validate the Pact files before publishing."))
+    .arg(Arg::with_name("broker-url")
+      .short("b")
+      .long("broker-url")
+      .env("PACT_BROKER_BASE_URL")
+      .required(true)
+      .takes_value(true)
+      .use_delimiter(false)
+      .empty_values(false)
+      .help("URL of the pact broker to publish to"))
+    .arg(Arg::with_name("consumer-app-version")
+      .short("a")
+      .long("consumer-app-version")
+      .required(true)
+      .takes_value(true)
+      .use_delimiter(false)
+      .empty_values(false)
+      .help("Consumer application version to publish the pacts for"))
+    .arg(Arg::with_name("branch")
+      .long("branch")
+      .takes_value(true)
+      .use_delimiter(false)
+      .empty_values(false)
+      .help("Repository branch of the consumer version"))
+    .arg(Arg::with_name("tag")
+      .long("tag")
+      .takes_value(true)
+      .use_delimiter(false)
+      .multiple(true)
+      .number_of_values(1)
+      .empty_values(false)
+      .help("Tag to apply to the consumer version (can be repeated)"))
+    .arg(Arg::with_name("build-url")
+      .long("build-url")
+      .takes_value(true)
+      .use_delimiter(false)
+      .empty_values(false)
+      .help("URL of the build that produced the pacts"))
     .arg(Arg::with_name("user")
       .long("user")
       .takes_value(true)
@@ -139,34 +179,48 @@ fn handle_matches(args: &ArgMatches) -> Result<(), i32> {
     eprintln!();
   }
 
-  let _files = load_files(args).map_err(|_| 1)?;
-
-  // let results = files.iter().map(|(source, pact_json)| {
-  //   let results = match spec_version {
-  //     PactSpecification::V4 => V4Pact::verify_json("/", pact_json, args.is_present("strict")),
-  //     _ => match pact_json {
-  //       Value::Object(map) => if map.contains_key("messages") {
-  //         MessagePact::verify_json("/", pact_json, args.is_present("strict"))
-  //       } else {
-  //         RequestResponsePact::verify_json("/", pact_json, args.is_present("strict"))
-  //       },
-  //       _ => vec![PactFileVerificationResult::new("/", ResultLevel::ERROR,
-  //         &format!("Must be an Object, got {}", json_type_of(pact_json)))]
-  //     }
-  //   };
-  //   VerificationResult::new(source, results)
-  // }).collect();
-  //
-  // let display_result = display_results(&results, "console");
-  //
-  // if display_result.is_err() {
-  //   Err(3)
-  // } else if results.iter().any(|res| res.has_errors()) {
-  //   Err(2)
-  // } else {
-  //   Ok(())
-  // }
-  Err(1)
+  let files = load_files(args).map_err(|_| 1)?;
+
+  if args.is_present("validate") {
+    let results = files.iter().map(|(source, pact_json)| {
+      let results = verify_json(pact_json, PactSpecification::Unknown, source, false);
+      VerificationResult::new(source, results)
+    }).collect::<Vec<_>>();
+    if results.iter().any(|res| res.has_errors()) {
+      let _ = display_results(&results, "console");
+      error!("One or more pact files failed validation, not publishing");
+      return Err(2);
+    }
+  }
+
+  let pacts = files.iter().map(|(_, pact_json)| pact_json.clone()).collect::<Vec<_>>();
+  let runtime = tokio::runtime::Builder::new_multi_thread()
+    .enable_all()
+    .build()
+    .map_err(|err| {
+      error!("Could not start a Tokio runtime for publishing the pacts - {}", err);
+      3
+    })?;
+  let result = runtime.block_on(publish_pacts(
+    args.value_of("broker-url").unwrap_or_default(),
+    broker_auth(args),
+    &pacts,
+    args.value_of("consumer-app-version").unwrap_or_default(),
+    args.value_of("branch").map(|b| b.to_string()),
+    args.values_of("tag").map(|tags| tags.map(|t| t.to_string()).collect()).unwrap_or_default(),
+    args.value_of("build-url").map(|url| url.to_string())
+  ));
+
+  match result {
+    Ok(_) => {
+      println!("Published {} pact file(s) to {}", files.len(), args.value_of("broker-url").unwrap_or_default());
+      Ok(())
+    },
+    Err(err) => {
+      error!("Failed to publish the pact files - {}", err);
+      Err(3)
+    }
+  }
 }
 
 fn load_files(args: &ArgMatches) -> anyhow::Result<Vec<(String, Value)>> {
@@ -176,10 +230,25 @@ fn load_files(args: &ArgMatches) -> anyhow::Result<Vec<(String, Value)>> {
       (v.to_string(), load_file(v))
     }).collect::<Vec<(String, anyhow::Result<Value>)>>());
   };
-  if let Some(values) = args.values_of("url") {
-    sources.extend(values.map(|v| {
-      (v.to_string(), fetch_pact(v, args).map(|(_, value)| value))
-    }).collect::<Vec<(String, anyhow::Result<Value>)>>());
+  if let Some(values) = args.values_of("dir") {
+    for value in values {
+      for entry in fs::read_dir(value)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().unwrap_or_default() == "json" {
+          let file_name = path.to_str().ok_or(anyhow!("Directory contains non-UTF-8 entry"))?;
+          sources.push((file_name.to_string(), load_file(file_name)));
+        }
+      }
+    }
+  };
+  if let Some(values) = args.values_of("glob") {
+    for value in values {
+      for entry in glob(value)? {
+        let entry = entry?;
+        let file_name = entry.to_str().ok_or(anyhow!("Glob matched non-UTF-8 entry"))?;
+        sources.push((file_name.to_string(), load_file(file_name)));
+      }
+    }
   };
 
   if sources.iter().any(|(_, res)| res.is_err()) {
@@ -193,17 +262,16 @@ fn load_files(args: &ArgMatches) -> anyhow::Result<Vec<(String, Value)>> {
   }
 }
 
-fn fetch_pact(url: &str, args: &ArgMatches) -> anyhow::Result<(String, Value)> {
-  let auth = if args.is_present("user") {
-    args.value_of("password").map(|user | {
+fn broker_auth(args: &ArgMatches) -> Option<HttpAuth> {
+  if args.is_present("user") {
+    args.value_of("user").map(|user| {
       HttpAuth::User(user.to_string(), args.value_of("password").map(|p| p.to_string()))
     })
   } else if args.is_present("token") {
     args.value_of("token").map(|token| HttpAuth::Token(token.to_string()))
   } else {
     None
-  };
-  http_utils::fetch_json_from_url(&url.to_string(), &auth)
+  }
 }
 
 fn load_file(file_name: &str) -> anyhow::Result<Value> {
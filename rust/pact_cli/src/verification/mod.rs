@@ -1,12 +1,17 @@
 //! Functions to verify a Pact file
 
+use std::collections::HashMap;
+
 use ansi_term::Colour::*;
 use log::error;
 use serde::Serialize;
 use serde_json::Value;
 
+use pact_matching::validate_interaction_examples;
+use pact_models::http_parts::HttpPart;
+use pact_models::matchingrules::lint::dead_matching_rules;
 use pact_models::message_pact::MessagePact;
-use pact_models::pact::{determine_spec_version, parse_meta_data};
+use pact_models::pact::{determine_spec_version, load_pact_from_json, parse_meta_data, Pact};
 use pact_models::PactSpecification;
 use pact_models::sync_pact::RequestResponsePact;
 use pact_models::v4::pact::V4Pact;
@@ -57,6 +62,80 @@ pub fn verify_json(pact_json: &Value, spec_version: PactSpecification, source: &
   }
 }
 
+/// Checks the matching rules of all the interactions in the Pact file against the example
+/// bodies, headers and query parameters, and returns a warning for each rule that does not
+/// match anything (dead rules)
+pub fn lint_matching_rules(pact_json: &Value, source: &str) -> Vec<PactFileVerificationResult> {
+  let (pact, interactions_key) = match load_pact(pact_json, source) {
+    Some(result) => result,
+    None => return vec![]
+  };
+
+  let mut results = vec![];
+  for (index, interaction) in pact.interactions().iter().enumerate() {
+    let path = format!("/{}/{}", interactions_key, index);
+    if let Some(http) = interaction.as_v4_http() {
+      results.extend(lint_part(&format!("{}/request", path), &http.request, http.request.query.as_ref()));
+      results.extend(lint_part(&format!("{}/response", path), &http.response, None));
+    } else if let Some(message) = interaction.as_v4_sync_message() {
+      results.extend(lint_part(&format!("{}/request", path), &message.request, None));
+      for (response_index, response) in message.response.iter().enumerate() {
+        results.extend(lint_part(&format!("{}/response/{}", path, response_index), response, None));
+      }
+    } else if let Some(message) = interaction.as_v4_async_message() {
+      results.extend(lint_part(&path, &message, None));
+    }
+  }
+  results
+}
+
+/// Validates that the examples in all the interactions in the Pact file satisfy their own matching
+/// rules, and returns an error for each example value that does not
+pub async fn validate_examples(pact_json: &Value, source: &str) -> Vec<PactFileVerificationResult> {
+  let (pact, interactions_key) = match load_pact(pact_json, source) {
+    Some(result) => result,
+    None => return vec![]
+  };
+
+  let mut results = vec![];
+  for (index, interaction) in pact.interactions().iter().enumerate() {
+    for (part, mismatch) in validate_interaction_examples(interaction, &pact).await {
+      results.push(PactFileVerificationResult::new(format!("/{}/{}/{}", interactions_key, index, part),
+        ResultLevel::ERROR, format!("Example does not satisfy its own matching rules - {}", mismatch.description())));
+    }
+  }
+  results
+}
+
+/// Loads the Pact from the JSON, returning it along with the key of the interactions in the JSON
+fn load_pact(pact_json: &Value, source: &str) -> Option<(Box<dyn Pact + Send + Sync>, &'static str)> {
+  match load_pact_from_json(source, pact_json) {
+    Ok(pact) => {
+      let interactions_key = match pact_json.get("messages") {
+        Some(_) => "messages",
+        None => "interactions"
+      };
+      Some((pact, interactions_key))
+    }
+    Err(err) => {
+      error!("Could not load the Pact file '{}' to check the interactions - {}", source, err);
+      None
+    }
+  }
+}
+
+fn lint_part(
+  path: &str,
+  part: &dyn HttpPart,
+  query: Option<&HashMap<String, Vec<String>>>
+) -> Vec<PactFileVerificationResult> {
+  dead_matching_rules(part, query).iter().map(|rule| {
+    let category: String = rule.category.clone().into();
+    PactFileVerificationResult::new(format!("{}/matchingRules/{}/{}", path, category, rule.path),
+      ResultLevel::WARNING, rule.to_string())
+  }).collect()
+}
+
 pub fn display_results(result: &Vec<VerificationResult>, output_type: &str) -> anyhow::Result<()> {
   if output_type == "json" {
     generate_json_output(result)
@@ -5,7 +5,7 @@ use pact_models::PactSpecification;
 use pact_models::sync_interaction::RequestResponseInteraction;
 use pact_models::verify_json::{PactJsonVerifier, ResultLevel};
 
-use super::verify_json;
+use super::{lint_matching_rules, validate_examples, verify_json};
 
 #[test]
 fn empty_json() {
@@ -520,3 +520,78 @@ fn verify_interaction_deprecated_provider_state() {
   expect!(messages).to(be_equal_to(
     vec![("'providerState' is deprecated, use 'providerStates' instead", "/interactions/0")]));
 }
+
+#[test]
+fn lint_matching_rules_returns_warnings_for_dead_rules() {
+  let json = json!({
+    "consumer": { "name": "test" },
+    "provider": { "name": "test" },
+    "interactions": [
+      {
+        "description": "a request",
+        "request": {
+          "method": "GET",
+          "path": "/",
+          "query": { "page": ["1"] },
+          "matchingRules": {
+            "query": {
+              "page": { "matchers": [ { "match": "integer" } ] },
+              "size": { "matchers": [ { "match": "integer" } ] }
+            }
+          }
+        },
+        "response": {
+          "status": 200,
+          "headers": { "Content-Type": "application/json" },
+          "body": { "id": 100 },
+          "matchingRules": {
+            "body": {
+              "$.id": { "matchers": [ { "match": "integer" } ] },
+              "$.name": { "matchers": [ { "match": "type" } ] }
+            }
+          }
+        }
+      }
+    ],
+    "metadata": { "pactSpecification": { "version": "3.0.0" } }
+  });
+  let results = lint_matching_rules(&json, "");
+
+  expect!(results.iter().map(|result| (result.level.clone(), result.path.clone())).collect::<Vec<_>>()).to(be_equal_to(vec![
+    (ResultLevel::WARNING, "/interactions/0/request/matchingRules/query/size".to_string()),
+    (ResultLevel::WARNING, "/interactions/0/response/matchingRules/body/$.name".to_string())
+  ]));
+  expect!(results[1].message.clone()).to(be_equal_to(
+    "Matching rule for body path '$.name' does not match anything in the example".to_string()));
+}
+
+#[tokio::test]
+async fn validate_examples_returns_errors_for_examples_that_do_not_satisfy_their_matching_rules() {
+  let json = json!({
+    "consumer": { "name": "test" },
+    "provider": { "name": "test" },
+    "interactions": [
+      {
+        "description": "a request",
+        "request": { "method": "GET", "path": "/" },
+        "response": {
+          "status": 200,
+          "headers": { "Content-Type": "application/json" },
+          "body": { "id": "abc" },
+          "matchingRules": {
+            "body": {
+              "$.id": { "matchers": [ { "match": "regex", "regex": "\\d+" } ] }
+            }
+          }
+        }
+      }
+    ],
+    "metadata": { "pactSpecification": { "version": "3.0.0" } }
+  });
+  let results = validate_examples(&json, "").await;
+
+  expect!(results.iter().map(|result| (result.level.clone(), result.path.clone())).collect::<Vec<_>>()).to(be_equal_to(vec![
+    (ResultLevel::ERROR, "/interactions/0/response".to_string())
+  ]));
+  expect!(results[0].message.starts_with("Example does not satisfy its own matching rules - $.id -> ")).to(be_true());
+}
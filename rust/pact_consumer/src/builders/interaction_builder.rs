@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 
 use maplit::hashmap;
+use pact_mock_server::expectations::ExpectedCalls;
 use pact_models::provider_states::ProviderState;
+use pact_models::query_strings::{QUERY_STRUCTURE_COMMENT_KEY, QueryStructure};
 use pact_models::sync_interaction::RequestResponseInteraction;
 use pact_models::v4::synch_http::SynchronousHttp;
 use serde_json::{json, Value};
@@ -18,6 +20,7 @@ pub struct InteractionBuilder {
     provider_states: Vec<ProviderState>,
     comments: Vec<String>,
     test_name: Option<String>,
+    pub(crate) expected_calls: Option<ExpectedCalls>,
 
     /// A builder for this interaction's `Request`.
     pub request: RequestBuilder,
@@ -37,6 +40,7 @@ impl InteractionBuilder {
       provider_states: vec![],
       comments: vec![],
       test_name: None,
+      expected_calls: None,
       request: RequestBuilder::default(),
       response: ResponseBuilder::default(),
     }
@@ -65,6 +69,15 @@ impl InteractionBuilder {
     self
   }
 
+  /// Sets the number of times the mock server must receive the request for this interaction (by
+  /// default, it must be received at least once). Use `ExpectedCalls::Exactly(1)` to detect
+  /// duplicate requests. This is only used to configure the mock server, and is not written to
+  /// the pact file.
+  pub fn expected_calls(&mut self, expected_calls: ExpectedCalls) -> &mut Self {
+    self.expected_calls = Some(expected_calls);
+    self
+  }
+
   /// The interaction we've built.
   pub fn build(&self) -> RequestResponseInteraction {
     RequestResponseInteraction {
@@ -80,6 +93,15 @@ impl InteractionBuilder {
   pub fn build_v4(&self) -> SynchronousHttp {
     debug!("Building V4 HTTP interaction: {:?}", self);
 
+    let mut comments = hashmap!{
+      "text".to_string() => json!(self.comments),
+      "testname".to_string() => json!(self.test_name)
+    };
+    let query_structure = self.request.query_structure();
+    if query_structure != QueryStructure::default() {
+      comments.insert(QUERY_STRUCTURE_COMMENT_KEY.to_string(), query_structure.to_json());
+    }
+
     SynchronousHttp {
       id: None,
       key: None,
@@ -87,10 +109,7 @@ impl InteractionBuilder {
       provider_states: self.provider_states.clone(),
       request: self.request.build_v4(),
       response: self.response.build_v4(),
-      comments: hashmap!{
-        "text".to_string() => json!(self.comments),
-        "testname".to_string() => json!(self.test_name)
-      },
+      comments,
       pending: false,
       plugin_config: self.plugin_config(),
       interaction_markup: self.request.interaction_markup().merge(self.response.interaction_markup()),
@@ -2,9 +2,11 @@ use std::future::Future;
 use std::path::PathBuf;
 
 use async_trait::async_trait;
+use itertools::Itertools;
 use pact_models::{Consumer, Provider};
 use pact_models::interaction::Interaction;
 use pact_models::pact::Pact;
+use pact_models::query_strings::QueryStructure;
 use pact_models::sync_pact::RequestResponsePact;
 use pact_models::v4::async_message::AsynchronousMessage;
 use pact_models::v4::pact::V4Pact;
@@ -13,9 +15,11 @@ use pact_plugin_driver::catalogue_manager;
 use pact_plugin_driver::catalogue_manager::CatalogueEntryType;
 use pact_plugin_driver::plugin_manager::{drop_plugin_access, load_plugin};
 use pact_plugin_driver::plugin_models::{PluginDependency, PluginDependencyType};
-use tracing::trace;
+use tracing::{trace, warn};
 
 use pact_matching::metrics::{MetricEvent, send_metrics};
+use pact_matching::validate_interaction_examples;
+use pact_mock_server::mock_server::MockServerConfig;
 
 use crate::builders::message_builder::MessageInteractionBuilder;
 use crate::builders::message_iter::{asynchronous_messages_iter, MessageIterator, synchronous_messages_iter};
@@ -52,7 +56,9 @@ use super::interaction_builder::InteractionBuilder;
 /// ```
 pub struct PactBuilder {
   pact: Box<dyn Pact + Send + Sync>,
-  output_dir: Option<PathBuf>
+  output_dir: Option<PathBuf>,
+  mock_server_config: MockServerConfig,
+  fail_on_invalid_examples: bool
 }
 
 impl PactBuilder {
@@ -78,7 +84,12 @@ impl PactBuilder {
           pact.add_md_version("consumer", version);
         }
 
-        PactBuilder { pact: pact.boxed(), output_dir: None }
+        PactBuilder {
+          pact: pact.boxed(),
+          output_dir: None,
+          mock_server_config: MockServerConfig::default(),
+          fail_on_invalid_examples: false
+        }
     }
 
     /// Create a new `PactBuilder` for a V4 specification Pact, specifying the names of the service
@@ -101,7 +112,12 @@ impl PactBuilder {
         pact.add_md_version("consumer", version);
       }
 
-      PactBuilder { pact: pact.boxed(), output_dir: None }
+      PactBuilder {
+        pact: pact.boxed(),
+        output_dir: None,
+        mock_server_config: MockServerConfig::default(),
+        fail_on_invalid_examples: false
+      }
     }
 
     /// Add a plugin to be used by the test
@@ -131,19 +147,36 @@ impl PactBuilder {
 
     /// Add a new HTTP `Interaction` to the `Pact`. Needs to return a clone of the builder
     /// that is passed in.
+    ///
+    /// Structured query parameters (`RequestBuilder::structured_query`) only work with V4
+    /// specification pacts. If the request uses them and the pact being built is V3 format, a
+    /// warning is logged and the query parameter names are matched as is.
     pub async fn interaction<D, F, O>(&mut self, description: D, interaction_type: D, build_fn: F) -> &mut Self
     where
         D: Into<String>,
         F: FnOnce(InteractionBuilder) -> O,
         O: Future<Output=InteractionBuilder> + Send
     {
-        let interaction = InteractionBuilder::new(description.into(), interaction_type.into());
+        let description = description.into();
+        let interaction = InteractionBuilder::new(description.clone(), interaction_type.into());
         let interaction = build_fn(interaction).await;
 
+        if let Some(expected_calls) = interaction.expected_calls {
+          self.mock_server_config.expected_calls.insert(description.clone(), expected_calls);
+        }
+
         if self.pact.is_v4() {
-          self.push_interaction(&interaction.build_v4())
+          let interaction = interaction.build_v4();
+          self.validate_examples(&interaction).await;
+          self.push_interaction(&interaction)
         } else {
-          self.push_interaction(&interaction.build())
+          if interaction.request.query_structure() == QueryStructure::Nested {
+            warn!("Interaction '{}' uses structured query parameters, which require V4 specification pacts. \
+              The query parameter names will be matched as is", description);
+          }
+          let interaction = interaction.build();
+          self.validate_examples(&interaction).await;
+          self.push_interaction(&interaction)
         }
     }
 
@@ -167,6 +200,28 @@ impl PactBuilder {
     self
   }
 
+  /// Panics when an interaction is added if its examples do not satisfy its own matching rules.
+  /// By default, a warning is logged instead.
+  pub fn fail_on_invalid_examples(&mut self, fail: bool) -> &mut Self {
+    self.fail_on_invalid_examples = fail;
+    self
+  }
+
+  /// Requires the mock server to receive the requests in the order the interactions were added.
+  /// Any request received after a request for a later interaction will fail the test.
+  pub fn strict_ordering(&mut self, strict_ordering: bool) -> &mut Self {
+    self.mock_server_config.strict_ordering = strict_ordering;
+    self
+  }
+
+  /// Plugin mock servers are not started with the mock server config, so fail the test instead
+  /// of silently ignoring the expected calls or call ordering
+  fn check_plugin_mock_server_config(&self) {
+    if !self.mock_server_config.expected_calls.is_empty() || self.mock_server_config.strict_ordering {
+      panic!("Expected calls and strict ordering are not supported by plugin mock servers");
+    }
+  }
+
   /// Add a new Asynchronous message `Interaction` to the `Pact`. Needs to return a clone of the builder
   /// that is passed in.
   pub async fn message_interaction<D, F, O>(&mut self, description: D, build_fn: F) -> &mut Self
@@ -183,7 +238,9 @@ impl PactBuilder {
                            Some(plugin_data.configuration.clone()));
     }
 
-    self.push_interaction(&interaction.build())
+    let interaction = interaction.build();
+    self.validate_examples(&interaction).await;
+    self.push_interaction(&interaction)
   }
 
 
@@ -203,7 +260,33 @@ impl PactBuilder {
                                    Some(plugin_data.configuration.clone()));
     }
 
-    self.push_interaction(&interaction.build())
+    let interaction = interaction.build();
+    self.validate_examples(&interaction).await;
+    self.push_interaction(&interaction)
+  }
+
+  /// Checks that the examples in the interaction satisfy its own matching rules, as otherwise the
+  /// provider verification will fail. Interactions for Pacts that use plugins are not checked.
+  ///
+  /// # Panics
+  ///
+  /// Panics if any of the example values do not match their matching rules and
+  /// `fail_on_invalid_examples` is set, otherwise a warning is logged.
+  async fn validate_examples(&self, interaction: &dyn Interaction) {
+    if self.pact.plugin_data().is_empty() {
+      let mismatches = validate_interaction_examples(&interaction.boxed(), &self.pact).await;
+      if !mismatches.is_empty() {
+        let message = format!("The examples in interaction '{}' do not satisfy its own matching rules:\n{}",
+          interaction.description(), mismatches.iter()
+            .map(|(part, mismatch)| format!("  {}: {}", part, mismatch.description()))
+            .join("\n"));
+        if self.fail_on_invalid_examples {
+          panic!("{}", message);
+        } else {
+          warn!("{}", message);
+        }
+      }
+    }
   }
 
   /// Returns an iterator over the asynchronous messages in the Pact
@@ -235,6 +318,7 @@ impl StartMockServer for PactBuilder {
     match catalog_entry {
       Some(entry_name) => match catalogue_manager::lookup_entry(entry_name) {
         Some(entry) => if entry.entry_type == CatalogueEntryType::TRANSPORT {
+          self.check_plugin_mock_server_config();
           PluginMockServer::start(self.build(), self.output_dir.clone(), &entry)
             .expect("Could not start the plugin mock server")
         } else {
@@ -242,7 +326,8 @@ impl StartMockServer for PactBuilder {
         }
         None => panic!("Did not find a catalogue entry for key '{}'", entry_name)
       }
-      None => ValidatingHttpMockServer::start(self.build(), self.output_dir.clone())
+      None => ValidatingHttpMockServer::start_with_config(self.build(), self.output_dir.clone(),
+        self.mock_server_config.clone())
     }
   }
 
@@ -250,6 +335,7 @@ impl StartMockServer for PactBuilder {
     match catalog_entry {
       Some(entry_name) => match catalogue_manager::lookup_entry(entry_name) {
         Some(entry) => if entry.entry_type == CatalogueEntryType::TRANSPORT {
+          self.check_plugin_mock_server_config();
           PluginMockServer::start_async(self.build(), self.output_dir.clone(), &entry).await
             .expect("Could not start the plugin mock server")
         } else {
@@ -257,7 +343,8 @@ impl StartMockServer for PactBuilder {
         }
         None => panic!("Did not find a catalogue entry for key '{}'", entry_name)
       }
-      None => ValidatingHttpMockServer::start_async(self.build(), self.output_dir.clone()).await
+      None => ValidatingHttpMockServer::start_async_with_config(self.build(), self.output_dir.clone(),
+        self.mock_server_config.clone()).await
     }
   }
 }
@@ -13,6 +13,7 @@ use pact_models::http_parts::HttpPart;
 use pact_models::json_utils::body_from_json;
 use pact_models::matchingrules::{Category, MatchingRules};
 use pact_models::path_exp::DocPath;
+use pact_models::query_strings::{flatten_structured_query, QueryStructure};
 use pact_models::request::Request;
 use pact_models::v4::http_parts::HttpRequest;
 use pact_models::v4::interaction::InteractionMarkup;
@@ -33,7 +34,8 @@ use crate::util::GetDefaulting;
 pub struct RequestBuilder {
   request: HttpRequest,
   plugin_config: HashMap<String, PluginConfiguration>,
-  interaction_markup: InteractionMarkup
+  interaction_markup: InteractionMarkup,
+  query_structure: QueryStructure
 }
 
 impl RequestBuilder {
@@ -142,6 +144,35 @@ impl RequestBuilder {
         self
     }
 
+    /// Specify the query parameters as a nested structure, which will be encoded using the Rails
+    /// and qs conventions for the parameter names (i.e. `filter[status]=open&ids[]=1&ids[]=2`).
+    /// Any matching rules in the pattern are applied to the nested values (i.e. `$.filter.status`).
+    /// This replaces any query parameters set with `query_param`. Structured query parameters are
+    /// only supported by V4 pacts, with V3 pacts a warning is logged and the parameter names are
+    /// matched as is.
+    ///
+    /// ```
+    /// use pact_consumer::*;
+    /// use pact_consumer::builders::RequestBuilder;
+    /// use regex::Regex;
+    ///
+    /// RequestBuilder::default()
+    ///     .structured_query(json_pattern!({
+    ///         "filter": { "status": term!("^(open|closed)$", "open") },
+    ///         "ids": each_like!("1")
+    ///     }));
+    /// ```
+    pub fn structured_query<Q: Into<JsonPattern>>(&mut self, query: Q) -> &mut Self {
+        let query = query.into();
+        self.request.query = Some(flatten_structured_query(&query.to_example()));
+        self.query_structure = QueryStructure::Nested;
+        query.extract_matching_rules(
+            DocPath::root(),
+            self.request.matching_rules.add_category("query"),
+        );
+        self
+    }
+
     /// Build the specified `Request` object.
     pub fn build(&self) -> Request {
          self.request.as_v3_request()
@@ -237,6 +268,10 @@ impl RequestBuilder {
   pub(crate) fn interaction_markup(&self) -> InteractionMarkup {
     self.interaction_markup.clone()
   }
+
+  pub(crate) fn query_structure(&self) -> QueryStructure {
+    self.query_structure
+  }
 }
 
 impl Default for RequestBuilder {
@@ -244,7 +279,8 @@ impl Default for RequestBuilder {
         RequestBuilder {
           request: HttpRequest::default(),
           plugin_config: Default::default(),
-          interaction_markup: Default::default()
+          interaction_markup: Default::default(),
+          query_structure: Default::default()
         }
     }
 }
@@ -339,6 +375,67 @@ async fn query_param_pattern() {
     assert_requests_do_not_match!(bad, pattern);
 }
 
+#[tokio::test]
+async fn structured_query_pattern() {
+    let pattern = PactBuilder::new_v4("C", "P")
+        .interaction("I", "", |mut i| {
+            i.request.structured_query(json_pattern!({
+                "filter": { "status": term!("^(open|closed)$", "open"), "owner": "me" },
+                "ids": each_like!("1")
+            }));
+            futures::future::ready(i)
+        })
+        .await
+        .build();
+    let good = PactBuilder::new_v4("C", "P")
+        .interaction("I", "", |mut i| {
+            i.request
+                .query_param("filter[owner]", "me")
+                .query_param("filter[status]", "closed")
+                .query_param("ids[]", "2")
+                .query_param("ids[]", "3");
+            futures::future::ready(i)
+        })
+        .await
+        .build();
+    let bad = PactBuilder::new_v4("C", "P")
+        .interaction("I", "", |mut i| {
+            i.request
+                .query_param("filter[owner]", "me")
+                .query_param("filter[status]", "pending")
+                .query_param("ids", "2");
+            futures::future::ready(i)
+        })
+        .await
+        .build();
+    assert_requests_match!(good, pattern);
+    assert_requests_do_not_match!(bad, pattern);
+}
+
+#[tokio::test]
+async fn structured_query_is_only_recorded_in_v4_pacts() {
+    let v3 = PactBuilder::new("C", "P")
+        .interaction("I", "", |mut i| {
+            i.request.structured_query(json_pattern!({ "filter": { "status": "open" } }));
+            futures::future::ready(i)
+        })
+        .await
+        .build();
+    let interaction = v3.interactions()[0].as_request_response().unwrap();
+    assert_eq!(interaction.request.query, Some(hashmap!{ "filter[status]".to_string() => vec!["open".to_string()] }));
+
+    let v4 = PactBuilder::new_v4("C", "P")
+        .interaction("I", "", |mut i| {
+            i.request.structured_query(json_pattern!({ "filter": { "status": "open" } }));
+            futures::future::ready(i)
+        })
+        .await
+        .build();
+    let interaction = v4.interactions()[0].as_v4_http().unwrap();
+    assert_eq!(interaction.request.query, Some(hashmap!{ "filter[status]".to_string() => vec!["open".to_string()] }));
+    assert_eq!(interaction.comments.get("queryStructure"), Some(&json!("nested")));
+}
+
 #[tokio::test]
 async fn query_param_with_underscore() {
     let pattern = PactBuilder::new("C", "P")
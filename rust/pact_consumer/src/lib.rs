@@ -407,9 +407,10 @@ pub mod util;
 pub mod prelude {
     pub use crate::builders::{HttpPartBuilder, PactBuilder};
     pub use crate::mock_server::{StartMockServer, ValidatingMockServer};
+    pub use pact_mock_server::expectations::ExpectedCalls;
     pub use crate::patterns::{EachLike, Like, Term};
     pub use crate::patterns::{JsonPattern, Pattern, StringPattern};
-    pub use crate::patterns::{DateTime};
+    pub use crate::patterns::{Approximately, DateTime};
     pub use crate::util::strip_null_fields;
 }
 
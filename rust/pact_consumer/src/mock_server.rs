@@ -5,6 +5,7 @@ use pact_models::pact::Pact;
 use pact_models::sync_pact::RequestResponsePact;
 use url::Url;
 
+use pact_mock_server::journal::JournalEntry;
 use pact_mock_server::matching::MatchResult;
 use pact_mock_server::mock_server::MockServerMetrics;
 
@@ -36,6 +37,12 @@ pub trait ValidatingMockServer {
 
   /// Returns the metrics collected by the mock server
   fn metrics(&self) -> MockServerMetrics;
+
+  /// Returns the journal of all the requests received by the mock server, in the order they were
+  /// received. Mock servers provided by plugins do not record a journal, so this will be empty.
+  fn journal(&self) -> Vec<JournalEntry> {
+    vec![]
+  }
 }
 
 /// This trait is implemented by types which allow us to start a mock server.
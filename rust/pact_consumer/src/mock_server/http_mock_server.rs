@@ -11,6 +11,7 @@ use url::Url;
 use uuid::Uuid;
 
 use pact_matching::metrics::{MetricEvent, send_metrics};
+use pact_mock_server::journal::JournalEntry;
 use pact_mock_server::matching::MatchResult;
 use pact_mock_server::mock_server;
 use pact_mock_server::mock_server::{MockServerConfig, MockServerMetrics};
@@ -44,11 +45,24 @@ impl ValidatingHttpMockServer {
   /// Panics:
   /// Will panic if the provided Pact can not be sent to the background thread.
   pub fn start(pact: Box<dyn Pact + Send + Sync>, output_dir: Option<PathBuf>) -> Box<dyn ValidatingMockServer> {
+    Self::start_with_config(pact, output_dir, MockServerConfig::default())
+  }
+
+  /// Create a new mock server with the given configuration, which handles requests as described
+  /// in the pact, and runs in a background thread
+  ///
+  /// Panics:
+  /// Will panic if the provided Pact can not be sent to the background thread.
+  pub fn start_with_config(
+    pact: Box<dyn Pact + Send + Sync>,
+    output_dir: Option<PathBuf>,
+    config: MockServerConfig
+  ) -> Box<dyn ValidatingMockServer> {
     debug!("Starting mock server from pact {:?}", pact);
     // Spawn new runtime in thread to prevent reactor execution context conflict
     let (pact_tx, pact_rx) = std::sync::mpsc::channel::<Box<dyn Pact + Send + Sync>>();
     pact_tx.send(pact).expect("INTERNAL ERROR: Could not pass pact into mock server thread");
-    let (mock_server, done_rx) = std::thread::spawn(|| {
+    let (mock_server, done_rx) = std::thread::spawn(move || {
       let runtime = tokio::runtime::Builder::new_current_thread()
         .enable_all()
         .build()
@@ -59,7 +73,7 @@ impl ValidatingHttpMockServer {
           Uuid::new_v4().to_string(),
           pact_rx.recv().unwrap(),
           ([0, 0, 0, 0], 0).into(),
-          MockServerConfig::default()
+          config
         )
           .await
           .unwrap()
@@ -107,13 +121,26 @@ impl ValidatingHttpMockServer {
   /// Panics:
   /// Will panic if unable to get the URL to the spawned mock server
   pub async fn start_async(pact: Box<dyn Pact + Send + Sync>, output_dir: Option<PathBuf>) -> Box<dyn ValidatingMockServer> {
+    Self::start_async_with_config(pact, output_dir, MockServerConfig::default()).await
+  }
+
+  /// Create a new mock server with the given configuration, which handles requests as described
+  /// in the pact, and runs in a background task in the current Tokio runtime.
+  ///
+  /// Panics:
+  /// Will panic if unable to get the URL to the spawned mock server
+  pub async fn start_async_with_config(
+    pact: Box<dyn Pact + Send + Sync>,
+    output_dir: Option<PathBuf>,
+    config: MockServerConfig
+  ) -> Box<dyn ValidatingMockServer> {
     debug!("Starting mock server from pact {:?}", pact);
 
     let (mock_server, server_future) = mock_server::MockServer::new(
       Uuid::new_v4().to_string(),
       pact,
       ([0, 0, 0, 0], 0 as u16).into(),
-      MockServerConfig::default()
+      config
     )
       .await
       .unwrap();
@@ -212,6 +239,18 @@ impl ValidatingHttpMockServer {
             );
             let _ = writeln!(&mut msg, "{:#?}", request);
           }
+          MatchResult::IncorrectCallCount(request, expected, calls) => {
+            let _ = writeln!(
+              &mut msg,
+              "- request {} expected {}, but was received {} time(s)", request, expected, calls,
+            );
+          }
+          MatchResult::RequestOutOfOrder(request, after) => {
+            let _ = writeln!(
+              &mut msg,
+              "- request {} was received out of order, after request {}", request, after,
+            );
+          }
         }
       }
       Err(msg)
@@ -238,6 +277,10 @@ impl ValidatingMockServer for ValidatingHttpMockServer {
   fn metrics(&self) -> MockServerMetrics {
     self.mock_server.lock().unwrap().metrics.clone()
   }
+
+  fn journal(&self) -> Vec<JournalEntry> {
+    self.mock_server.lock().unwrap().journal()
+  }
 }
 
 impl Drop for ValidatingHttpMockServer {
@@ -13,11 +13,14 @@ mod special_rules;
 mod string_pattern;
 #[macro_use]
 mod date_time;
+#[macro_use]
+mod numbers;
 
 pub use self::json_pattern::*;
 pub use self::special_rules::*;
 pub use self::string_pattern::*;
 pub use self::date_time::*;
+pub use self::numbers::*;
 
 /// Abstract interface to types which can:
 ///
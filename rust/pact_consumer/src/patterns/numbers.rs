@@ -0,0 +1,128 @@
+//! Matchers for matching numbers
+
+use std::marker::PhantomData;
+
+use pact_models::matchingrules::{MatchingRule, MatchingRuleCategory, NumberTolerance, RuleLogic};
+use pact_models::path_exp::DocPath;
+use serde_json::json;
+
+use crate::patterns::{JsonPattern, Pattern, StringPattern};
+
+/// Match numbers that are within a tolerance of the example number.
+#[derive(Debug)]
+pub struct Approximately<Nested: Pattern> {
+  /// The example number we generate when asked.
+  example: f64,
+  /// The tolerance the actual number must be within.
+  tolerance: NumberTolerance,
+  /// Since we always store `example` as a number, we need to mention our
+  /// `Nested` type somewhere. We can do that using the zero-length
+  /// `PhantomData` type.
+  phantom: PhantomData<Nested>
+}
+
+impl <Nested: Pattern> Approximately<Nested> {
+  /// Construct a new `Approximately` that matches numbers within the absolute tolerance of the
+  /// example (i.e. `Approximately::absolute(10.5, 0.01)` will match from 10.49 to 10.51).
+  pub fn absolute<E: Into<f64>>(example: E, tolerance: f64) -> Self {
+    Approximately {
+      example: example.into(),
+      tolerance: NumberTolerance::Absolute(tolerance),
+      phantom: PhantomData
+    }
+  }
+
+  /// Construct a new `Approximately` that matches numbers within the relative tolerance of the
+  /// example (i.e. `Approximately::relative(200, 0.05)` will match from 190 to 210).
+  pub fn relative<E: Into<f64>>(example: E, tolerance: f64) -> Self {
+    Approximately {
+      example: example.into(),
+      tolerance: NumberTolerance::Relative(tolerance),
+      phantom: PhantomData
+    }
+  }
+}
+
+impl Pattern for Approximately<JsonPattern> {
+  type Matches = serde_json::Value;
+
+  fn to_example(&self) -> Self::Matches {
+    json!(self.example)
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    rules_out.add_rule(path, MatchingRule::Approximately(self.tolerance), RuleLogic::And);
+  }
+}
+
+impl Pattern for Approximately<StringPattern> {
+  type Matches = String;
+
+  fn to_example(&self) -> Self::Matches {
+    self.example.to_string()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    rules_out.add_rule(path, MatchingRule::Approximately(self.tolerance), RuleLogic::And);
+  }
+}
+
+#[test]
+fn approximately_is_pattern() {
+  use serde_json::*;
+  use expectest::prelude::*;
+
+  let matchable = Approximately::<JsonPattern>::absolute(10.5, 0.01);
+  expect!(matchable.to_example()).to(be_equal_to(json!(10.5)));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  let expected_rules = json!({
+    "$": {
+      "combine": "AND", "matchers": [
+        { "match": "approximately", "absolute": 0.01 }
+      ]
+    }
+  });
+  expect!(rules.to_v3_json()).to(be_equal_to(expected_rules));
+
+  let matchable = Approximately::<StringPattern>::relative(200, 0.05);
+  expect!(matchable.to_example()).to(be_equal_to("200"));
+}
+
+impl_from_for_pattern!(Approximately<JsonPattern>, JsonPattern);
+impl_from_for_pattern!(Approximately<StringPattern>, StringPattern);
+
+#[test]
+fn approximately_into() {
+  // Make sure we can convert `Approximately` into different pattern types.
+  let _: JsonPattern = Approximately::absolute(10.5, 0.01).into();
+  let _: StringPattern = Approximately::relative(10.5, 0.01).into();
+}
+
+/// A pattern which matches numbers within a tolerance of `$example`, and which generates `$example`.
+/// The tolerance is either `absolute = $tolerance` or `relative = $tolerance` (a fraction of the example).
+///
+/// ```
+/// use pact_consumer::*;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "price": approximately!(10.5, absolute = 0.01),
+///   "temperature": approximately!(21.5, relative = 0.05)
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! approximately {
+  ($example:expr, absolute = $tolerance:expr) => {
+    {
+      $crate::patterns::Approximately::absolute($example, $tolerance)
+    }
+  };
+  ($example:expr, relative = $tolerance:expr) => {
+    {
+      $crate::patterns::Approximately::relative($example, $tolerance)
+    }
+  }
+}
@@ -11,7 +11,7 @@ use pact_models::sync_pact::RequestResponsePact;
 use rand::prelude::*;
 use reqwest::Client;
 
-use pact_consumer::{json_pattern, json_pattern_internal};
+use pact_consumer::{json_pattern, json_pattern_internal, term};
 use pact_consumer::prelude::*;
 
 /// This is supposed to be a doctest in mod, but it's breaking there, so
@@ -100,6 +100,105 @@ async fn mock_server_failing_validation() {
     let _ = reqwest::blocking::get(url);
 }
 
+#[tokio::test]
+#[should_panic]
+async fn mock_server_failing_validation_with_duplicate_requests() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let order_service = PactBuilder::new_v4("Order Client", "Order Server")
+        .interaction("create an order", "", |mut i| async move {
+          i.request.post().path("/orders");
+          i.response.created();
+          i.expected_calls(ExpectedCalls::Exactly(1));
+          i.clone()
+        })
+      .await
+      .start_mock_server(None);
+    // Send the request twice, which should lead to a panic at the end of the function.
+    let client = Client::new();
+    let _ = client.post(order_service.path("/orders")).send().await;
+    let _ = client.post(order_service.path("/orders")).send().await;
+}
+
+#[tokio::test]
+#[should_panic]
+async fn mock_server_failing_validation_with_requests_out_of_order() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let order_service = PactBuilder::new("Order Client", "Order Server")
+        .strict_ordering(true)
+        .interaction("create an order", "", |mut i| async move {
+          i.request.post().path("/orders");
+          i.response.created();
+          i.clone()
+        })
+      .await
+        .interaction("get the orders", "", |mut i| async move {
+          i.request.path("/orders");
+          i.clone()
+        })
+      .await
+      .start_mock_server(None);
+    // Get the orders before creating one, which should lead to a panic at the end of the function.
+    let client = Client::new();
+    let _ = client.get(order_service.path("/orders")).send().await;
+    let _ = client.post(order_service.path("/orders")).send().await;
+}
+
+#[tokio::test]
+#[should_panic(expected = "The examples in interaction 'request an order' do not satisfy its own matching rules")]
+async fn interaction_with_examples_that_do_not_satisfy_the_matching_rules() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    PactBuilder::new("Order Client", "Order Server")
+        .fail_on_invalid_examples(true)
+        .interaction("request an order", "", |mut i| async move {
+          i.request.path("/orders/1");
+          i.response.json_body(json_pattern!({
+            "id": term!("^\\d+$", "abc")
+          }));
+          i.clone()
+        })
+      .await;
+}
+
+#[tokio::test]
+async fn interaction_with_examples_that_do_not_satisfy_the_matching_rules_only_logs_a_warning_by_default() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let mut pact_builder = PactBuilder::new("Order Client", "Order Server");
+    pact_builder
+        .interaction("request an order", "", |mut i| async move {
+          i.request.path("/orders/1");
+          i.response.json_body(json_pattern!({
+            "id": term!("^\\d+$", "abc")
+          }));
+          i.clone()
+        })
+      .await;
+    expect!(pact_builder.build().interactions().len()).to(be_equal_to(1));
+}
+
+#[tokio::test]
+async fn mock_server_records_a_journal_of_the_requests() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let hello_service = PactBuilder::new("Hello CLI", "Hello Server")
+        .interaction("request a greeting", "", |mut i| async move {
+          i.request.path("/hello");
+          i.response.body("Hello!");
+          i.clone()
+        })
+      .await
+      .start_mock_server(None);
+    let _ = reqwest::get(hello_service.path("/hello")).await;
+
+    let journal = hello_service.journal();
+    expect!(journal.len()).to(be_equal_to(1));
+    expect!(journal[0].request.path.as_str()).to(be_equal_to("/hello"));
+    expect!(journal[0].interaction.clone()).to(be_some().value("request a greeting"));
+}
+
 #[tokio::test]
 async fn duplicate_interactions() {
   let _ = env_logger::builder().is_test(true).try_init();
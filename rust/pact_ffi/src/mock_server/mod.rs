@@ -22,6 +22,17 @@
 //! code using the mock server is complete. The [`cleanup_mock_server`](fn.cleanup_mock_server.html) function is provided for this purpose.
 //! If the mock server is not cleaned up properly, this will result in memory leaks as the Rust heap will not be reclaimed.
 //!
+//! ## [mock_server_journal](fn.pactffi_mock_server_journal.html)
+//!
+//! This returns the journal of all the requests received by the mock server in JSON format, given the port number of
+//! the mock server. Each entry has the time the request was received, the request, the interaction that matched it (if
+//! any) and the response that was sent. The same memory rules apply as for `mock_server_mismatches`.
+//!
+//! ## [mock_server_clear_journal](fn.pactffi_mock_server_clear_journal.html)
+//!
+//! Clears the journal of the requests received by the mock server with the given port number. Returns `true`, unless
+//! a mock server with the given port number does not exist, or the function fails in some way.
+//!
 //! ## [cleanup_mock_server](fn.cleanup_mock_server.html)
 //!
 //! This function will try terminate the mock server with the given port number and cleanup any memory allocated for it by
@@ -64,7 +75,7 @@ use tracing::error;
 use uuid::Uuid;
 
 use pact_matching::logging::fetch_buffer_contents;
-use pact_mock_server::{MANAGER, mock_server_mismatches, MockServerError, tls::TlsConfigBuilder, WritePactFileErr};
+use pact_mock_server::{clear_mock_server_journal, MANAGER, mock_server_journal, mock_server_mismatches, MockServerError, tls::TlsConfigBuilder, WritePactFileErr};
 use pact_mock_server::mock_server::MockServerConfig;
 use pact_mock_server::server_manager::ServerManager;
 
@@ -413,6 +424,62 @@ pub extern fn pactffi_mock_server_mismatches(mock_server_port: i32) -> *mut c_ch
   }
 }
 
+/// External interface to get the journal of all the requests received by a mock server. The port
+/// number of the mock server is passed in, and a pointer to a C string with the journal entries in
+/// JSON format is returned. The entries are in the order the requests were received.
+///
+/// **NOTE:** The JSON string for the result is allocated on the heap, and will have to be freed
+/// once the code using the mock server is complete. The [`cleanup_mock_server`](fn.cleanup_mock_server.html) function is
+/// provided for this purpose.
+///
+/// # Errors
+///
+/// If there is no mock server with the provided port number, or the function panics, a NULL
+/// pointer will be returned. Don't try to dereference it, it will not end well for you.
+///
+#[no_mangle]
+pub extern fn pactffi_mock_server_journal(mock_server_port: i32) -> *mut c_char {
+  let result = catch_unwind(|| {
+    let result = mock_server_journal(mock_server_port);
+    match result {
+      Some(str) => {
+        let s = CString::new(str).unwrap();
+        let p = s.as_ptr() as *mut _;
+        MANAGER.lock().unwrap()
+          .get_or_insert_with(ServerManager::new)
+          .store_mock_server_resource(mock_server_port as u16, s);
+        p
+      },
+      None => std::ptr::null_mut()
+    }
+  });
+
+  match result {
+    Ok(val) => val,
+    Err(cause) => {
+      error!("{}", error_message(cause, "mock_server_journal"));
+      std::ptr::null_mut()
+    }
+  }
+}
+
+/// External interface to clear the journal of the requests received by a mock server. Returns
+/// true, unless a mock server with the given port number does not exist, or the function panics.
+#[no_mangle]
+pub extern fn pactffi_mock_server_clear_journal(mock_server_port: i32) -> bool {
+  let result = catch_unwind(|| {
+    clear_mock_server_journal(mock_server_port)
+  });
+
+  match result {
+    Ok(val) => val,
+    Err(cause) => {
+      error!("{}", error_message(cause, "mock_server_clear_journal"));
+      false
+    }
+  }
+}
+
 /// External interface to cleanup a mock server. This function will try terminate the mock server
 /// with the given port number and cleanup any memory allocated for it. Returns true, unless a
 /// mock server with the given port number does not exist, or the function panics.
@@ -11,6 +11,8 @@ use pact_ffi::mock_server::{
   pactffi_cleanup_mock_server,
   pactffi_create_mock_server,
   pactffi_create_mock_server_for_pact,
+  pactffi_mock_server_clear_journal,
+  pactffi_mock_server_journal,
   pactffi_mock_server_mismatches,
   pactffi_write_pact_file
 };
@@ -55,9 +57,23 @@ fn post_to_mock_server_with_mismatches() {
   let mismatches = unsafe {
     CStr::from_ptr(pactffi_mock_server_mismatches(port)).to_string_lossy().into_owned()
   };
+  let journal = unsafe {
+    CStr::from_ptr(pactffi_mock_server_journal(port)).to_string_lossy().into_owned()
+  };
+  let cleared = pactffi_mock_server_clear_journal(port);
+  let cleared_journal = unsafe {
+    CStr::from_ptr(pactffi_mock_server_journal(port)).to_string_lossy().into_owned()
+  };
 
   pactffi_cleanup_mock_server(port);
 
+  let journal: serde_json::Value = serde_json::from_str(journal.as_str()).unwrap();
+  expect!(journal.as_array().map(|entries| entries.len())).to(be_some().value(1));
+  expect!(&journal[0]["request"]["path"]).to(be_equal_to(&serde_json::json!("/path")));
+  expect!(&journal[0]["result"]).to(be_equal_to(&serde_json::json!("Request-Mismatch")));
+  expect!(&journal[0]["response"]["status"]).to(be_equal_to(&serde_json::json!(500)));
+  expect!(cleared).to(be_true());
+  expect!(cleared_journal).to(be_equal_to("[]"));
   expect!(mismatches).to(be_equal_to("[{\"method\":\"POST\",\"mismatches\":[{\"actual\":\"\\\"no-very-bar\\\"\",\"expected\":\"\\\"bar\\\"\",\"mismatch\":\"Expected \'bar\' to be equal to \'no-very-bar\'\",\"path\":\"$.foo\",\"type\":\"BodyMismatch\"}],\"path\":\"/path\",\"type\":\"request-mismatch\"}]"));
 }
 
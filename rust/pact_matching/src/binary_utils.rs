@@ -284,7 +284,7 @@ impl Matches<&MimeFile> for &MimeFile {
           Err(err) => Err(anyhow!("'{}' is not a valid regular expression - {}", regex, err))
         }
       },
-      MatchingRule::Equality => {
+      MatchingRule::Equality | MatchingRule::EqualsIgnoreOrder => {
         if self.data == actual.data {
           Ok(())
         } else {
@@ -0,0 +1,402 @@
+//!
+//! Support for explaining how the matching rules were applied during matching (explain mode).
+//!
+//! When a matching context has an `Explainer` (see `ExplainingMatchingContext`), every path that
+//! is visited while matching will
+//! record the candidate matching rules (all the rules with a path expression that matches the
+//! path, along with their calculated weight), the rules that were selected and the outcome of
+//! matching the path.
+//!
+//! As the explanation records every rule that was a candidate for a path, it can also be used to
+//! find the matching rules that were never applied while matching (see `MatchExplanation::unused_rules`).
+//!
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::sync::{Arc, Mutex};
+
+use itertools::Itertools;
+use pact_models::matchingrules::{Category, MatchingRule, MatchingRuleCategory, MatchingRules, RuleList};
+use pact_models::path_exp::DocPath;
+use serde_json::{json, Value};
+
+use crate::{CoreMatchingContext, DiffConfig, MatchingContext, Mismatch, PluginInteractionConfig};
+
+/// Matching rule that could apply to a path
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandidateRule {
+  /// Path expression of the matching rule
+  pub path: String,
+  /// Calculated weight of the path expression for the path
+  pub weight: usize,
+  /// Matching rules configured for the path expression
+  pub rules: RuleList
+}
+
+/// Entry in a match explanation for a single path
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExplanationEntry {
+  /// Category of the item being matched (body, header, query, etc.)
+  pub category: String,
+  /// Path that was matched
+  pub path: String,
+  /// Matching rules with path expressions that matched the path
+  pub candidates: Vec<CandidateRule>,
+  /// Matching rules that were selected. If this is `None`, the default comparison for the item was
+  /// used (normally equality).
+  pub selected: Option<RuleList>,
+  /// Mismatches that resulted from matching the path. This will be empty if the path matched.
+  pub mismatches: Vec<String>
+}
+
+impl ExplanationEntry {
+  /// If the path matched
+  pub fn matched(&self) -> bool {
+    self.mismatches.is_empty()
+  }
+
+  /// Converts this entry to a JSON value
+  pub fn to_json(&self) -> Value {
+    json!({
+      "category": self.category,
+      "path": self.path,
+      "candidates": self.candidates.iter().map(|candidate| json!({
+        "path": candidate.path,
+        "weight": candidate.weight,
+        "rules": rules_to_json(&candidate.rules.rules)
+      })).collect_vec(),
+      "selected": self.selected.as_ref().map(|rules| json!({
+        "rules": rules_to_json(&rules.rules),
+        "cascaded": rules.cascaded
+      })),
+      "matched": self.matched(),
+      "mismatches": self.mismatches
+    })
+  }
+}
+
+fn rules_to_json(rules: &[MatchingRule]) -> Vec<Value> {
+  rules.iter().map(|rule| rule.to_json()).collect()
+}
+
+fn rules_to_string(rules: &RuleList) -> String {
+  rules.rules.iter().map(|rule| rule.to_json().to_string()).join(", ")
+}
+
+impl Display for ExplanationEntry {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    if self.path.is_empty() {
+      writeln!(f, "[{}]", self.category)?;
+    } else {
+      writeln!(f, "[{}] {}", self.category, self.path)?;
+    }
+    if self.candidates.is_empty() {
+      writeln!(f, "  candidates: none")?;
+    } else {
+      writeln!(f, "  candidates:")?;
+      for candidate in &self.candidates {
+        writeln!(f, "    {} (weight {}): {}", candidate.path, candidate.weight, rules_to_string(&candidate.rules))?;
+      }
+    }
+    match &self.selected {
+      Some(rules) if rules.cascaded => writeln!(f, "  selected: {} (cascaded from parent)", rules_to_string(rules))?,
+      Some(rules) => writeln!(f, "  selected: {}", rules_to_string(rules))?,
+      None => writeln!(f, "  selected: none (default comparison)")?
+    }
+    if self.matched() {
+      write!(f, "  outcome: OK")
+    } else {
+      write!(f, "  outcome: FAILED")?;
+      for mismatch in &self.mismatches {
+        write!(f, "\n    {}", mismatch)?;
+      }
+      Ok(())
+    }
+  }
+}
+
+/// Explanation of how the matching rules were applied while matching, in the order the paths were
+/// visited
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MatchExplanation {
+  /// Entry for each path that was visited
+  pub entries: Vec<ExplanationEntry>
+}
+
+impl MatchExplanation {
+  /// Converts this explanation to a JSON value
+  pub fn to_json(&self) -> Value {
+    Value::Array(self.entries.iter().map(|entry| entry.to_json()).collect())
+  }
+
+  /// Returns the matching rules that were not a candidate for any of the paths visited while
+  /// matching. Only categories that have entries in this explanation are checked, as the others
+  /// were not matched.
+  pub fn unused_rules(&self, rules: &MatchingRules) -> Vec<(Category, DocPath)> {
+    rules.rules.iter()
+      .filter(|(category, _)| {
+        let name: String = (*category).clone().into();
+        self.entries.iter().any(|entry| entry.category == name)
+      })
+      .flat_map(|(category, rules)| {
+        let name: String = category.clone().into();
+        rules.rules.keys()
+          .filter(|path| {
+            let path = path.to_string();
+            !self.entries.iter()
+              .filter(|entry| entry.category == name)
+              .any(|entry| entry.candidates.iter().any(|candidate| candidate.path == path))
+          })
+          .map(|path| (category.clone(), path.clone()))
+          .collect_vec()
+      })
+      .sorted_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.to_string().cmp(&b.1.to_string())))
+      .collect()
+  }
+}
+
+impl Display for MatchExplanation {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.entries.iter().map(|entry| entry.to_string()).join("\n"))
+  }
+}
+
+/// Records how the matching rules were applied while matching. Clones of an explainer share the
+/// same recorded entries, so a single explainer can be used with the matching contexts for each
+/// category.
+#[derive(Debug, Clone, Default)]
+pub struct Explainer {
+  category: String,
+  entries: Arc<Mutex<Vec<ExplanationEntry>>>
+}
+
+impl Explainer {
+  /// Creates a new explainer
+  pub fn new() -> Self {
+    Explainer::default()
+  }
+
+  /// Returns a copy of this explainer that will record entries for the given category
+  pub fn for_category(&self, category: &str) -> Self {
+    Explainer {
+      category: category.to_string(),
+      entries: self.entries.clone()
+    }
+  }
+
+  /// Returns the explanation with all the entries recorded so far
+  pub fn explanation(&self) -> MatchExplanation {
+    MatchExplanation {
+      entries: self.entries.lock().unwrap().clone()
+    }
+  }
+
+  fn start(&self, context: &dyn MatchingContext, path: &DocPath) -> usize {
+    let path_vec = path.to_vec();
+    let path_slice = path_vec.iter().map(|p| p.as_str()).collect_vec();
+    let candidates = context.matchers().resolve_matchers_for_path(path_slice.as_slice()).rules.iter()
+      .map(|(path_exp, rules)| {
+        let (weight, tokens) = path_exp.path_weight(path_slice.as_slice());
+        CandidateRule {
+          path: path_exp.to_string(),
+          weight: weight * tokens,
+          rules: rules.clone()
+        }
+      })
+      .sorted_by(|a, b| b.weight.cmp(&a.weight).then_with(|| a.path.cmp(&b.path)))
+      .collect();
+    let selected = if context.matcher_is_defined(path) {
+      Some(context.select_best_matcher(path))
+    } else {
+      None
+    };
+
+    let mut entries = self.entries.lock().unwrap();
+    entries.push(ExplanationEntry {
+      category: self.category.clone(),
+      path: path.to_string(),
+      candidates,
+      selected,
+      mismatches: vec![]
+    });
+    entries.len() - 1
+  }
+
+  fn finish(&self, index: usize, result: &Result<(), Vec<Mismatch>>) {
+    if let Err(mismatches) = result {
+      if let Some(entry) = self.entries.lock().unwrap().get_mut(index) {
+        entry.mismatches = mismatches.iter().map(|mismatch| mismatch.description()).collect();
+      }
+    }
+  }
+}
+
+/// Matching context that records how the matching rules were applied with an explainer (explain
+/// mode). The matching is delegated to the wrapped core matching context.
+#[derive(Debug, Clone)]
+pub struct ExplainingMatchingContext {
+  context: CoreMatchingContext,
+  explainer: Explainer
+}
+
+impl ExplainingMatchingContext {
+  /// Creates a new context that matches using the given context, and records with the explainer
+  pub fn new(context: CoreMatchingContext, explainer: Explainer) -> Self {
+    ExplainingMatchingContext { context, explainer }
+  }
+}
+
+impl MatchingContext for ExplainingMatchingContext {
+  fn matcher_is_defined(&self, path: &DocPath) -> bool {
+    self.context.matcher_is_defined(path)
+  }
+
+  fn select_best_matcher(&self, path: &DocPath) -> RuleList {
+    self.context.select_best_matcher(path)
+  }
+
+  fn type_matcher_defined(&self, path: &DocPath) -> bool {
+    self.context.type_matcher_defined(path)
+  }
+
+  fn values_matcher_defined(&self, path: &DocPath) -> bool {
+    self.context.values_matcher_defined(path)
+  }
+
+  fn direct_matcher_defined(&self, path: &DocPath, matchers: &HashSet<&str>) -> bool {
+    self.context.direct_matcher_defined(path, matchers)
+  }
+
+  fn match_keys(&self, path: &DocPath, expected: &BTreeSet<String>, actual: &BTreeSet<String>) -> Result<(), Vec<Mismatch>> {
+    self.context.match_keys(path, expected, actual)
+  }
+
+  fn plugin_configuration(&self) -> &HashMap<String, PluginInteractionConfig> {
+    self.context.plugin_configuration()
+  }
+
+  fn matchers(&self) -> &MatchingRuleCategory {
+    self.context.matchers()
+  }
+
+  fn config(&self) -> DiffConfig {
+    self.context.config()
+  }
+
+  fn clone_with(&self, matchers: &MatchingRuleCategory) -> Box<dyn MatchingContext> {
+    Box::new(ExplainingMatchingContext {
+      context: CoreMatchingContext {
+        matchers: matchers.clone(),
+        .. self.context.clone()
+      },
+      explainer: self.explainer.clone()
+    })
+  }
+
+  fn explainer(&self) -> Option<&Explainer> {
+    Some(&self.explainer)
+  }
+}
+
+/// Matches the path using the provided function, recording the result with the explainer from the
+/// context (if explain mode is enabled)
+pub(crate) fn explain_path<F>(context: &dyn MatchingContext, path: &DocPath, f: F) -> Result<(), Vec<Mismatch>>
+  where F: FnOnce() -> Result<(), Vec<Mismatch>> {
+  match context.explainer() {
+    Some(explainer) => {
+      let index = explainer.start(context, path);
+      let result = f();
+      explainer.finish(index, &result);
+      result
+    }
+    None => f()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use maplit::hashmap;
+  use pact_models::matchingrules;
+  use pact_models::matchingrules::MatchingRule;
+  use pact_models::path_exp::DocPath;
+  use serde_json::json;
+
+  use crate::{CoreMatchingContext, DiffConfig, MatchingContext, Mismatch};
+
+  use super::*;
+
+  #[test]
+  fn explain_path_records_the_candidate_and_selected_rules() {
+    let explainer = Explainer::new();
+    let context = ExplainingMatchingContext::new(CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys,
+      &matchingrules! {
+        "body" => {
+          "$.id" => [ MatchingRule::Integer ],
+          "$.*" => [ MatchingRule::Type ]
+        }
+      }.rules_for_category("body").unwrap(), &hashmap!{}), explainer.for_category("body"));
+
+    let path = DocPath::new_unwrap("$.id");
+    let result = explain_path(&context, &path, || Ok(()));
+    expect!(result).to(be_ok());
+    let path = DocPath::new_unwrap("$.name");
+    let result = explain_path(&context, &path, || Err(vec![Mismatch::BodyMismatch {
+      path: "$.name".to_string(),
+      expected: None,
+      actual: None,
+      mismatch: "Expected 'a' to be equal to 'b'".to_string()
+    }]));
+    expect!(result).to(be_err());
+
+    let explanation = explainer.explanation();
+    expect!(explanation.entries.len()).to(be_equal_to(2));
+    let entry = &explanation.entries[0];
+    expect!(entry.category.as_str()).to(be_equal_to("body"));
+    expect!(entry.candidates.iter().map(|c| c.path.as_str()).collect::<Vec<_>>()).to(be_equal_to(vec!["$.id", "$.*"]));
+    expect!(entry.selected.as_ref().map(|rules| rules.rules.clone())).to(be_some().value(vec![MatchingRule::Integer]));
+    expect!(entry.matched()).to(be_true());
+    let entry = &explanation.entries[1];
+    expect!(entry.selected.as_ref().map(|rules| rules.rules.clone())).to(be_some().value(vec![MatchingRule::Type]));
+    expect!(entry.mismatches.clone()).to(be_equal_to(vec!["$.name -> Expected 'a' to be equal to 'b'".to_string()]));
+    expect!(entry.to_json()).to(be_equal_to(json!({
+      "category": "body",
+      "path": "$.name",
+      "candidates": [ { "path": "$.*", "weight": 4, "rules": [ { "match": "type" } ] } ],
+      "selected": { "rules": [ { "match": "type" } ], "cascaded": false },
+      "matched": false,
+      "mismatches": [ "$.name -> Expected 'a' to be equal to 'b'" ]
+    })));
+  }
+
+  #[test]
+  fn unused_rules_returns_the_rules_that_were_never_a_candidate() {
+    let rules = matchingrules! {
+      "body" => {
+        "$.id" => [ MatchingRule::Integer ],
+        "$.name" => [ MatchingRule::Type ]
+      },
+      "header" => {
+        "X-Request-Id" => [ MatchingRule::Type ]
+      }
+    };
+    let explainer = Explainer::new();
+    let context = ExplainingMatchingContext::new(CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys,
+      &rules.rules_for_category("body").unwrap(), &hashmap!{}), explainer.for_category("body"));
+
+    let path = DocPath::new_unwrap("$.id");
+    expect!(explain_path(&context, &path, || Ok(()))).to(be_ok());
+
+    expect!(explainer.explanation().unused_rules(&rules)).to(be_equal_to(vec![
+      (Category::BODY, DocPath::new_unwrap("$.name"))
+    ]));
+  }
+
+  #[test]
+  fn explain_path_does_nothing_if_explain_mode_is_not_enabled() {
+    let context = CoreMatchingContext::default();
+    expect!(context.explainer().is_none()).to(be_true());
+    let result = explain_path(&context, &DocPath::root(), || Ok(()));
+    expect!(result).to(be_ok());
+  }
+}
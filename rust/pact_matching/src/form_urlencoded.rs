@@ -0,0 +1,310 @@
+//! Module for functions dealing with matching application/x-www-form-urlencoded bodies
+
+use std::collections::HashMap;
+
+use pact_models::http_parts::HttpPart;
+use pact_models::matchingrules::MatchingRule;
+use pact_models::path_exp::DocPath;
+use tracing::debug;
+
+use crate::{DiffConfig, Matches, MatchingContext, merge_result, Mismatch};
+use crate::matchers::match_values;
+
+/// Matches the bodies of the HTTP parts as form post (application/x-www-form-urlencoded) bodies.
+/// Both bodies are parsed into key/value maps, so the order of the fields is not significant,
+/// and matching rules are applied to each field using paths like `$.field`.
+pub fn match_form_urlencoded(
+  expected: &dyn HttpPart,
+  actual: &dyn HttpPart,
+  context: &dyn MatchingContext
+) -> Result<(), Vec<Mismatch>> {
+  let expected_body = expected.body().value().unwrap_or_default();
+  let actual_body = actual.body().value().unwrap_or_default();
+  let expected_form = parse_form_body(&expected_body);
+  let actual_form = parse_form_body(&actual_body);
+
+  match (expected_form, actual_form) {
+    (Ok(expected_form), Ok(actual_form)) => match_form_maps(&expected_form, &actual_form, context),
+    (expected_form, actual_form) => {
+      let mut mismatches = vec![];
+      if let Err(err) = expected_form {
+        mismatches.push(Mismatch::BodyMismatch {
+          path: "$".to_string(),
+          expected: expected.body().value(),
+          actual: actual.body().value(),
+          mismatch: format!("Failed to parse the expected body: '{}'", err)
+        });
+      }
+      if let Err(err) = actual_form {
+        mismatches.push(Mismatch::BodyMismatch {
+          path: "$".to_string(),
+          expected: expected.body().value(),
+          actual: actual.body().value(),
+          mismatch: format!("Failed to parse the actual body: '{}'", err)
+        });
+      }
+      Err(mismatches)
+    }
+  }
+}
+
+/// Parses a form post body into a map of field name to values, keeping the order of any repeated
+/// values for a field
+fn parse_form_body(body: &[u8]) -> Result<HashMap<String, Vec<String>>, serde_urlencoded::de::Error> {
+  let pairs: Vec<(String, String)> = serde_urlencoded::from_bytes(body)?;
+  Ok(pairs.into_iter().fold(HashMap::new(), |mut map, (key, value)| {
+    map.entry(key).or_insert_with(Vec::new).push(value);
+    map
+  }))
+}
+
+fn match_form_maps(
+  expected: &HashMap<String, Vec<String>>,
+  actual: &HashMap<String, Vec<String>>,
+  context: &dyn MatchingContext
+) -> Result<(), Vec<Mismatch>> {
+  let mut mismatches = vec![];
+  for (key, expected_values) in expected {
+    let path = DocPath::root().join(key.as_str());
+    match actual.get(key) {
+      Some(actual_values) => if let Err(errors) = match_form_values(&path, key, expected_values, actual_values, context) {
+        mismatches.extend(errors);
+      },
+      None => mismatches.push(Mismatch::BodyMismatch {
+        path: path.to_string(),
+        expected: Some(format!("{:?}", expected_values).into()),
+        actual: None,
+        mismatch: format!("Expected form post parameter '{}' but was missing", key)
+      })
+    }
+  }
+
+  if context.config() == DiffConfig::NoUnexpectedKeys {
+    for (key, actual_values) in actual {
+      if !expected.contains_key(key) {
+        mismatches.push(Mismatch::BodyMismatch {
+          path: DocPath::root().join(key.as_str()).to_string(),
+          expected: None,
+          actual: Some(format!("{:?}", actual_values).into()),
+          mismatch: format!("Unexpected form post parameter '{}' received", key)
+        });
+      }
+    }
+  }
+
+  if mismatches.is_empty() {
+    Ok(())
+  } else {
+    Err(mismatches)
+  }
+}
+
+fn match_form_values(
+  path: &DocPath,
+  key: &str,
+  expected: &[String],
+  actual: &[String],
+  context: &dyn MatchingContext
+) -> Result<(), Vec<Mismatch>> {
+  if context.matcher_is_defined(path) {
+    debug!("match_form_values: Matcher defined for form post parameter '{}'", key);
+    // Only the collection length rules apply to the list of values, the rest will cascade to
+    // each of the values
+    let mut rules = context.select_best_matcher(path);
+    rules.rules.retain(|rule| matches!(rule,
+      MatchingRule::MinType(_) | MatchingRule::MaxType(_) | MatchingRule::MinMaxType(_, _)));
+    let result = if rules.is_empty() {
+      Ok(())
+    } else {
+      match_values(path, &rules, expected, actual)
+        .map_err(|errors| errors.iter().map(|message| {
+          Mismatch::BodyMismatch {
+            path: path.to_string(),
+            expected: Some(format!("{:?}", expected).into()),
+            actual: Some(format!("{:?}", actual).into()),
+            mismatch: message.clone()
+          }
+        }).collect())
+    };
+    merge_result(result, compare_form_values_with_matchers(path, expected, actual, context))
+  } else if expected.len() != actual.len() {
+    merge_result(
+      compare_form_values(path, key, expected, actual, context),
+      Err(vec![Mismatch::BodyMismatch {
+        path: path.to_string(),
+        expected: Some(format!("{:?}", expected).into()),
+        actual: Some(format!("{:?}", actual).into()),
+        mismatch: format!("Expected form post parameter '{}' with {} value(s) but received {} value(s)",
+          key, expected.len(), actual.len())
+      }])
+    )
+  } else {
+    compare_form_values(path, key, expected, actual, context)
+  }
+}
+
+fn compare_form_value(
+  path: &DocPath,
+  expected: &str,
+  actual: &str,
+  context: &dyn MatchingContext
+) -> Vec<Mismatch> {
+  let result = if context.matcher_is_defined(path) {
+    match_values(path, &context.select_best_matcher(path), expected, actual)
+  } else {
+    expected.matches_with(actual, &MatchingRule::Equality, false)
+      .map_err(|err| vec![err.to_string()])
+  };
+  result.err().unwrap_or_default().iter().map(|message| Mismatch::BodyMismatch {
+    path: path.to_string(),
+    expected: Some(expected.to_string().into()),
+    actual: Some(actual.to_string().into()),
+    mismatch: message.clone()
+  }).collect()
+}
+
+/// With a matcher defined for the field, every actual value is compared to the expected value
+/// at the same index (or the first one if there are more actual values)
+fn compare_form_values_with_matchers(
+  path: &DocPath,
+  expected: &[String],
+  actual: &[String],
+  context: &dyn MatchingContext
+) -> Result<(), Vec<Mismatch>> {
+  let mismatches: Vec<Mismatch> = actual.iter().enumerate().flat_map(|(index, actual_value)| {
+    match expected.get(index).or_else(|| expected.first()) {
+      Some(expected_value) => compare_form_value(&path.join(index.to_string()), expected_value,
+        actual_value, context),
+      None => vec![]
+    }
+  }).collect();
+
+  if mismatches.is_empty() {
+    Ok(())
+  } else {
+    Err(mismatches)
+  }
+}
+
+fn compare_form_values(
+  path: &DocPath,
+  key: &str,
+  expected: &[String],
+  actual: &[String],
+  context: &dyn MatchingContext
+) -> Result<(), Vec<Mismatch>> {
+  let mismatches: Vec<Mismatch> = expected.iter().enumerate().flat_map(|(index, expected_value)| {
+    match actual.get(index) {
+      Some(actual_value) => compare_form_value(&path.join(index.to_string()), expected_value,
+        actual_value, context),
+      None => vec![Mismatch::BodyMismatch {
+        path: path.to_string(),
+        expected: Some(expected_value.clone().into()),
+        actual: None,
+        mismatch: format!("Expected form post parameter '{}' value '{}' but was missing", key, expected_value)
+      }]
+    }
+  }).collect();
+
+  if mismatches.is_empty() {
+    Ok(())
+  } else {
+    Err(mismatches)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use maplit::hashmap;
+  use pact_models::bodies::OptionalBody;
+  use pact_models::matchingrules;
+  use pact_models::matchingrules::MatchingRule;
+  use pact_models::request::Request;
+
+  use crate::{CoreMatchingContext, DiffConfig, Mismatch};
+
+  use super::match_form_urlencoded;
+
+  fn request(body: &str) -> Request {
+    Request {
+      body: OptionalBody::Present(body.as_bytes().to_vec().into(), None, None),
+      .. Request::default()
+    }
+  }
+
+  fn mismatch_paths(mismatches: &[Mismatch]) -> Vec<String> {
+    mismatches.iter().map(|m| match m {
+      Mismatch::BodyMismatch { path, .. } => path.clone(),
+      _ => String::default()
+    }).collect()
+  }
+
+  #[test]
+  fn match_form_urlencoded_ignores_the_order_of_the_fields() {
+    let expected = request("a=1&b=2&c=hello+world");
+    let actual = request("c=hello%20world&a=1&b=2");
+    let context = CoreMatchingContext::with_config(DiffConfig::NoUnexpectedKeys);
+    expect!(match_form_urlencoded(&expected, &actual, &context)).to(be_ok());
+  }
+
+  #[test]
+  fn match_form_urlencoded_reports_mismatched_missing_and_unexpected_fields() {
+    let expected = request("a=1&b=2");
+    let actual = request("a=3&c=4");
+    let context = CoreMatchingContext::with_config(DiffConfig::NoUnexpectedKeys);
+    let result = match_form_urlencoded(&expected, &actual, &context);
+    let mut paths = mismatch_paths(&result.unwrap_err());
+    paths.sort();
+    expect!(paths).to(be_equal_to(vec!["$.a[0]".to_string(), "$.b".to_string(), "$.c".to_string()]));
+
+    let context = CoreMatchingContext::with_config(DiffConfig::AllowUnexpectedKeys);
+    let result = match_form_urlencoded(&expected, &actual, &context);
+    let mut paths = mismatch_paths(&result.unwrap_err());
+    paths.sort();
+    expect!(paths).to(be_equal_to(vec!["$.a[0]".to_string(), "$.b".to_string()]));
+  }
+
+  #[test]
+  fn match_form_urlencoded_compares_repeated_values_in_order() {
+    let expected = request("id=1&id=2");
+    let context = CoreMatchingContext::with_config(DiffConfig::NoUnexpectedKeys);
+    expect!(match_form_urlencoded(&expected, &request("id=1&id=2"), &context)).to(be_ok());
+
+    let result = match_form_urlencoded(&expected, &request("id=2&id=1"), &context);
+    expect!(mismatch_paths(&result.unwrap_err())).to(be_equal_to(vec!["$.id[0]".to_string(), "$.id[1]".to_string()]));
+
+    let result = match_form_urlencoded(&expected, &request("id=1"), &context);
+    expect!(mismatch_paths(&result.unwrap_err())).to(be_equal_to(vec!["$.id".to_string(), "$.id".to_string()]));
+  }
+
+  #[test]
+  fn match_form_urlencoded_applies_matching_rules_to_fields() {
+    let expected = request("amount=100&currency=AUD&id=1&id=2");
+    let context = CoreMatchingContext::new(DiffConfig::NoUnexpectedKeys, &matchingrules! {
+      "body" => {
+        "$.amount" => [ MatchingRule::Regex("\\d+".to_string()) ],
+        "$.id" => [ MatchingRule::MinType(1) ]
+      }
+    }.rules_for_category("body").unwrap(), &hashmap!{});
+
+    let actual = request("currency=AUD&amount=2500&id=10");
+    expect!(match_form_urlencoded(&expected, &actual, &context)).to(be_ok());
+
+    let actual = request("currency=AUD&amount=lots&id=10&id=11&id=12");
+    let result = match_form_urlencoded(&expected, &actual, &context);
+    expect!(mismatch_paths(&result.unwrap_err())).to(be_equal_to(vec!["$.amount[0]".to_string()]));
+
+    let actual = request("currency=AUD&amount=100");
+    let result = match_form_urlencoded(&expected, &actual, &context);
+    expect!(mismatch_paths(&result.unwrap_err())).to(be_equal_to(vec!["$.id".to_string()]));
+  }
+
+  #[test]
+  fn match_form_urlencoded_with_an_empty_body() {
+    let context = CoreMatchingContext::with_config(DiffConfig::NoUnexpectedKeys);
+    expect!(match_form_urlencoded(&request(""), &request(""), &context)).to(be_ok());
+    let result = match_form_urlencoded(&request("a=1"), &request(""), &context);
+    expect!(mismatch_paths(&result.unwrap_err())).to(be_equal_to(vec!["$.a".to_string()]));
+  }
+}
@@ -41,6 +41,28 @@ async fn applies_header_generator_for_headers_to_the_copy_of_the_response() {
   expect!(headers.get("A").unwrap().first().unwrap()).to_not(be_equal_to("a"));
 }
 
+#[tokio::test]
+async fn applies_jwt_generator_for_headers_to_the_copy_of_the_response() {
+  let token = pact_models::jwt::encode_jwt(&json!({ "claims": { "sub": "1234", "scope": "read" } }), None).unwrap();
+  let response = HttpResponse { headers: Some(hashmap!{
+      s!("Authorization") => vec![format!("Bearer {}", token)]
+    }), generators: generators! {
+      "HEADER" => {
+        "Authorization" => Generator::Jwt(hashmap!{
+          DocPath::new_unwrap("$.claims.sub") => Generator::Uuid(None)
+        }, Some(s!("secret")))
+      }
+    }, .. HttpResponse::default()
+  };
+  let headers = generate_response(&response, &GeneratorTestMode::Consumer, &hashmap!{}).await.headers.unwrap().clone();
+  let header = headers.get("Authorization").unwrap().first().unwrap();
+  expect!(header.starts_with("Bearer ")).to(be_true());
+  let generated = pact_models::jwt::decode_jwt(header).unwrap();
+  expect!(&generated["header"]["alg"]).to(be_equal_to(&json!("HS256")));
+  expect!(&generated["claims"]["sub"]).to_not(be_equal_to(&json!("1234")));
+  expect!(&generated["claims"]["scope"]).to(be_equal_to(&json!("read")));
+}
+
 #[tokio::test]
 async fn returns_original_request_if_there_are_no_generators() {
   let request = HttpRequest::default();
@@ -290,3 +312,24 @@ fn applies_the_generator_to_the_object_graph_with_wildcard() {
   expect!(&json_handler.value["b"]).to(be_equal_to(&json!("B")));
   expect!(&json_handler.value["c"]).to(be_equal_to(&json!("C")));
 }
+
+#[tokio::test]
+async fn applies_body_generators_to_each_record_of_an_ndjson_body() {
+  let content_type = ContentType::parse("application/x-ndjson").unwrap();
+  let body = OptionalBody::Present("{\"id\":1,\"name\":\"one\"}\n{\"id\":2,\"name\":\"two\"}\n".into(),
+    Some(content_type.clone()), None);
+  let generators = hashmap!{
+    DocPath::new_unwrap("$[*].id") => Generator::RandomInt(100, 200),
+    DocPath::new_unwrap("$[1].name") => Generator::RandomString(10)
+  };
+  let result = generators_process_body(&GeneratorTestMode::Provider, &body, Some(content_type.clone()),
+    &hashmap!{}, &generators, &DefaultVariantMatcher{}).await.unwrap();
+
+  expect!(result.content_type()).to(be_some().value(content_type));
+  let lines = result.str_value().lines().map(|line| serde_json::from_str::<Value>(line).unwrap()).collect::<Vec<_>>();
+  expect!(lines.len()).to(be_equal_to(2));
+  expect!(lines[0]["id"].as_i64().unwrap()).to(be_greater_or_equal_to(100));
+  expect!(&lines[0]["name"]).to(be_equal_to(&json!("one")));
+  expect!(lines[1]["id"].as_i64().unwrap()).to(be_greater_or_equal_to(100));
+  expect!(lines[1]["name"].as_str().unwrap().len()).to(be_equal_to(10));
+}
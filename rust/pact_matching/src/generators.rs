@@ -16,6 +16,7 @@ use tracing::{debug, error, warn};
 
 use crate::{CoreMatchingContext, DiffConfig, MatchingContext};
 use crate::json::compare_json;
+use crate::ndjson::{parse_ndjson, to_ndjson};
 
 /// Implementation of a content type handler for XML (currently unimplemented).
 pub struct XmlHandler<'a> {
@@ -87,12 +88,33 @@ pub async fn generators_process_body(
           Ok(body.clone())
         }
       }
+    } else if content_type.is_ndjson() {
+      debug!("apply_body_generators: NDJSON content type");
+      match parse_ndjson(&body.value().unwrap_or_default()) {
+        Ok(records) => {
+          let mut handler = JsonHandler { value: Value::Array(records) };
+          match handler.process_body(generators, mode, context, &matcher.boxed()) {
+            Ok(_) => {
+              let records = handler.value.as_array().cloned().unwrap_or_default();
+              Ok(OptionalBody::Present(to_ndjson(&records).into(), Some(content_type), None))
+            },
+            Err(err) => {
+              error!("Failed to generate the body: {}", err);
+              Ok(body.clone())
+            }
+          }
+        },
+        Err(err) => {
+          error!("Failed to parse the body, so not applying any generators: {}", err);
+          Ok(body.clone())
+        }
+      }
     } else if let Some(content_generator) = find_content_generator(&content_type) {
       debug!("apply_body_generators: Found a content generator from a plugin");
       content_generator.generate_content(&content_type, &generators.iter()
         .map(|(k, v)| (k.to_string(), v.clone())).collect(), body).await
     } else {
-      warn!("Unsupported content type {} - Generators only support JSON and XML", content_type);
+      warn!("Unsupported content type {} - Generators only support JSON, NDJSON and XML", content_type);
       Ok(body.clone())
     },
     _ => Ok(body.clone())
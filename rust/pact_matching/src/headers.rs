@@ -5,11 +5,13 @@ use std::iter::FromIterator;
 
 use maplit::*;
 
-use pact_models::headers::PARAMETERISED_HEADERS;
-use pact_models::matchingrules::MatchingRule;
+use pact_models::headers::{COOKIE_HEADERS, LIST_HEADERS, PARAMETERISED_HEADERS, QUALITY_LIST_HEADERS};
+use pact_models::matchingrules::{MatchingRule, RuleList};
 use pact_models::path_exp::DocPath;
 
 use crate::{matchers, MatchingContext, Mismatch};
+use crate::explain::explain_path;
+use crate::jwt::{is_jwt, match_jwt, partition_jwt_rules};
 use crate::matchers::Matches;
 
 fn strip_whitespace<'a, T: FromIterator<&'a str>>(val: &'a str, split_by: &'a str) -> T {
@@ -65,23 +67,393 @@ pub(crate) fn match_header_value(
   let expected: String = strip_whitespace(expected, ",");
   let actual: String = strip_whitespace(actual, ",");
 
-  let matcher_result = if context.matcher_is_defined(&path) {
-    matchers::match_values(&path, &context.select_best_matcher(&path), &expected, &actual)
-  } else if PARAMETERISED_HEADERS.contains(&key.to_lowercase().as_str()) {
-    match_parameter_header(expected.as_str(), actual.as_str(), key, "header")
+  explain_path(context, &path, || {
+    let matcher_result = if context.matcher_is_defined(&path) {
+      let rules = context.select_best_matcher(&path);
+      if rules.rules.iter().any(is_jwt) {
+        match_jwt_header(key, &path, &rules, &expected, &actual)
+      } else {
+        matchers::match_values(&path, &rules, &expected, &actual)
+      }
+    } else if PARAMETERISED_HEADERS.contains(&key.to_lowercase().as_str()) {
+      match_parameter_header(expected.as_str(), actual.as_str(), key, "header")
+    } else {
+      Matches::matches_with(&expected, &actual, &MatchingRule::Equality, false)
+        .map_err(|err| vec![err.to_string()])
+    };
+    matcher_result.map_err(|messages| {
+      messages.iter().map(|message| {
+        Mismatch::HeaderMismatch {
+          key: key.to_string(),
+          expected: expected.to_string(),
+          actual: actual.to_string(),
+          mismatch: format!("Mismatch with header '{}': {}", key, message)
+        }
+      }).collect()
+    })
+  })
+}
+
+/// Matches a header containing a JWT (i.e. `Authorization: Bearer <token>`). Each mismatch names
+/// the claim that did not match (i.e. `header.Authorization.jwt.claims.sub`).
+fn match_jwt_header(
+  key: &str,
+  path: &DocPath,
+  rules: &RuleList,
+  expected: &String,
+  actual: &String
+) -> Result<(), Vec<String>> {
+  let (jwt_rules, other_rules) = partition_jwt_rules(rules);
+  let mut messages = vec![];
+  for rules in &jwt_rules {
+    if let Err(mismatches) = match_jwt(rules, expected, actual) {
+      messages.extend(mismatches.iter()
+        .map(|(jwt_path, message)| format!("header.{}.{} -> {}", key, jwt_path, message)));
+    }
+  }
+  if !other_rules.is_empty() {
+    if let Err(mismatches) = matchers::match_values(path, &other_rules, expected, actual) {
+      messages.extend(mismatches);
+    }
+  }
+
+  if messages.is_empty() {
+    Ok(())
   } else {
-    Matches::matches_with(&expected, &actual, &MatchingRule::Equality, false)
+    Err(messages)
+  }
+}
+
+/// A cookie parsed from a Cookie or Set-Cookie header value
+#[derive(Debug, Clone, PartialEq)]
+struct Cookie {
+  /// Name of the cookie
+  name: String,
+  /// Value of the cookie
+  value: String,
+  /// Attributes of the cookie (only set for Set-Cookie headers). Attributes like HttpOnly have no value.
+  attributes: Vec<(String, Option<String>)>
+}
+
+impl Cookie {
+  fn attribute(&self, name: &str) -> Option<&Option<String>> {
+    self.attributes.iter()
+      .find(|(attribute, _)| attribute.eq_ignore_ascii_case(name))
+      .map(|(_, value)| value)
+  }
+}
+
+fn parse_cookie_pair(pair: &str) -> (String, Option<String>) {
+  match pair.split_once('=') {
+    Some((name, value)) => (name.trim().to_string(), Some(value.trim().to_string())),
+    None => (pair.trim().to_string(), None)
+  }
+}
+
+/// Parses the values of a Cookie header (`name=value; name2=value2`) or a Set-Cookie header
+/// (`name=value; Attribute=value; Flag`, one cookie per value) into the individual cookies
+fn parse_cookies(key: &str, values: &[String]) -> Vec<Cookie> {
+  let set_cookie = key.eq_ignore_ascii_case("set-cookie");
+  values.iter().flat_map(|value| {
+    let pairs = value.split(';')
+      .filter(|pair| !pair.trim().is_empty())
+      .map(parse_cookie_pair)
+      .collect::<Vec<_>>();
+    if set_cookie {
+      pairs.split_first().map(|((name, value), attributes)| Cookie {
+        name: name.clone(),
+        value: value.clone().unwrap_or_default(),
+        attributes: attributes.to_vec()
+      }).into_iter().collect::<Vec<_>>()
+    } else {
+      pairs.iter().map(|(name, value)| Cookie {
+        name: name.clone(),
+        value: value.clone().unwrap_or_default(),
+        attributes: vec![]
+      }).collect()
+    }
+  }).collect()
+}
+
+/// Returns the matchers defined at exactly the path. Parent matchers are not used, so that a matcher
+/// for a cookie value or list member does not get applied to its attributes or parameters.
+fn exact_path_matchers(path: &DocPath, context: &dyn MatchingContext) -> Option<RuleList> {
+  let path_vec = path.to_vec();
+  let path_slice = path_vec.iter().map(|p| p.as_str()).collect::<Vec<_>>();
+  let matchers = context.matchers()
+    .filter(|(rule_path, _)| rule_path.matches_path_exactly(path_slice.as_slice()));
+  if matchers.is_not_empty() {
+    Some(matchers.as_rule_list())
+  } else {
+    None
+  }
+}
+
+/// Matches a part of a header value (i.e. a cookie value or attribute) using the matchers defined at
+/// exactly the path, otherwise using equality
+fn match_header_part(
+  path: &DocPath,
+  expected: &str,
+  actual: &str,
+  context: &dyn MatchingContext
+) -> Result<(), Vec<String>> {
+  match exact_path_matchers(path, context) {
+    Some(matchers) => matchers::match_values(path, &matchers, expected, actual),
+    None => Matches::matches_with(&expected, actual, &MatchingRule::Equality, false)
       .map_err(|err| vec![err.to_string()])
+  }
+}
+
+/// Matches the cookies of a Cookie or Set-Cookie header. Each expected cookie must be present in the
+/// actual header, and matching rules can be applied to the cookie value (`header.Set-Cookie.session`)
+/// or to an attribute of the cookie (`header.Set-Cookie.session.Max-Age`).
+pub(crate) fn match_cookie_header(
+  key: &str,
+  expected: &[String],
+  actual: &[String],
+  context: &dyn MatchingContext
+) -> Result<(), Vec<Mismatch>> {
+  let path = DocPath::root().join(key);
+  let expected_cookies = parse_cookies(key, expected);
+  let actual_cookies = parse_cookies(key, actual);
+  let mismatch = |expected: &str, actual: &str, message: String| Mismatch::HeaderMismatch {
+    key: key.to_string(),
+    expected: expected.to_string(),
+    actual: actual.to_string(),
+    mismatch: format!("Mismatch with header '{}': {}", key, message)
   };
-  matcher_result.map_err(|messages| {
-    messages.iter().map(|message| {
-      Mismatch::HeaderMismatch {
-        key: key.to_string(),
-        expected: expected.to_string(),
-        actual: actual.to_string(),
-        mismatch: format!("Mismatch with header '{}': {}", key, message)
+
+  explain_path(context, &path, || {
+    let mut mismatches = vec![];
+    for cookie in &expected_cookies {
+      let cookie_path = path.join(cookie.name.as_str());
+      match actual_cookies.iter().find(|actual| actual.name == cookie.name) {
+        Some(actual_cookie) => {
+          if let Err(messages) = match_header_part(&cookie_path, &cookie.value, &actual_cookie.value, context) {
+            mismatches.extend(messages.iter().map(|message| mismatch(&cookie.value, &actual_cookie.value,
+              format!("cookie '{}': {}", cookie.name, message))));
+          }
+          for (attribute, value) in &cookie.attributes {
+            match actual_cookie.attribute(attribute) {
+              Some(actual_value) => if let Some(value) = value {
+                let actual_value = actual_value.clone().unwrap_or_default();
+                if let Err(messages) = match_header_part(&cookie_path.join(attribute.as_str()), value,
+                                                         &actual_value, context) {
+                  mismatches.extend(messages.iter().map(|message| mismatch(value, &actual_value,
+                    format!("cookie '{}' attribute '{}': {}", cookie.name, attribute, message))));
+                }
+              },
+              None => mismatches.push(mismatch(value.as_deref().unwrap_or(attribute), "",
+                format!("Expected cookie '{}' to have attribute '{}' but was missing", cookie.name, attribute)))
+            }
+          }
+        },
+        None => mismatches.push(mismatch(&cookie.value, "",
+          format!("Expected cookie '{}' but was missing", cookie.name)))
       }
-    }).collect()
+    }
+
+    if mismatches.is_empty() {
+      Ok(())
+    } else {
+      Err(mismatches)
+    }
+  })
+}
+
+/// A member of a list header value, i.e. `max-age=0` in `Cache-Control: no-cache, max-age=0` or
+/// `text/html;level=1;q=0.9` in an `Accept` header
+#[derive(Debug, Clone, PartialEq)]
+struct ListMember {
+  /// Name of the member (`max-age` or `text/html`)
+  name: String,
+  /// Value of the member, if it has one (`0` for `max-age=0`)
+  value: Option<String>,
+  /// Parameters of the member. Parameter names are lower case.
+  parameters: Vec<(String, String)>
+}
+
+impl ListMember {
+  fn parameter(&self, name: &str) -> Option<&String> {
+    self.parameters.iter()
+      .find(|(parameter, _)| parameter == name)
+      .map(|(_, value)| value)
+  }
+
+  /// The quality (`q` parameter) of the member, defaulting to 1
+  fn quality(&self) -> f64 {
+    self.parameter("q")
+      .and_then(|q| q.parse().ok())
+      .unwrap_or(1.0)
+  }
+}
+
+/// Splits a header value on the separator, ignoring any separators in quoted strings or in URIs
+/// (i.e. `<https://example.com/?a=1,2>` in a `Link` header)
+fn split_header_value(value: &str, separator: char) -> Vec<&str> {
+  let mut parts = vec![];
+  let mut start = 0;
+  let mut in_quotes = false;
+  let mut in_uri = false;
+  let mut escaped = false;
+  for (index, ch) in value.char_indices() {
+    if escaped {
+      escaped = false;
+    } else if ch == '\\' && in_quotes {
+      escaped = true;
+    } else if ch == '"' {
+      in_quotes = !in_quotes;
+    } else if ch == '<' && !in_quotes {
+      in_uri = true;
+    } else if ch == '>' && !in_quotes {
+      in_uri = false;
+    } else if ch == separator && !in_quotes && !in_uri {
+      parts.push(&value[start..index]);
+      start = index + ch.len_utf8();
+    }
+  }
+  parts.push(&value[start..]);
+  parts.iter().map(|part| part.trim()).filter(|part| !part.is_empty()).collect()
+}
+
+fn unquote(value: &str) -> String {
+  let value = value.trim();
+  if value.len() > 1 && value.starts_with('"') && value.ends_with('"') {
+    value[1..value.len() - 1].replace("\\\"", "\"").replace("\\\\", "\\")
+  } else {
+    value.to_string()
+  }
+}
+
+/// Parses the values of a list header into the members of the list. The values are joined first, as
+/// they may have been split on commas that were part of a quoted string.
+fn parse_list_header(values: &[String]) -> Vec<ListMember> {
+  let value = values.join(",");
+  split_header_value(&value, ',').iter().filter_map(|member| {
+    let parts = split_header_value(member, ';');
+    parts.split_first().map(|(first, parameters)| {
+      let name_value = if first.starts_with('<') { None } else { first.split_once('=') };
+      let (name, value) = match name_value {
+        Some((name, value)) => (name.trim().to_string(), Some(unquote(value))),
+        None => (first.to_string(), None)
+      };
+      ListMember {
+        name,
+        value,
+        parameters: parameters.iter().map(|parameter| match parameter.split_once('=') {
+          Some((name, value)) => (name.trim().to_lowercase(), unquote(value)),
+          None => (parameter.to_lowercase(), String::default())
+        }).collect()
+      }
+    })
+  }).collect()
+}
+
+/// Compares the names of two list header members. Directive names and media types (`type/subtype`, see
+/// RFC 9110) are not case-sensitive. The other values of content negotiation headers, URIs (`<...>`) and
+/// quoted strings like entity tags must be equal.
+fn same_member_name(ordered: bool, expected: &str, actual: &str) -> bool {
+  if (ordered && !expected.contains('/')) || expected.starts_with('<') || expected.contains('"') {
+    expected == actual
+  } else {
+    expected.eq_ignore_ascii_case(actual)
+  }
+}
+
+/// Matches the members of a list header (like `Cache-Control` or `Accept`). Each expected member must be
+/// present in the actual header, regardless of order, and any other actual members are ignored. Directive
+/// names (like `no-cache`) are not case-sensitive, see `same_member_name`. For content negotiation headers
+/// (like `Accept`), the members are ordered by their quality (`q` parameter), and the expected members must be in the same order
+/// of preference. Member values are compared with the matchers defined for `header.<KEY>.<MEMBER>`, and
+/// parameters (which are compared as a map) with the matchers defined for `header.<KEY>.<MEMBER>.<PARAMETER>`.
+pub(crate) fn match_list_header(
+  key: &str,
+  expected: &[String],
+  actual: &[String],
+  context: &dyn MatchingContext
+) -> Result<(), Vec<Mismatch>> {
+  let path = DocPath::root().join(key);
+  let ordered = QUALITY_LIST_HEADERS.contains(&key.to_lowercase().as_str());
+  let mut expected_members = parse_list_header(expected);
+  let mut actual_members = parse_list_header(actual);
+  if ordered {
+    expected_members.sort_by(|a, b| b.quality().total_cmp(&a.quality()));
+    actual_members.sort_by(|a, b| b.quality().total_cmp(&a.quality()));
+  }
+  let expected_value = expected.join(", ");
+  let actual_value = actual.join(", ");
+  let mismatch = |message: String| Mismatch::HeaderMismatch {
+    key: key.to_string(),
+    expected: expected_value.clone(),
+    actual: actual_value.clone(),
+    mismatch: format!("Mismatch with header '{}': {}", key, message)
+  };
+
+  explain_path(context, &path, || {
+    let mut mismatches = vec![];
+    let mut previous: Option<(usize, &ListMember)> = None;
+    for member in &expected_members {
+      let member_path = path.join(member.name.as_str());
+      match actual_members.iter().position(|actual| same_member_name(ordered, &member.name, &actual.name)) {
+        Some(index) => {
+          let actual_member = &actual_members[index];
+          if let Some((previous_index, previous_member)) = previous {
+            if ordered && index < previous_index {
+              mismatches.push(mismatch(format!("Expected '{}' to be preferred over '{}'",
+                previous_member.name, member.name)));
+            }
+          }
+          previous = Some((index, member));
+
+          if member.value.is_some() || actual_member.value.is_some() {
+            let expected = member.value.clone().unwrap_or_default();
+            let actual = actual_member.value.clone().unwrap_or_default();
+            if let Err(messages) = match_header_part(&member_path, &expected, &actual, context) {
+              mismatches.extend(messages.iter().map(|message| mismatch(format!("value '{}': {}",
+                member.name, message))));
+            }
+          }
+
+          if ordered && exact_path_matchers(&member_path.join("q"), context).is_none() &&
+            (member.quality() - actual_member.quality()).abs() > f64::EPSILON {
+            mismatches.push(mismatch(format!("value '{}': Expected a quality of {} but was {}",
+              member.name, member.quality(), actual_member.quality())));
+          }
+
+          for (name, value) in &member.parameters {
+            if ordered && name == "q" && exact_path_matchers(&member_path.join("q"), context).is_none() {
+              continue;
+            }
+            let parameter_path = member_path.join(name.as_str());
+            match actual_member.parameter(name) {
+              Some(actual) => {
+                let result = match exact_path_matchers(&parameter_path, context) {
+                  Some(matchers) => matchers::match_values(&parameter_path, &matchers, value.as_str(), actual.as_str()),
+                  None => if value.eq_ignore_ascii_case(actual) {
+                    Ok(())
+                  } else {
+                    Err(vec![format!("Expected '{}' to be equal to '{}'", value, actual)])
+                  }
+                };
+                if let Err(messages) = result {
+                  mismatches.extend(messages.iter().map(|message| mismatch(format!("value '{}' parameter '{}': {}",
+                    member.name, name, message))));
+                }
+              },
+              None => mismatches.push(mismatch(format!("Expected value '{}' to have parameter '{}' but was missing",
+                member.name, name)))
+            }
+          }
+        },
+        None => mismatches.push(mismatch(format!("Expected value '{}' but was missing", member.name)))
+      }
+    }
+
+    if mismatches.is_empty() {
+      Ok(())
+    } else {
+      Err(mismatches)
+    }
   })
 }
 
@@ -100,9 +472,20 @@ fn match_header_maps(
   let mut result = hashmap!{};
   for (key, value) in &expected {
     match find_entry(&actual, key) {
-      Some((_, actual_value)) => for (index, val) in value.iter().enumerate() {
-        result.insert(key.clone(), match_header_value(key, val,
-                                                      actual_value.get(index).unwrap_or(&String::default()), context).err().unwrap_or_default());
+      Some((_, actual_value)) => if COOKIE_HEADERS.contains(&key.to_lowercase().as_str()) &&
+        !context.matcher_is_defined(&DocPath::root().join(key)) {
+        result.insert(key.clone(), match_cookie_header(key, value, &actual_value, context)
+          .err().unwrap_or_default());
+      } else if (LIST_HEADERS.contains(&key.to_lowercase().as_str()) ||
+        QUALITY_LIST_HEADERS.contains(&key.to_lowercase().as_str())) &&
+        !context.matcher_is_defined(&DocPath::root().join(key)) {
+        result.insert(key.clone(), match_list_header(key, value, &actual_value, context)
+          .err().unwrap_or_default());
+      } else {
+        for (index, val) in value.iter().enumerate() {
+          result.insert(key.clone(), match_header_value(key, val,
+                                                        actual_value.get(index).unwrap_or(&String::default()), context).err().unwrap_or_default());
+        }
       },
       None => {
         result.insert(key.clone(), vec![Mismatch::HeaderMismatch { key: key.clone(),
@@ -143,7 +526,7 @@ mod tests {
   use pact_models::matchingrules::MatchingRule;
 
   use crate::{CoreMatchingContext, DiffConfig, Mismatch};
-  use crate::headers::{match_header_value, match_headers};
+  use crate::headers::{match_cookie_header, match_header_value, match_headers, match_list_header, parse_list_header};
 
   #[test]
   fn matching_headers_be_true_when_headers_are_equal() {
@@ -345,4 +728,200 @@ mod tests {
       mismatch: s!(""),
     } ]));
   }
+
+  #[test]
+  fn cookie_header_matches_when_all_expected_cookies_are_present() {
+    let expected = hashmap! { s!("Cookie") => vec![s!("session=1234; theme=dark")] };
+    let actual = hashmap! { s!("cookie") => vec![s!("theme=dark;session=1234; tracking=abc")] };
+    let result = match_headers(Some(expected), Some(actual), &CoreMatchingContext::default());
+    expect!(result.values().flatten()).to(be_empty());
+  }
+
+  #[test]
+  fn cookie_header_mismatches_name_the_cookie() {
+    let mismatches = match_cookie_header("Cookie", &[s!("session=1234; theme=dark")],
+      &[s!("session=5678")], &CoreMatchingContext::default()).unwrap_err();
+    let messages = mismatches.iter().map(|m| m.description()).collect::<Vec<_>>();
+    expect!(messages).to(be_equal_to(vec![
+      s!("Mismatch with header 'Cookie': cookie 'session': Expected '1234' to be equal to '5678'"),
+      s!("Mismatch with header 'Cookie': Expected cookie 'theme' but was missing")
+    ]));
+  }
+
+  #[test]
+  fn set_cookie_header_matches_with_an_expires_attribute() {
+    let expected = hashmap! {
+      s!("Set-Cookie") => vec![s!("session=1234; Expires=Wed, 21 Oct 2015 07:28:00 GMT; HttpOnly")]
+    };
+    let actual = hashmap! {
+      s!("set-cookie") => vec![s!("session=1234; httponly; expires=Wed, 21 Oct 2015 07:28:00 GMT; Path=/")]
+    };
+    let result = match_headers(Some(expected), Some(actual), &CoreMatchingContext::default());
+    expect!(result.values().flatten()).to(be_empty());
+  }
+
+  #[test]
+  fn set_cookie_header_mismatches_on_attributes() {
+    let mismatches = match_cookie_header("Set-Cookie",
+      &[s!("session=1234; Max-Age=3600; Secure"), s!("theme=dark")],
+      &[s!("theme=dark"), s!("session=1234; Max-Age=60")], &CoreMatchingContext::default()).unwrap_err();
+    let messages = mismatches.iter().map(|m| m.description()).collect::<Vec<_>>();
+    expect!(messages).to(be_equal_to(vec![
+      s!("Mismatch with header 'Set-Cookie': cookie 'session' attribute 'Max-Age': Expected '3600' to be equal to '60'"),
+      s!("Mismatch with header 'Set-Cookie': Expected cookie 'session' to have attribute 'Secure' but was missing")
+    ]));
+  }
+
+  #[test]
+  fn set_cookie_header_applies_matchers_to_cookies_and_attributes() {
+    let context = CoreMatchingContext::new(
+      DiffConfig::AllowUnexpectedKeys,
+      &matchingrules! {
+        "header" => {
+          "Set-Cookie.session" => [ MatchingRule::Regex(s!("[a-f0-9]+")) ],
+          "Set-Cookie.session.Max-Age" => [ MatchingRule::Integer ]
+        }
+      }.rules_for_category("header").unwrap_or_default(), &hashmap!{}
+    );
+    let result = match_cookie_header("Set-Cookie", &[s!("session=1234; Max-Age=3600; Path=/")],
+      &[s!("session=beef01; Max-Age=60; Path=/")], &context);
+    expect!(result).to(be_ok());
+
+    let mismatches = match_cookie_header("Set-Cookie", &[s!("session=1234; Max-Age=3600; Path=/")],
+      &[s!("session=xyz; Max-Age=sixty; Path=/api")], &context).unwrap_err();
+    let messages = mismatches.iter().map(|m| m.description()).collect::<Vec<_>>();
+    expect!(messages).to(be_equal_to(vec![
+      s!("Mismatch with header 'Set-Cookie': cookie 'session': Expected 'xyz' to match '[a-f0-9]+'"),
+      s!("Mismatch with header 'Set-Cookie': cookie 'session' attribute 'Max-Age': Expected 'sixty' to match an integer number"),
+      s!("Mismatch with header 'Set-Cookie': cookie 'session' attribute 'Path': Expected '/' to be equal to '/api'")
+    ]));
+  }
+
+  #[test]
+  fn cookie_header_uses_a_matcher_defined_for_the_whole_header() {
+    let context = CoreMatchingContext::new(
+      DiffConfig::AllowUnexpectedKeys,
+      &matchingrules! {
+        "header" => {
+          "Cookie" => [ MatchingRule::Regex(s!("session=\\d+")) ]
+        }
+      }.rules_for_category("header").unwrap_or_default(), &hashmap!{}
+    );
+    let expected = hashmap! { s!("Cookie") => vec![s!("session=1234")] };
+    let actual = hashmap! { s!("Cookie") => vec![s!("session=5678")] };
+    let result = match_headers(Some(expected), Some(actual), &context);
+    expect!(result.values().flatten()).to(be_empty());
+  }
+
+  #[test]
+  fn list_header_members_match_in_any_order() {
+    let expected = hashmap! { s!("Cache-Control") => vec![s!("no-cache"), s!("max-age=0")] };
+    let actual = hashmap! { s!("cache-control") => vec![s!("max-age=0"), s!("no-cache")] };
+    let result = match_headers(Some(expected), Some(actual), &CoreMatchingContext::default());
+    expect!(result.values().flatten()).to(be_empty());
+  }
+
+  #[test]
+  fn list_header_mismatches_name_the_member() {
+    let mismatches = match_list_header("Cache-Control", &[s!("no-cache"), s!("max-age=0")],
+      &[s!("max-age=60"), s!("public")], &CoreMatchingContext::default()).unwrap_err();
+    let messages = mismatches.iter().map(|m| m.description()).collect::<Vec<_>>();
+    expect!(messages).to(be_equal_to(vec![
+      s!("Mismatch with header 'Cache-Control': Expected value 'no-cache' but was missing"),
+      s!("Mismatch with header 'Cache-Control': value 'max-age': Expected '0' to be equal to '60'")
+    ]));
+  }
+
+  #[test]
+  fn list_header_ignores_unexpected_members() {
+    let expected = hashmap! { s!("Cache-Control") => vec![s!("no-cache")] };
+    let actual = hashmap! { s!("Cache-Control") => vec![s!("no-cache, no-store")] };
+    let result = match_headers(Some(expected), Some(actual), &CoreMatchingContext::default());
+    expect!(result.values().flatten()).to(be_empty());
+  }
+
+  #[test]
+  fn list_header_directive_names_are_not_case_sensitive() {
+    let result = match_list_header("Cache-Control", &[s!("no-cache, Max-Age=0")],
+      &[s!("No-Cache, max-age=0")], &CoreMatchingContext::default());
+    expect!(result).to(be_ok());
+    let result = match_list_header("Accept", &[s!("text/html, application/json;q=0.9")],
+      &[s!("Text/HTML, Application/JSON;q=0.9")], &CoreMatchingContext::default());
+    expect!(result).to(be_ok());
+    let result = match_list_header("Accept", &[s!("alligators")], &[s!("Alligators")],
+      &CoreMatchingContext::default());
+    expect!(result).to(be_err());
+    let result = match_list_header("If-None-Match", &[s!("\"abc\"")], &[s!("\"ABC\"")],
+      &CoreMatchingContext::default());
+    expect!(result).to(be_err());
+  }
+
+  #[test]
+  fn list_header_values_can_contain_quoted_commas() {
+    let members = parse_list_header(&[
+      s!("<https://example.com/?page=2>; rel=\"next\"; title=\"Next, please\""),
+      s!("<https://example.com/?page=1,2>; rel=prev")
+    ]);
+    expect!(members.iter().map(|m| m.name.clone()).collect::<Vec<_>>()).to(be_equal_to(vec![
+      s!("<https://example.com/?page=2>"), s!("<https://example.com/?page=1,2>")
+    ]));
+    expect!(members[0].parameter("title")).to(be_some().value(&s!("Next, please")));
+
+    let expected = hashmap! { s!("Link") => vec![s!("<https://example.com/?page=2>; rel=\"next\""),
+      s!("<https://example.com/?page=1,2>; rel=prev")] };
+    let actual = hashmap! { s!("Link") => vec![s!("<https://example.com/?page=1"), s!("2>; rel=\"prev\""),
+      s!("<https://example.com/?page=2>; title=\"Next, please\"; REL=next")] };
+    let result = match_headers(Some(expected), Some(actual), &CoreMatchingContext::default());
+    expect!(result.values().flatten()).to(be_empty());
+  }
+
+  #[test]
+  fn accept_header_members_are_ordered_by_quality() {
+    let expected = hashmap! { s!("Accept") => vec![s!("text/html;q=0.9"), s!("application/json")] };
+    let actual = hashmap! { s!("Accept") => vec![s!("application/json;q=1.0"), s!("text/html; q=0.90")] };
+    let result = match_headers(Some(expected), Some(actual), &CoreMatchingContext::default());
+    expect!(result.values().flatten()).to(be_empty());
+
+    let mismatches = match_list_header("Accept", &[s!("alligators"), s!("hippos")],
+      &[s!("hippos"), s!("alligators")], &CoreMatchingContext::default()).unwrap_err();
+    let messages = mismatches.iter().map(|m| m.description()).collect::<Vec<_>>();
+    expect!(messages).to(be_equal_to(vec![
+      s!("Mismatch with header 'Accept': Expected 'alligators' to be preferred over 'hippos'")
+    ]));
+
+    let mismatches = match_list_header("Accept", &[s!("text/html;level=1;q=0.5")],
+      &[s!("text/html;level=2;q=0.8")], &CoreMatchingContext::default()).unwrap_err();
+    let messages = mismatches.iter().map(|m| m.description()).collect::<Vec<_>>();
+    expect!(messages).to(be_equal_to(vec![
+      s!("Mismatch with header 'Accept': value 'text/html': Expected a quality of 0.5 but was 0.8"),
+      s!("Mismatch with header 'Accept': value 'text/html' parameter 'level': Expected '1' to be equal to '2'")
+    ]));
+  }
+
+  #[test]
+  fn list_header_applies_matchers_to_members_and_parameters() {
+    let context = CoreMatchingContext::new(
+      DiffConfig::AllowUnexpectedKeys,
+      &matchingrules! {
+        "header" => {
+          "Cache-Control.max-age" => [ MatchingRule::Integer ],
+          "Prefer.wait" => [ MatchingRule::Integer ],
+          "Accept['text/html'].q" => [ MatchingRule::Regex(s!("0\\.\\d+")) ]
+        }
+      }.rules_for_category("header").unwrap_or_default(), &hashmap!{}
+    );
+    let result = match_list_header("Cache-Control", &[s!("max-age=0, no-store")],
+      &[s!("no-store, max-age=3600")], &context);
+    expect!(result).to(be_ok());
+    let result = match_list_header("Accept", &[s!("text/html;q=0.9, application/json")],
+      &[s!("application/json, text/html;q=0.2")], &context);
+    expect!(result).to(be_ok());
+
+    let mismatches = match_list_header("Prefer", &[s!("respond-async, wait=10")],
+      &[s!("wait=ten, respond-async")], &context).unwrap_err();
+    let messages = mismatches.iter().map(|m| m.description()).collect::<Vec<_>>();
+    expect!(messages).to(be_equal_to(vec![
+      s!("Mismatch with header 'Prefer': value 'wait': Expected 'ten' to match an integer number")
+    ]));
+  }
 }
@@ -1,23 +1,28 @@
 //! The `json` module provides functions to compare and display the differences between JSON bodies
 
+use std::cmp::Ordering;
 use std::str::FromStr;
 
 use ansi_term::Colour::*;
 use anyhow::anyhow;
 use difference::*;
+use itertools::Itertools;
 use onig::Regex;
 use semver::Version;
 use serde_json::{json, Value};
 
 use pact_models::http_parts::HttpPart;
 use pact_models::json_utils::json_to_string;
-use pact_models::matchingrules::MatchingRule;
-use pact_models::path_exp::DocPath;
+use pact_models::matchingrules::{MatchingRule, RuleList};
+use pact_models::path_exp::{DocPath, PathToken};
 use pact_models::time_utils::validate_datetime;
 use tracing::debug;
 
 use crate::{DiffConfig, MatchingContext, merge_result};
 use crate::binary_utils::{convert_data, match_content_type};
+use crate::explain::explain_path;
+use crate::json_schema::validate_json_schema;
+use crate::jwt::{is_jwt, jwt_mismatch_messages, match_jwt, partition_jwt_rules};
 use crate::matchers::*;
 use crate::matchingrules::{compare_lists_with_matchingrule, compare_maps_with_matchingrule};
 
@@ -134,7 +139,7 @@ impl Matches<&Value> for Value {
           (_, _) => Err(anyhow!("Expected '{}' to be the same type as '{}'", json_to_string(self), json_to_string(actual))),
         }
       },
-      MatchingRule::Equality | MatchingRule::Values => {
+      MatchingRule::Equality | MatchingRule::EqualsIgnoreOrder | MatchingRule::Values => {
         if self == actual {
           Ok(())
         } else {
@@ -211,6 +216,40 @@ impl Matches<&Value> for Value {
         }
         _ => Err(anyhow!("Expected something that matches a semantic version, but got '{}'", actual))
       }
+      MatchingRule::NumberRange(min, max) => match actual.as_f64() {
+        Some(number) => match_number_range(number, min, max),
+        None => Err(anyhow!("Expected '{}' to be a number", json_to_string(actual)))
+      }
+      MatchingRule::StringLength(min, max) => match actual {
+        Value::String(s) => match_string_length(s, min, max),
+        _ => Err(anyhow!("Expected '{}' to be a string", json_to_string(actual)))
+      }
+      MatchingRule::Format(format) => match actual {
+        Value::String(s) => format.validate(s),
+        _ => Err(anyhow!("Expected '{}' to be a string", json_to_string(actual)))
+      }
+      MatchingRule::Approximately(tolerance) => match (self.as_f64(), actual.as_f64()) {
+        (Some(expected), Some(number)) => match_approximately(expected, number, tolerance),
+        (None, Some(number)) => Err(anyhow!("Unable to match {} approximately as the expected value '{}' is not a number",
+          number, json_to_string(self))),
+        (_, None) => Err(anyhow!("Expected '{}' to be a number", json_to_string(actual)))
+      }
+      MatchingRule::OneOf(values) => match_one_of(actual, values),
+      MatchingRule::JsonSchema(schema) => {
+        let errors = validate_json_schema(schema, actual);
+        if errors.is_empty() {
+          Ok(())
+        } else {
+          Err(anyhow!("Expected value to match the JSON schema: {}", errors.iter()
+            .map(|error| format!("'{}' - {}", json_pointer(&error.instance_path), error.message))
+            .join("; ")))
+        }
+      }
+      MatchingRule::Jwt(rules) => match (self, actual) {
+        (Value::String(expected), Value::String(actual)) => match_jwt(rules, expected, actual)
+          .map_err(|mismatches| anyhow!(jwt_mismatch_messages(&mismatches).join("; "))),
+        _ => Err(anyhow!("Expected '{}' to be a JWT", json_to_string(actual)))
+      }
       _ => Ok(())
     };
     debug!("JSON -> JSON: Comparing '{}' to '{}' using {:?} -> {:?}", self, actual, matcher, result);
@@ -306,8 +345,21 @@ pub fn display_diff(expected: &str, actual: &str, path: &str, indent: &str) -> S
 /// Compares the actual JSON to the expected one
 pub fn compare_json(path: &DocPath, expected: &Value, actual: &Value, context: &dyn MatchingContext) -> Result<(), Vec<Mismatch>> {
   debug!("compare: Comparing path {}", path);
+  explain_path(context, path, || compare_json_values(path, expected, actual, context))
+}
+
+fn compare_json_values(path: &DocPath, expected: &Value, actual: &Value, context: &dyn MatchingContext) -> Result<(), Vec<Mismatch>> {
   match (expected, actual) {
+    (&Value::Object(_), _) | (&Value::Array(_), _) if context.matcher_is_defined(path) &&
+      context.select_best_matcher(path).rules.iter().any(is_json_schema) => {
+      compare_values(path, expected, actual, context)
+    }
     (&Value::Object(ref emap), &Value::Object(ref amap)) => compare_maps(path, emap, amap, context),
+    (&Value::Array(ref elist), &Value::Array(ref alist)) => compare_lists(path, elist, alist, context),
+    (&Value::Object(_), _) | (&Value::Array(_), _) if context.matcher_is_defined(path) &&
+      context.select_best_matcher(path).rules.iter().any(|rule| rule.is_combinator()) => {
+      compare_values(path, expected, actual, context)
+    }
     (&Value::Object(_), _) => {
       Err(vec![ Mismatch::BodyMismatch {
         path: path.to_string(),
@@ -317,7 +369,6 @@ pub fn compare_json(path: &DocPath, expected: &Value, actual: &Value, context: &
                           type_of(expected), expected, type_of(actual), actual),
       } ])
     }
-    (&Value::Array(ref elist), &Value::Array(ref alist)) => compare_lists(path, elist, alist, context),
     (&Value::Array(_), _) => {
       Err(vec![ Mismatch::BodyMismatch {
         path: path.to_string(),
@@ -447,8 +498,15 @@ fn compare_values(
   context: &dyn MatchingContext
 ) -> Result<(), Vec<Mismatch>> {
   let matcher_result = if context.matcher_is_defined(path) {
+    let rules = context.select_best_matcher(path);
+    if rules.rules.iter().any(is_json_schema) {
+      return compare_with_json_schema(path, &rules, expected, actual);
+    }
+    if rules.rules.iter().any(is_jwt) {
+      return compare_with_jwt(path, &rules, expected, actual);
+    }
     debug!("compare_values: Calling match_values for path {}", path);
-    match_values(path, &context.select_best_matcher(&path), expected, actual)
+    match_values(path, &rules, expected, actual)
   } else {
     expected.matches_with(actual, &MatchingRule::Equality, false).map_err(|err| vec![err.to_string()])
   };
@@ -465,6 +523,221 @@ fn compare_values(
   })
 }
 
+/// Decodes the expected and actual tokens and compares them using the nested rules of the JWT
+/// matchers in the rule list. Each mismatch is returned with the path of the claim in the token
+/// (i.e. `$.token.jwt.claims.sub`). Any other rules in the list are applied to the value as normal.
+fn compare_with_jwt(
+  path: &DocPath,
+  rules: &RuleList,
+  expected: &Value,
+  actual: &Value
+) -> Result<(), Vec<Mismatch>> {
+  let (jwt_rules, other_rules) = partition_jwt_rules(rules);
+  let mut mismatches = vec![];
+  for rules in &jwt_rules {
+    debug!("compare_values: Comparing the JWT at path {}", path);
+    let result = match (expected, actual) {
+      (Value::String(expected), Value::String(actual)) => match_jwt(rules, expected, actual),
+      _ => Err(vec![("jwt".to_string(), format!("Expected '{}' to be a JWT", json_to_string(actual)))])
+    };
+    if let Err(errors) = result {
+      mismatches.extend(errors.iter().map(|(jwt_path, message)| Mismatch::BodyMismatch {
+        path: format!("{}.{}", path, jwt_path),
+        expected: Some(format!("{}", expected).into()),
+        actual: Some(format!("{}", actual).into()),
+        mismatch: message.clone()
+      }));
+    }
+  }
+
+  if !other_rules.is_empty() {
+    if let Err(messages) = match_values(path, &other_rules, expected, actual) {
+      mismatches.extend(messages.iter().map(|message| Mismatch::BodyMismatch {
+        path: path.to_string(),
+        expected: Some(format!("{}", expected).into()),
+        actual: Some(format!("{}", actual).into()),
+        mismatch: message.clone()
+      }));
+    }
+  }
+
+  if mismatches.is_empty() {
+    Ok(())
+  } else {
+    Err(mismatches)
+  }
+}
+
+fn is_json_schema(rule: &MatchingRule) -> bool {
+  matches!(rule, MatchingRule::JsonSchema(_))
+}
+
+/// Validates the actual value against the JSON Schemas in the rule list. Each schema error is
+/// returned as a mismatch with the path of the value in the actual document that failed validation.
+/// Any other rules in the list are applied to the value as normal.
+fn compare_with_json_schema(
+  path: &DocPath,
+  rules: &RuleList,
+  expected: &Value,
+  actual: &Value
+) -> Result<(), Vec<Mismatch>> {
+  let mut mismatches = vec![];
+  for schema in rules.rules.iter().filter_map(|rule| match rule {
+    MatchingRule::JsonSchema(schema) => Some(schema),
+    _ => None
+  }) {
+    debug!("compare_values: Validating the value at path {} against a JSON schema", path);
+    for error in validate_json_schema(schema, actual) {
+      let mut error_path = path.clone();
+      for token in &error.instance_path {
+        match token {
+          PathToken::Field(name) => { error_path.push_field(name.as_str()); }
+          PathToken::Index(index) => { error_path.push_index(*index); }
+          _ => ()
+        }
+      }
+      mismatches.push(Mismatch::BodyMismatch {
+        path: error_path.to_string(),
+        expected: resolve_path(expected, &error.instance_path).map(|value| value.to_string().into()),
+        actual: resolve_path(actual, &error.instance_path).map(|value| value.to_string().into()),
+        mismatch: error.message
+      });
+    }
+  }
+
+  let other_rules = RuleList {
+    rules: rules.rules.iter().filter(|rule| !is_json_schema(rule)).cloned().collect(),
+    .. rules.clone()
+  };
+  if !other_rules.is_empty() {
+    if let Err(messages) = match_values(path, &other_rules, expected, actual) {
+      mismatches.extend(messages.iter().map(|message| Mismatch::BodyMismatch {
+        path: path.to_string(),
+        expected: Some(format!("{}", expected).into()),
+        actual: Some(format!("{}", actual).into()),
+        mismatch: message.clone()
+      }));
+    }
+  }
+
+  if mismatches.is_empty() {
+    Ok(())
+  } else {
+    Err(mismatches)
+  }
+}
+
+/// Generates an RFC 6902 JSON Patch document that will transform the actual JSON into the expected
+/// JSON, for the paths of the given body mismatches. Only the mismatched values are patched, so
+/// values that matched via matching rules are left as is. Unexpected keys in the actual JSON will
+/// only be removed if the diff config is `NoUnexpectedKeys`. Mismatches at paths that do not exist
+/// in both documents (i.e. extra items matched against a type matcher) are ignored.
+pub fn json_patch(expected: &Value, actual: &Value, mismatches: &[Mismatch], config: DiffConfig) -> Value {
+  let pointers: Vec<(String, Vec<PathToken>)> = mismatches.iter()
+    .filter_map(|mismatch| match mismatch {
+      Mismatch::BodyMismatch { path, .. } => DocPath::new(path.as_str()).ok(),
+      _ => None
+    })
+    .map(|path| (json_pointer(path.tokens()), path.tokens().clone()))
+    .sorted_by(|a, b| a.0.cmp(&b.0))
+    .dedup_by(|a, b| a.0 == b.0)
+    .collect();
+
+  let mut ops = vec![];
+  for (pointer, tokens) in pointers {
+    if let (Some(expected), Some(actual)) = (resolve_path(expected, &tokens), resolve_path(actual, &tokens)) {
+      patch_value(&pointer, expected, actual, config, &mut ops);
+    }
+  }
+  Value::Array(order_patch_ops(ops))
+}
+
+/// Drops any operations for values under a value that is removed or replaced, and moves the
+/// remove operations after the others in descending path order, so that removing an item from an
+/// array does not change the indices of the other operations.
+fn order_patch_ops(ops: Vec<Value>) -> Vec<Value> {
+  let op_path = |op: &Value| op["path"].as_str().unwrap_or_default().to_string();
+  let covering = ops.iter()
+    .filter(|op| op["op"] == "remove" || op["op"] == "replace")
+    .map(op_path)
+    .collect::<Vec<_>>();
+  let (removes, others): (Vec<Value>, Vec<Value>) = ops.into_iter()
+    .filter(|op| {
+      let path = op_path(op);
+      !covering.iter().any(|parent| path.starts_with(&format!("{}/", parent)))
+    })
+    .partition(|op| op["op"] == "remove");
+  others.into_iter()
+    .chain(removes.into_iter().sorted_by(|a, b| compare_pointers(&op_path(b), &op_path(a))))
+    .collect()
+}
+
+/// Compares two JSON pointers, comparing array indices numerically
+fn compare_pointers(a: &str, b: &str) -> Ordering {
+  let a_tokens = a.split('/').collect::<Vec<_>>();
+  let b_tokens = b.split('/').collect::<Vec<_>>();
+  for (a_token, b_token) in a_tokens.iter().zip(b_tokens.iter()) {
+    let ordering = match (a_token.parse::<usize>(), b_token.parse::<usize>()) {
+      (Ok(a_index), Ok(b_index)) => a_index.cmp(&b_index),
+      _ => a_token.cmp(b_token)
+    };
+    if ordering != Ordering::Equal {
+      return ordering;
+    }
+  }
+  a_tokens.len().cmp(&b_tokens.len())
+}
+
+fn resolve_path<'a>(json: &'a Value, tokens: &[PathToken]) -> Option<&'a Value> {
+  tokens.iter().try_fold(json, |value, token| match token {
+    PathToken::Root => Some(value),
+    PathToken::Field(name) => value.as_object().and_then(|map| map.get(name)),
+    PathToken::Index(index) => value.as_array().and_then(|list| list.get(*index)),
+    _ => None
+  })
+}
+
+fn json_pointer(tokens: &[PathToken]) -> String {
+  tokens.iter()
+    .filter_map(|token| match token {
+      PathToken::Field(name) => Some(escape_pointer(name)),
+      PathToken::Index(index) => Some(index.to_string()),
+      _ => None
+    })
+    .map(|token| format!("/{}", token))
+    .collect()
+}
+
+fn escape_pointer(token: &str) -> String {
+  token.replace('~', "~0").replace('/', "~1")
+}
+
+fn patch_value(pointer: &str, expected: &Value, actual: &Value, config: DiffConfig, ops: &mut Vec<Value>) {
+  match (expected, actual) {
+    (Value::Object(emap), Value::Object(amap)) => {
+      for (key, value) in emap.iter().filter(|(key, _)| !amap.contains_key(*key)) {
+        ops.push(json!({ "op": "add", "path": format!("{}/{}", pointer, escape_pointer(key)), "value": value }));
+      }
+      if config == DiffConfig::NoUnexpectedKeys {
+        for key in amap.keys().filter(|key| !emap.contains_key(*key)) {
+          ops.push(json!({ "op": "remove", "path": format!("{}/{}", pointer, escape_pointer(key)) }));
+        }
+      }
+    }
+    (Value::Array(elist), Value::Array(alist)) => {
+      for (index, value) in elist.iter().enumerate().skip(alist.len()) {
+        ops.push(json!({ "op": "add", "path": format!("{}/{}", pointer, index), "value": value }));
+      }
+      for index in (elist.len()..alist.len()).rev() {
+        ops.push(json!({ "op": "remove", "path": format!("{}/{}", pointer, index) }));
+      }
+    }
+    _ => if expected != actual {
+      ops.push(json!({ "op": "replace", "path": pointer, "value": expected }));
+    }
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use std::collections::HashMap;
@@ -993,6 +1266,59 @@ mod tests {
     ]));
   }
 
+  #[test]
+  fn compare_lists_with_ignore_order_matcher() {
+    let expected = request!(r#"["read", "write", "admin"]"#);
+    let context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys, &matchingrules!{
+      "body" => {
+        "$" => [ MatchingRule::EqualsIgnoreOrder ]
+      }
+    }.rules_for_category("body").unwrap(), &hashmap!{});
+
+    expect!(match_json(&expected, &request!(r#"["admin", "read", "write"]"#), &context)).to(be_ok());
+    expect!(match_json(&expected, &request!(r#"["admin", "read", "write", "delete"]"#), &context)).to(be_err().value(vec![
+      BodyMismatch {
+        path: "$".to_string(),
+        expected: Some("[\"\\\"read\\\"\",\"\\\"write\\\"\",\"\\\"admin\\\"\"]".into()),
+        actual: Some("\"delete\"".into()),
+        mismatch: "Actual item at index 3 (\"delete\") was not expected".to_string()
+      }
+    ]));
+    expect!(match_json(&expected, &request!(r#"["admin", "read"]"#), &context)).to(be_err().value(vec![
+      BodyMismatch {
+        path: "$".to_string(),
+        expected: Some("\"write\"".into()),
+        actual: Some("[\"\\\"admin\\\"\",\"\\\"read\\\"\"]".into()),
+        mismatch: "Expected item at index 1 (\"write\") was not found in the actual list".to_string()
+      }
+    ]));
+    expect!(match_json(&expected, &request!(r#"["admin", "read", "read"]"#), &context)).to(be_err());
+    expect!(match_json(&request!("[]"), &request!(r#"["admin"]"#), &context)).to(be_err());
+  }
+
+  #[test]
+  fn compare_lists_with_ignore_order_matcher_uses_the_rules_for_each_item() {
+    let expected = request!(r#"[{"id": 1, "name": "read"}, {"id": 2, "name": "write"}]"#);
+    let context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys, &matchingrules!{
+      "body" => {
+        "$" => [ MatchingRule::EqualsIgnoreOrder ],
+        "$[*].id" => [ MatchingRule::Integer ]
+      }
+    }.rules_for_category("body").unwrap(), &hashmap!{});
+    expect!(match_json(&expected, &request!(r#"[{"id": 20, "name": "write"}, {"id": 10, "name": "read"}]"#), &context)).to(be_ok());
+    expect!(match_json(&expected, &request!(r#"[{"id": 20, "name": "write"}, {"id": 10, "name": "delete"}]"#), &context)).to(be_err());
+
+    // The first actual item matches both expected items, so the first expected item needs to be moved to the second
+    let expected = request!(r#"["ab", "ab"]"#);
+    let context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys, &matchingrules!{
+      "body" => {
+        "$" => [ MatchingRule::EqualsIgnoreOrder ],
+        "$[0]" => [ MatchingRule::Regex("a.".to_string()) ]
+      }
+    }.rules_for_category("body").unwrap(), &hashmap!{});
+    expect!(match_json(&expected, &request!(r#"["ab", "ax"]"#), &context)).to(be_ok());
+  }
+
   #[test]
   fn compare_lists_with_array_contains_matcher_with_more_complex_object() {
     let expected = request!(r#"
@@ -1129,4 +1455,210 @@ mod tests {
     let result = compare_maps(&DocPath::root(), expected, actual, &context);
     expect!(result).to(be_err());
   }
+
+  #[test]
+  fn compare_json_with_any_of_matcher_allows_nullable_values() {
+    let expected = json!({
+      "dob": "2000-01-01",
+      "address": { "street": "1 Main St" }
+    });
+    let context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys, &matchingrules! {
+      "body" => {
+        "$.dob" => [ MatchingRule::AnyOf(vec![MatchingRule::Null, MatchingRule::Date("yyyy-MM-dd".to_string())]) ],
+        "$.address" => [ MatchingRule::AnyOf(vec![MatchingRule::Null, MatchingRule::Type]) ]
+      }
+    }.rules_for_category("body").unwrap(), &hashmap!{});
+
+    let result = compare_json_values(&DocPath::root(), &expected, &json!({
+      "dob": null,
+      "address": null
+    }), &context);
+    expect!(result).to(be_ok());
+
+    let result = compare_json_values(&DocPath::root(), &expected, &json!({
+      "dob": "2021-10-18",
+      "address": { "street": "2 Other St" }
+    }), &context);
+    expect!(result).to(be_ok());
+
+    let result = compare_json_values(&DocPath::root(), &expected, &json!({
+      "dob": "18/10/2021",
+      "address": 100
+    }), &context);
+    expect!(result.unwrap_err().iter().map(|m| m.description()).collect::<Vec<String>>()).to(be_equal_to(vec![
+      "$.address -> Expected value to match any of the rules, but none did: \
+        rule 1 (null) - Expected '100' to be a null value; \
+        rule 2 (type) - Expected '{\"street\":\"1 Main St\"}' to be the same type as '100'".to_string(),
+      "$.dob -> Expected value to match any of the rules, but none did: \
+        rule 1 (null) - Expected '18/10/2021' to be a null value; \
+        rule 2 (date) - Expected '\"18/10/2021\"' to match a date format of 'yyyy-MM-dd': \
+        Error(Nom(\"/10/2021\", Tag))".to_string()
+    ]));
+  }
+
+  #[test]
+  fn compare_json_with_json_schema_matcher() {
+    let expected = json!({
+      "id": 1,
+      "customer": { "name": "Fred", "tags": ["a"] }
+    });
+    let context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys, &matchingrules! {
+      "body" => {
+        "$.customer" => [ MatchingRule::JsonSchema(json!({
+          "type": "object",
+          "properties": {
+            "name": { "type": "string", "minLength": 1 },
+            "tags": { "type": "array", "items": { "type": "string" } }
+          },
+          "required": ["name"]
+        })) ]
+      }
+    }.rules_for_category("body").unwrap(), &hashmap!{});
+
+    let result = compare_json_values(&DocPath::root(), &expected, &json!({
+      "id": 1,
+      "customer": { "name": "Mary", "tags": ["b", "c", "d"] }
+    }), &context);
+    expect!(result).to(be_ok());
+
+    let result = compare_json_values(&DocPath::root(), &expected, &json!({
+      "id": 1,
+      "customer": { "tags": ["b", 100] }
+    }), &context);
+    expect!(result.unwrap_err()).to(be_equal_to(vec![
+      Mismatch::BodyMismatch {
+        path: "$.customer".to_string(),
+        expected: Some("{\"name\":\"Fred\",\"tags\":[\"a\"]}".into()),
+        actual: Some("{\"tags\":[\"b\",100]}".into()),
+        mismatch: "Required property 'name' is missing".to_string()
+      },
+      Mismatch::BodyMismatch {
+        path: "$.customer.tags[1]".to_string(),
+        expected: None,
+        actual: Some("100".into()),
+        mismatch: "Expected '100' to be of type string".to_string()
+      }
+    ]));
+
+    let result = compare_json_values(&DocPath::root(), &expected, &json!({ "id": 1, "customer": null }), &context);
+    expect!(result.unwrap_err().iter().map(|m| m.description()).collect::<Vec<String>>()).to(be_equal_to(vec![
+      "$.customer -> Expected 'null' to be of type object".to_string()
+    ]));
+
+    let schema = MatchingRule::JsonSchema(json!({ "type": "array", "items": { "type": "integer" } }));
+    expect!(json!([1]).matches_with(&json!([1, 2]), &schema, false)).to(be_ok());
+    expect!(json!([1]).matches_with(&json!([1, "2"]), &schema, false).unwrap_err().to_string()).to(
+      be_equal_to("Expected value to match the JSON schema: '/1' - Expected '2' to be of type integer"));
+  }
+
+  #[test]
+  fn compare_json_with_jwt_matcher() {
+    let token = |claims: Value| pact_models::jwt::encode_jwt(&json!({ "claims": claims }), None).unwrap();
+    let expected = json!({ "token": token(json!({ "sub": "1234", "exp": 1700000000 })) });
+    let context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys, &matchingrules! {
+      "body" => {
+        "$.token" => [ MatchingRule::Jwt(matchingrules_list! {
+          "body";
+          "$.claims.sub" => [ MatchingRule::Regex("\\d+".to_string()) ],
+          "$.claims.exp" => [ MatchingRule::Integer ]
+        }) ]
+      }
+    }.rules_for_category("body").unwrap(), &hashmap!{});
+
+    let result = compare_json_values(&DocPath::root(), &expected,
+      &json!({ "token": token(json!({ "sub": "5678", "exp": 1800000000, "iss": "test" })) }), &context);
+    expect!(result).to(be_ok());
+
+    let result = compare_json_values(&DocPath::root(), &expected,
+      &json!({ "token": token(json!({ "sub": "abc", "exp": 1800000000 })) }), &context);
+    expect!(result.unwrap_err().iter().map(|m| m.description()).collect::<Vec<String>>()).to(be_equal_to(vec![
+      "$.token.jwt.claims.sub -> Expected 'abc' to match '\\d+'".to_string()
+    ]));
+
+    let result = compare_json_values(&DocPath::root(), &expected, &json!({ "token": 100 }), &context);
+    expect!(result.unwrap_err().iter().map(|m| m.description()).collect::<Vec<String>>()).to(be_equal_to(vec![
+      "$.token.jwt -> Expected '100' to be a JWT".to_string()
+    ]));
+  }
+
+  #[test]
+  fn json_patch_test() {
+    let mismatch = |path: &str| BodyMismatch {
+      path: path.to_string(),
+      expected: None,
+      actual: None,
+      mismatch: "".to_string()
+    };
+
+    let expected = json!({
+      "id": 100,
+      "name": "Fred",
+      "a/b": { "c~d": true },
+      "items": [1, 2, 3],
+      "address": { "street": "Main St", "city": "Springfield" }
+    });
+    let actual = json!({
+      "id": 101,
+      "name": "Fred",
+      "a/b": { "c~d": false },
+      "items": [1, 2, 3, 4, 5],
+      "address": { "street": "Main St", "country": "US" }
+    });
+    let mismatches = vec![
+      mismatch("$.id"),
+      mismatch("$['a/b']['c~d']"),
+      mismatch("$.items"),
+      mismatch("$.address"),
+      mismatch("$.id"),
+      Mismatch::StatusMismatch { expected: 200, actual: 404, mismatch: "".to_string() }
+    ];
+
+    expect!(json_patch(&expected, &actual, &mismatches, DiffConfig::AllowUnexpectedKeys)).to(be_equal_to(json!([
+      { "op": "add", "path": "/address/city", "value": "Springfield" },
+      { "op": "replace", "path": "/a~1b/c~0d", "value": true },
+      { "op": "replace", "path": "/id", "value": 100 },
+      { "op": "remove", "path": "/items/4" },
+      { "op": "remove", "path": "/items/3" }
+    ])));
+    expect!(json_patch(&expected, &actual, &[mismatch("$.address")], DiffConfig::NoUnexpectedKeys)).to(be_equal_to(json!([
+      { "op": "add", "path": "/address/city", "value": "Springfield" },
+      { "op": "remove", "path": "/address/country" }
+    ])));
+    expect!(json_patch(&json!([1, 2, 3]), &json!([1]), &[mismatch("$")], DiffConfig::AllowUnexpectedKeys)).to(be_equal_to(json!([
+      { "op": "add", "path": "/1", "value": 2 },
+      { "op": "add", "path": "/2", "value": 3 }
+    ])));
+    expect!(json_patch(&json!({"a": 1}), &json!([1]), &[mismatch("$")], DiffConfig::AllowUnexpectedKeys)).to(be_equal_to(json!([
+      { "op": "replace", "path": "", "value": { "a": 1 } }
+    ])));
+    expect!(json_patch(&json!([1]), &json!([1, "2"]), &[mismatch("$[1]")], DiffConfig::AllowUnexpectedKeys)).to(be_equal_to(json!([])));
+
+    let expected = json!({ "items": [ { "a": 1 }, { "a": 2 } ] });
+    let actual = json!({ "items": [ { "a": 1 }, { "a": 3 }, { "a": 4 }, { "a": 5 } ] });
+    expect!(json_patch(&expected, &actual, &[mismatch("$.items"), mismatch("$.items[1].a")], DiffConfig::AllowUnexpectedKeys)).to(be_equal_to(json!([
+      { "op": "replace", "path": "/items/1/a", "value": 2 },
+      { "op": "remove", "path": "/items/3" },
+      { "op": "remove", "path": "/items/2" }
+    ])));
+  }
+
+  #[test]
+  fn order_patch_ops_test() {
+    let ops = vec![
+      json!({ "op": "remove", "path": "/items/2" }),
+      json!({ "op": "replace", "path": "/items/2/a", "value": 1 }),
+      json!({ "op": "replace", "path": "/address", "value": {} }),
+      json!({ "op": "add", "path": "/address/city", "value": "Springfield" }),
+      json!({ "op": "remove", "path": "/items/10" }),
+      json!({ "op": "add", "path": "/addresses", "value": [] }),
+      json!({ "op": "replace", "path": "/items/1/a", "value": 1 })
+    ];
+    expect!(order_patch_ops(ops)).to(be_equal_to(vec![
+      json!({ "op": "replace", "path": "/address", "value": {} }),
+      json!({ "op": "add", "path": "/addresses", "value": [] }),
+      json!({ "op": "replace", "path": "/items/1/a", "value": 1 }),
+      json!({ "op": "remove", "path": "/items/10" }),
+      json!({ "op": "remove", "path": "/items/2" })
+    ]));
+  }
 }
@@ -0,0 +1,571 @@
+//! Module for validating JSON values against a JSON Schema. A subset of draft 2020-12 is
+//! supported: the type, enum and const keywords, the string, number, object and array validation
+//! keywords, the allOf/anyOf/oneOf/not and if/then/else applicators and local references
+//! (`$ref` values starting with `#`). The format keyword is treated as an annotation, as per the
+//! specification, and any other keywords are ignored.
+
+use onig::Regex;
+use pact_models::path_exp::PathToken;
+use serde_json::{Map, Value};
+
+use crate::matchers::{match_number_range, match_string_length};
+
+/// Maximum depth of schema references to resolve. This guards against schemas that refer to
+/// themselves without descending into the value.
+const MAX_REFERENCE_DEPTH: usize = 64;
+
+/// Error from validating a value against a JSON Schema
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SchemaError {
+  /// Location of the value that failed validation, relative to the value being validated
+  pub instance_path: Vec<PathToken>,
+  /// Description of the error
+  pub message: String
+}
+
+impl SchemaError {
+  fn new(path: &[PathToken], message: String) -> Self {
+    SchemaError {
+      instance_path: path.to_vec(),
+      message
+    }
+  }
+}
+
+/// Validates the value against the JSON Schema, returning all the validation errors
+pub(crate) fn validate_json_schema(schema: &Value, value: &Value) -> Vec<SchemaError> {
+  SchemaValidator { root: schema }.validate(schema, value, &[], 0)
+}
+
+struct SchemaValidator<'a> {
+  root: &'a Value
+}
+
+impl <'a> SchemaValidator<'a> {
+  fn validate(&self, schema: &Value, value: &Value, path: &[PathToken], depth: usize) -> Vec<SchemaError> {
+    let schema = match schema {
+      Value::Object(schema) => schema,
+      Value::Bool(false) => return vec![
+        SchemaError::new(path, format!("Expected no value, but got '{}'", display(value)))
+      ],
+      _ => return vec![]
+    };
+
+    let mut errors = vec![];
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+      errors.extend(self.validate_reference(reference, value, path, depth));
+    }
+    if let Some(types) = schema.get("type") {
+      let types = match types {
+        Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+        _ => types.as_str().map(|t| vec![t]).unwrap_or_default()
+      };
+      if !types.is_empty() && !types.iter().any(|t| is_of_type(value, t)) {
+        errors.push(SchemaError::new(path, format!("Expected '{}' to be of type {}",
+          display(value), types.join(" or "))));
+      }
+    }
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+      if !values.iter().any(|v| json_equal(v, value)) {
+        errors.push(SchemaError::new(path, format!("Expected '{}' to be one of {}",
+          display(value), Value::Array(values.clone()))));
+      }
+    }
+    if let Some(expected) = schema.get("const") {
+      if !json_equal(expected, value) {
+        errors.push(SchemaError::new(path, format!("Expected '{}' to be equal to '{}'",
+          display(value), display(expected))));
+      }
+    }
+
+    match value {
+      Value::String(s) => errors.extend(validate_string(schema, s, path)),
+      Value::Number(n) => if let Some(n) = n.as_f64() {
+        errors.extend(validate_number(schema, n, path));
+      }
+      Value::Object(map) => errors.extend(self.validate_object(schema, map, path, depth)),
+      Value::Array(items) => errors.extend(self.validate_array(schema, items, path, depth)),
+      _ => ()
+    }
+
+    errors.extend(self.validate_applicators(schema, value, path, depth));
+    errors
+  }
+
+  fn validate_reference(&self, reference: &str, value: &Value, path: &[PathToken], depth: usize) -> Vec<SchemaError> {
+    if depth >= MAX_REFERENCE_DEPTH {
+      vec![ SchemaError::new(path, format!("Exceeded the maximum depth of {} resolving the schema reference '{}'",
+        MAX_REFERENCE_DEPTH, reference)) ]
+    } else {
+      match reference.strip_prefix('#').and_then(|pointer| self.root.pointer(pointer)) {
+        Some(schema) => self.validate(schema, value, path, depth + 1),
+        None => vec![ SchemaError::new(path, format!("Could not resolve the schema reference '{}' \
+          (only references within the schema are supported)", reference)) ]
+      }
+    }
+  }
+
+  fn validate_object(
+    &self,
+    schema: &Map<String, Value>,
+    map: &Map<String, Value>,
+    path: &[PathToken],
+    depth: usize
+  ) -> Vec<SchemaError> {
+    let mut errors = vec![];
+
+    if let Some(required) = schema.get("required").and_then(Value::as_array) {
+      for name in required.iter().filter_map(Value::as_str) {
+        if !map.contains_key(name) {
+          errors.push(SchemaError::new(path, format!("Required property '{}' is missing", name)));
+        }
+      }
+    }
+    if let Some(dependent) = schema.get("dependentRequired").and_then(Value::as_object) {
+      for (key, required) in dependent.iter().filter(|(key, _)| map.contains_key(key.as_str())) {
+        for name in required.as_array().iter().flat_map(|names| names.iter()).filter_map(Value::as_str) {
+          if !map.contains_key(name) {
+            errors.push(SchemaError::new(path, format!("Property '{}' is required when '{}' is present", name, key)));
+          }
+        }
+      }
+    }
+    if let Some(min) = schema.get("minProperties").and_then(Value::as_u64) {
+      if (map.len() as u64) < min {
+        errors.push(SchemaError::new(path, format!("Expected an object with at least {} property(s), but it has {}",
+          min, map.len())));
+      }
+    }
+    if let Some(max) = schema.get("maxProperties").and_then(Value::as_u64) {
+      if (map.len() as u64) > max {
+        errors.push(SchemaError::new(path, format!("Expected an object with at most {} property(s), but it has {}",
+          max, map.len())));
+      }
+    }
+
+    let properties = schema.get("properties").and_then(Value::as_object);
+    let pattern_properties = schema.get("patternProperties").and_then(Value::as_object)
+      .map(|patterns| patterns.iter()
+        .map(|(pattern, schema)| (pattern, Regex::new(pattern), schema))
+        .collect::<Vec<_>>())
+      .unwrap_or_default();
+    for (pattern, regex, _) in &pattern_properties {
+      if let Err(err) = regex {
+        errors.push(SchemaError::new(path, format!("'{}' is not a valid regular expression - {}", pattern, err)));
+      }
+    }
+
+    for (key, value) in map {
+      let mut item_path = path.to_vec();
+      item_path.push(PathToken::Field(key.clone()));
+      let mut evaluated = false;
+
+      if let Some(schema) = properties.and_then(|properties| properties.get(key)) {
+        evaluated = true;
+        errors.extend(self.validate(schema, value, &item_path, depth));
+      }
+      for (_, regex, schema) in &pattern_properties {
+        if let Ok(regex) = regex {
+          if regex.find(key).is_some() {
+            evaluated = true;
+            errors.extend(self.validate(schema, value, &item_path, depth));
+          }
+        }
+      }
+      if !evaluated {
+        match schema.get("additionalProperties") {
+          Some(Value::Bool(false)) => errors.push(SchemaError::new(&item_path,
+            format!("Property '{}' is not allowed", key))),
+          Some(schema) => errors.extend(self.validate(schema, value, &item_path, depth)),
+          None => ()
+        }
+      }
+      if let Some(schema) = schema.get("propertyNames") {
+        errors.extend(self.validate(schema, &Value::String(key.clone()), &item_path, depth));
+      }
+    }
+
+    errors
+  }
+
+  fn validate_array(
+    &self,
+    schema: &Map<String, Value>,
+    items: &[Value],
+    path: &[PathToken],
+    depth: usize
+  ) -> Vec<SchemaError> {
+    let mut errors = vec![];
+
+    if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+      if (items.len() as u64) < min {
+        errors.push(SchemaError::new(path, format!("Expected an array with at least {} item(s), but it has {}",
+          min, items.len())));
+      }
+    }
+    if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+      if (items.len() as u64) > max {
+        errors.push(SchemaError::new(path, format!("Expected an array with at most {} item(s), but it has {}",
+          max, items.len())));
+      }
+    }
+    if schema.get("uniqueItems").and_then(Value::as_bool).unwrap_or(false) {
+      for (index, item) in items.iter().enumerate() {
+        if let Some(first) = items[..index].iter().position(|other| json_equal(other, item)) {
+          errors.push(SchemaError::new(path, format!("Expected the array items to be unique, but item {} is \
+            the same as item {}", index, first)));
+        }
+      }
+    }
+
+    let prefix_items = schema.get("prefixItems").and_then(Value::as_array).cloned().unwrap_or_default();
+    for (index, item) in items.iter().enumerate() {
+      let mut item_path = path.to_vec();
+      item_path.push(PathToken::Index(index));
+      if let Some(schema) = prefix_items.get(index) {
+        errors.extend(self.validate(schema, item, &item_path, depth));
+      } else if let Some(schema) = schema.get("items") {
+        errors.extend(self.validate(schema, item, &item_path, depth));
+      }
+    }
+
+    if let Some(contains) = schema.get("contains") {
+      let count = items.iter().enumerate()
+        .filter(|(index, item)| {
+          let mut item_path = path.to_vec();
+          item_path.push(PathToken::Index(*index));
+          self.validate(contains, item, &item_path, depth).is_empty()
+        })
+        .count() as u64;
+      let min = schema.get("minContains").and_then(Value::as_u64).unwrap_or(1);
+      if count < min {
+        errors.push(SchemaError::new(path, format!("Expected at least {} item(s) to match the 'contains' schema, \
+          but {} did", min, count)));
+      }
+      if let Some(max) = schema.get("maxContains").and_then(Value::as_u64) {
+        if count > max {
+          errors.push(SchemaError::new(path, format!("Expected at most {} item(s) to match the 'contains' schema, \
+            but {} did", max, count)));
+        }
+      }
+    }
+
+    errors
+  }
+
+  fn validate_applicators(
+    &self,
+    schema: &Map<String, Value>,
+    value: &Value,
+    path: &[PathToken],
+    depth: usize
+  ) -> Vec<SchemaError> {
+    let mut errors = vec![];
+
+    if let Some(schemas) = schema.get("allOf").and_then(Value::as_array) {
+      for schema in schemas {
+        errors.extend(self.validate(schema, value, path, depth));
+      }
+    }
+    if let Some(schemas) = schema.get("anyOf").and_then(Value::as_array) {
+      let results = schemas.iter()
+        .map(|schema| self.validate(schema, value, path, depth))
+        .collect::<Vec<_>>();
+      if !results.iter().any(|result| result.is_empty()) {
+        errors.push(SchemaError::new(path, format!("Expected '{}' to match at least one of the 'anyOf' schemas: {}",
+          display(value), describe_results(&results))));
+      }
+    }
+    if let Some(schemas) = schema.get("oneOf").and_then(Value::as_array) {
+      let results = schemas.iter()
+        .map(|schema| self.validate(schema, value, path, depth))
+        .collect::<Vec<_>>();
+      let count = results.iter().filter(|result| result.is_empty()).count();
+      if count == 0 {
+        errors.push(SchemaError::new(path, format!("Expected '{}' to match exactly one of the 'oneOf' schemas: {}",
+          display(value), describe_results(&results))));
+      } else if count > 1 {
+        errors.push(SchemaError::new(path, format!("Expected '{}' to match exactly one of the 'oneOf' schemas, \
+          but it matched {}", display(value), count)));
+      }
+    }
+    if let Some(schema) = schema.get("not") {
+      if self.validate(schema, value, path, depth).is_empty() {
+        errors.push(SchemaError::new(path, format!("Expected '{}' to not match the 'not' schema",
+          display(value))));
+      }
+    }
+    if let Some(condition) = schema.get("if") {
+      let branch = if self.validate(condition, value, path, depth).is_empty() {
+        schema.get("then")
+      } else {
+        schema.get("else")
+      };
+      if let Some(schema) = branch {
+        errors.extend(self.validate(schema, value, path, depth));
+      }
+    }
+
+    errors
+  }
+}
+
+fn validate_string(schema: &Map<String, Value>, s: &str, path: &[PathToken]) -> Vec<SchemaError> {
+  let mut errors = vec![];
+
+  let min = schema.get("minLength").and_then(Value::as_u64).map(|min| min as usize);
+  let max = schema.get("maxLength").and_then(Value::as_u64).map(|max| max as usize);
+  if let Err(err) = match_string_length(s, &min, &max) {
+    errors.push(SchemaError::new(path, err.to_string()));
+  }
+  if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+    match Regex::new(pattern) {
+      Ok(regex) => if regex.find(s).is_none() {
+        errors.push(SchemaError::new(path, format!("Expected '{}' to match the pattern '{}'", s, pattern)));
+      }
+      Err(err) => errors.push(SchemaError::new(path,
+        format!("'{}' is not a valid regular expression - {}", pattern, err)))
+    }
+  }
+
+  errors
+}
+
+fn validate_number(schema: &Map<String, Value>, n: f64, path: &[PathToken]) -> Vec<SchemaError> {
+  let mut errors = vec![];
+
+  let min = schema.get("minimum").and_then(Value::as_f64);
+  let max = schema.get("maximum").and_then(Value::as_f64);
+  if let Err(err) = match_number_range(n, &min, &max) {
+    errors.push(SchemaError::new(path, err.to_string()));
+  }
+  if let Some(min) = schema.get("exclusiveMinimum").and_then(Value::as_f64) {
+    if n <= min {
+      errors.push(SchemaError::new(path, format!("Expected {} to be greater than {}", n, min)));
+    }
+  }
+  if let Some(max) = schema.get("exclusiveMaximum").and_then(Value::as_f64) {
+    if n >= max {
+      errors.push(SchemaError::new(path, format!("Expected {} to be less than {}", n, max)));
+    }
+  }
+  if let Some(multiple) = schema.get("multipleOf").and_then(Value::as_f64) {
+    let quotient = n / multiple;
+    if multiple > 0.0 && (quotient - quotient.round()).abs() > 1e-9 {
+      errors.push(SchemaError::new(path, format!("Expected {} to be a multiple of {}", n, multiple)));
+    }
+  }
+
+  errors
+}
+
+fn is_of_type(value: &Value, value_type: &str) -> bool {
+  match value_type {
+    "null" => value.is_null(),
+    "boolean" => value.is_boolean(),
+    "object" => value.is_object(),
+    "array" => value.is_array(),
+    "string" => value.is_string(),
+    "number" => value.is_number(),
+    "integer" => value.as_f64().map(|n| n.fract() == 0.0).unwrap_or(false),
+    _ => false
+  }
+}
+
+/// JSON Schema equality, where numbers are compared by their numeric value (so 1 is equal to 1.0)
+fn json_equal(a: &Value, b: &Value) -> bool {
+  match (a, b) {
+    (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+    (Value::Array(a), Value::Array(b)) => a.len() == b.len() &&
+      a.iter().zip(b.iter()).all(|(a, b)| json_equal(a, b)),
+    (Value::Object(a), Value::Object(b)) => a.len() == b.len() &&
+      a.iter().all(|(key, value)| b.get(key).map(|other| json_equal(value, other)).unwrap_or(false)),
+    _ => a == b
+  }
+}
+
+/// Displays the value for an error message. Strings are displayed without quotes.
+fn display(value: &Value) -> String {
+  match value {
+    Value::String(s) => s.clone(),
+    _ => value.to_string()
+  }
+}
+
+fn describe_results(results: &[Vec<SchemaError>]) -> String {
+  results.iter().enumerate()
+    .map(|(index, errors)| format!("schema {} - {}", index + 1,
+      errors.iter().map(|error| error.message.as_str()).collect::<Vec<_>>().join(", ")))
+    .collect::<Vec<_>>()
+    .join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use serde_json::json;
+
+  use super::*;
+
+  fn messages(schema: &Value, value: &Value) -> Vec<String> {
+    validate_json_schema(schema, value).iter().map(|error| error.message.clone()).collect()
+  }
+
+  #[test]
+  fn boolean_schemas() {
+    expect!(validate_json_schema(&json!(true), &json!({ "a": 1 })).iter()).to(be_empty());
+    expect!(messages(&json!(false), &json!(1))).to(be_equal_to(vec!["Expected no value, but got '1'".to_string()]));
+  }
+
+  #[test]
+  fn type_enum_and_const() {
+    expect!(validate_json_schema(&json!({ "type": "integer" }), &json!(100)).iter()).to(be_empty());
+    expect!(validate_json_schema(&json!({ "type": "integer" }), &json!(100.0)).iter()).to(be_empty());
+    expect!(messages(&json!({ "type": "integer" }), &json!(100.5))).to(be_equal_to(vec![
+      "Expected '100.5' to be of type integer".to_string()
+    ]));
+    expect!(validate_json_schema(&json!({ "type": ["string", "null"] }), &Value::Null).iter()).to(be_empty());
+    expect!(messages(&json!({ "type": ["string", "null"] }), &json!(true))).to(be_equal_to(vec![
+      "Expected 'true' to be of type string or null".to_string()
+    ]));
+    expect!(validate_json_schema(&json!({ "enum": ["AUD", 1] }), &json!(1.0)).iter()).to(be_empty());
+    expect!(messages(&json!({ "enum": ["AUD", 1] }), &json!("USD"))).to(be_equal_to(vec![
+      "Expected 'USD' to be one of [\"AUD\",1]".to_string()
+    ]));
+    expect!(messages(&json!({ "const": "AUD" }), &json!("USD"))).to(be_equal_to(vec![
+      "Expected 'USD' to be equal to 'AUD'".to_string()
+    ]));
+  }
+
+  #[test]
+  fn string_and_number_keywords() {
+    let schema = json!({ "minLength": 2, "maxLength": 3, "pattern": "^[A-Z]+$" });
+    expect!(validate_json_schema(&schema, &json!("AUD")).iter()).to(be_empty());
+    expect!(messages(&schema, &json!("audd"))).to(be_equal_to(vec![
+      "Expected 'audd' to have a length of at most 3 character(s)".to_string(),
+      "Expected 'audd' to match the pattern '^[A-Z]+$'".to_string()
+    ]));
+
+    let schema = json!({ "minimum": 1, "exclusiveMaximum": 10, "multipleOf": 0.5 });
+    expect!(validate_json_schema(&schema, &json!(9.5)).iter()).to(be_empty());
+    expect!(messages(&schema, &json!(0.25))).to(be_equal_to(vec![
+      "Expected 0.25 to be greater than or equal to 1".to_string(),
+      "Expected 0.25 to be a multiple of 0.5".to_string()
+    ]));
+    expect!(messages(&schema, &json!(10))).to(be_equal_to(vec![
+      "Expected 10 to be less than 10".to_string()
+    ]));
+  }
+
+  #[test]
+  fn object_keywords() {
+    let schema = json!({
+      "type": "object",
+      "properties": {
+        "id": { "type": "integer" },
+        "name": { "type": "string" }
+      },
+      "patternProperties": {
+        "^x-": { "type": "string" }
+      },
+      "additionalProperties": false,
+      "required": ["id", "name"]
+    });
+    expect!(validate_json_schema(&schema, &json!({ "id": 1, "name": "Fred", "x-trace": "abc" })).iter()).to(be_empty());
+
+    let errors = validate_json_schema(&schema, &json!({ "id": "1", "x-trace": 1, "age": 10 }));
+    expect!(errors).to(be_equal_to(vec![
+      SchemaError::new(&[], "Required property 'name' is missing".to_string()),
+      SchemaError::new(&[PathToken::Field("age".to_string())], "Property 'age' is not allowed".to_string()),
+      SchemaError::new(&[PathToken::Field("id".to_string())], "Expected '1' to be of type integer".to_string()),
+      SchemaError::new(&[PathToken::Field("x-trace".to_string())], "Expected '1' to be of type string".to_string())
+    ]));
+  }
+
+  #[test]
+  fn array_keywords() {
+    let schema = json!({
+      "prefixItems": [ { "type": "string" } ],
+      "items": { "type": "integer" },
+      "minItems": 2,
+      "uniqueItems": true,
+      "contains": { "const": 10 }
+    });
+    expect!(validate_json_schema(&schema, &json!(["a", 1, 10])).iter()).to(be_empty());
+
+    let errors = validate_json_schema(&schema, &json!(["a", 1, 1, "b"]));
+    expect!(errors).to(be_equal_to(vec![
+      SchemaError::new(&[], "Expected the array items to be unique, but item 2 is the same as item 1".to_string()),
+      SchemaError::new(&[PathToken::Index(3)], "Expected 'b' to be of type integer".to_string()),
+      SchemaError::new(&[], "Expected at least 1 item(s) to match the 'contains' schema, but 0 did".to_string())
+    ]));
+    expect!(messages(&schema, &json!([10]))).to(be_equal_to(vec![
+      "Expected an array with at least 2 item(s), but it has 1".to_string(),
+      "Expected '10' to be of type string".to_string()
+    ]));
+  }
+
+  #[test]
+  fn applicators() {
+    let schema = json!({ "anyOf": [ { "type": "null" }, { "type": "string", "minLength": 1 } ] });
+    expect!(validate_json_schema(&schema, &Value::Null).iter()).to(be_empty());
+    expect!(validate_json_schema(&schema, &json!("a")).iter()).to(be_empty());
+    expect!(messages(&schema, &json!(""))).to(be_equal_to(vec![
+      "Expected '' to match at least one of the 'anyOf' schemas: schema 1 - Expected '' to be of type null; \
+      schema 2 - Expected '' to have a length of at least 1 character(s)".to_string()
+    ]));
+
+    let schema = json!({ "oneOf": [ { "type": "integer" }, { "minimum": 10 } ] });
+    expect!(validate_json_schema(&schema, &json!(5)).iter()).to(be_empty());
+    expect!(messages(&schema, &json!(20))).to(be_equal_to(vec![
+      "Expected '20' to match exactly one of the 'oneOf' schemas, but it matched 2".to_string()
+    ]));
+
+    let schema = json!({ "not": { "type": "string" } });
+    expect!(validate_json_schema(&schema, &json!(5)).iter()).to(be_empty());
+    expect!(messages(&schema, &json!("5"))).to(be_equal_to(vec![
+      "Expected '5' to not match the 'not' schema".to_string()
+    ]));
+
+    let schema = json!({
+      "if": { "properties": { "type": { "const": "card" } } },
+      "then": { "required": ["number"] },
+      "else": { "required": ["account"] }
+    });
+    expect!(validate_json_schema(&schema, &json!({ "type": "card", "number": "1234" })).iter()).to(be_empty());
+    expect!(messages(&schema, &json!({ "type": "bank" }))).to(be_equal_to(vec![
+      "Required property 'account' is missing".to_string()
+    ]));
+  }
+
+  #[test]
+  fn references() {
+    let schema = json!({
+      "$defs": {
+        "node": {
+          "type": "object",
+          "properties": {
+            "value": { "type": "integer" },
+            "children": { "type": "array", "items": { "$ref": "#/$defs/node" } }
+          }
+        }
+      },
+      "$ref": "#/$defs/node"
+    });
+    expect!(validate_json_schema(&schema, &json!({ "value": 1, "children": [ { "value": 2 } ] })).iter()).to(be_empty());
+    expect!(validate_json_schema(&schema, &json!({ "value": 1, "children": [ { "value": "2" } ] }))).to(be_equal_to(vec![
+      SchemaError::new(&[
+        PathToken::Field("children".to_string()),
+        PathToken::Index(0),
+        PathToken::Field("value".to_string())
+      ], "Expected '2' to be of type integer".to_string())
+    ]));
+
+    expect!(messages(&json!({ "$ref": "other.json" }), &json!(1))).to(be_equal_to(vec![
+      "Could not resolve the schema reference 'other.json' (only references within the schema are supported)".to_string()
+    ]));
+    expect!(messages(&json!({ "$ref": "#" }), &json!(1))).to(be_equal_to(vec![
+      "Exceeded the maximum depth of 64 resolving the schema reference '#'".to_string()
+    ]));
+  }
+}
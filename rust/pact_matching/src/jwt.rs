@@ -0,0 +1,157 @@
+//! Matching functions for JSON Web Tokens (JWT)
+
+use maplit::hashmap;
+use pact_models::jwt::decode_jwt;
+use pact_models::matchingrules::{MatchingRule, MatchingRuleCategory, RuleList, RuleLogic};
+use pact_models::path_exp::DocPath;
+use serde_json::Value;
+use tracing::debug;
+
+use crate::{CoreMatchingContext, DiffConfig, Mismatch};
+use crate::json::compare_json;
+
+pub(crate) fn is_jwt(rule: &MatchingRule) -> bool {
+  matches!(rule, MatchingRule::Jwt(_))
+}
+
+/// Splits the rule list into the nested rules of any JWT matchers and a rule list with the
+/// remaining rules
+pub(crate) fn partition_jwt_rules(rules: &RuleList) -> (Vec<MatchingRuleCategory>, RuleList) {
+  let jwt_rules = rules.rules.iter().filter_map(|rule| match rule {
+    MatchingRule::Jwt(rules) => Some(rules.clone()),
+    _ => None
+  }).collect();
+  let other_rules = RuleList {
+    rules: rules.rules.iter().filter(|rule| !is_jwt(rule)).cloned().collect(),
+    .. rules.clone()
+  };
+  (jwt_rules, other_rules)
+}
+
+/// Decodes the expected and actual tokens and compares the decoded claims using the nested matching
+/// rules. The signatures of the tokens are not verified, and the actual token may have additional
+/// claims. The token headers (i.e. `alg`, `kid`) are only compared if there are rules for them
+/// (i.e. `$.header.alg`), and then any other header fields are compared by type. Each mismatch is
+/// returned with its path relative to the token (i.e. `jwt.claims.sub`).
+pub(crate) fn match_jwt(
+  rules: &MatchingRuleCategory,
+  expected: &str,
+  actual: &str
+) -> Result<(), Vec<(String, String)>> {
+  let mut expected_token = decode_jwt(expected)
+    .map_err(|err| vec![("jwt".to_string(), format!("Unable to match '{}' as the expected value '{}' is not a valid JWT - {}", actual, expected, err))])?;
+  let mut actual_token = decode_jwt(actual)
+    .map_err(|err| vec![("jwt".to_string(), format!("Expected '{}' to be a valid JWT - {}", actual, err))])?;
+  debug!("Comparing JWT {} to {}", expected_token, actual_token);
+
+  let header_path = DocPath::root().join("header");
+  let mut rules = rules.clone();
+  if rules.rules.keys().any(|path| path.first_field() == Some("header")) {
+    if !rules.rules.contains_key(&header_path) {
+      rules.add_rule(header_path, MatchingRule::Type, RuleLogic::And);
+    }
+  } else {
+    for token in [&mut expected_token, &mut actual_token] {
+      if let Value::Object(map) = token {
+        map.remove("header");
+      }
+    }
+  }
+
+  let context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys, &rules, &hashmap!{});
+  compare_json(&DocPath::root(), &expected_token, &actual_token, &context)
+    .map_err(|mismatches| mismatches.iter().map(|mismatch| match mismatch {
+      Mismatch::BodyMismatch { path, mismatch, .. } =>
+        (format!("jwt{}", path.strip_prefix('$').unwrap_or(path)), mismatch.clone()),
+      _ => ("jwt".to_string(), mismatch.description())
+    }).collect())
+}
+
+/// Formats the JWT mismatches as `path -> message`
+pub(crate) fn jwt_mismatch_messages(mismatches: &[(String, String)]) -> Vec<String> {
+  mismatches.iter().map(|(path, message)| format!("{} -> {}", path, message)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use serde_json::json;
+
+  use pact_models::jwt::encode_jwt;
+  use pact_models::matchingrules::formats::StringFormat;
+  use pact_models::matchingrules_list;
+
+  use super::*;
+
+  #[test]
+  fn match_jwt_applies_the_rules_to_the_claims() {
+    let expected = encode_jwt(&json!({
+      "claims": { "sub": "c1e6f2b8-0d2e-4a57-a2a8-6a9a0b9d6c1e", "exp": 1700000000, "scope": "read" }
+    }), None).unwrap();
+    let rules = matchingrules_list! {
+      "body";
+      "$.claims.sub" => [ MatchingRule::Format(StringFormat::Uuid(None)) ],
+      "$.claims.exp" => [ MatchingRule::Integer ]
+    };
+
+    let actual = encode_jwt(&json!({
+      "claims": { "sub": "7d0c1c8e-6f5b-4b1a-9a43-5b4f6f0f2d11", "exp": 1800000000, "scope": "read", "iss": "test" }
+    }), None).unwrap();
+    expect!(match_jwt(&rules, expected.as_str(), format!("Bearer {}", actual).as_str())).to(be_ok());
+
+    let signed = encode_jwt(&json!({
+      "header": { "kid": "key-1" },
+      "claims": { "sub": "7d0c1c8e-6f5b-4b1a-9a43-5b4f6f0f2d11", "exp": 1800000000, "scope": "read" }
+    }), Some("secret")).unwrap();
+    expect!(match_jwt(&rules, expected.as_str(), signed.as_str())).to(be_ok());
+
+    let actual = encode_jwt(&json!({
+      "claims": { "sub": "1234", "exp": "soon", "scope": "write" }
+    }), None).unwrap();
+    let mut mismatches = match_jwt(&rules, expected.as_str(), actual.as_str()).unwrap_err();
+    mismatches.sort();
+    expect!(mismatches).to(be_equal_to(vec![
+      ("jwt.claims.exp".to_string(), "Expected 'soon' to be an integer value".to_string()),
+      ("jwt.claims.scope".to_string(), "Expected 'read' to be equal to 'write'".to_string()),
+      ("jwt.claims.sub".to_string(), "Expected '1234' to be a UUID".to_string())
+    ]));
+  }
+
+  #[test]
+  fn match_jwt_only_compares_the_header_if_there_are_rules_for_it() {
+    let expected = encode_jwt(&json!({
+      "header": { "kid": "key-1" },
+      "claims": { "sub": "1234" }
+    }), Some("secret")).unwrap();
+    let actual = encode_jwt(&json!({
+      "header": { "kid": "key-2" },
+      "claims": { "sub": "1234" }
+    }), None).unwrap();
+    expect!(match_jwt(&MatchingRuleCategory::empty("body"), expected.as_str(), actual.as_str())).to(be_ok());
+
+    let rules = matchingrules_list! {
+      "body";
+      "$.header.alg" => [ MatchingRule::Regex("^(HS|RS)256$".to_string()) ]
+    };
+    expect!(match_jwt(&rules, expected.as_str(), actual.as_str())).to(be_err().value(vec![
+      ("jwt.header.alg".to_string(), "Expected 'none' to match '^(HS|RS)256$'".to_string())
+    ]));
+
+    let actual = encode_jwt(&json!({
+      "header": { "kid": 2 },
+      "claims": { "sub": "1234" }
+    }), Some("other")).unwrap();
+    expect!(match_jwt(&rules, expected.as_str(), actual.as_str())).to(be_err().value(vec![
+      ("jwt.header.kid".to_string(), "Expected 'key-1' to be the same type as '2'".to_string())
+    ]));
+  }
+
+  #[test]
+  fn match_jwt_with_an_invalid_token() {
+    let expected = encode_jwt(&json!({ "claims": { "sub": "1234" } }), None).unwrap();
+    let rules = MatchingRuleCategory::empty("body");
+    expect!(match_jwt(&rules, expected.as_str(), "not-a-token")).to(be_err().value(vec![
+      ("jwt".to_string(), "Expected 'not-a-token' to be a valid JWT - 'not-a-token' is not a JWT, expected three segments separated by '.'".to_string())
+    ]));
+  }
+}
@@ -204,9 +204,17 @@
 //!
 //! Request headers are matched by excluding the cookie header.
 //!
-//! #### Matching Request cookies
+//! #### Matching cookies
 //!
-//! If the list of expected cookies contains all the actual cookies, the cookies match.
+//! `Cookie` and `Set-Cookie` headers are parsed into the individual cookies (and for `Set-Cookie`,
+//! the attributes of each cookie). Unless there is a matcher defined for the whole header:
+//!
+//! 1. Each expected cookie must be present in the actual header. Additional actual cookies are allowed.
+//! 2. If there is a matcher defined for `header.<HEADER_KEY>.<COOKIE_NAME>`, the cookie value is
+//!    compared with that matcher, otherwise the values must be equal.
+//! 3. Each expected attribute of a `Set-Cookie` cookie must be present (attribute names are not
+//!    case-sensitive). Attribute values are compared with the matcher defined for
+//!    `header.Set-Cookie.<COOKIE_NAME>.<ATTRIBUTE>`, otherwise they must be equal.
 //!
 //! ### Matching Status Codes
 //!
@@ -368,6 +376,7 @@ use pact_models::matchingrules::{Category, MatchingRule, MatchingRuleCategory, R
 use pact_models::pact::Pact;
 use pact_models::PactSpecification;
 use pact_models::path_exp::DocPath;
+use pact_models::query_strings::QueryStructure;
 use pact_models::v4::http_parts::{HttpRequest, HttpResponse};
 use pact_models::v4::message_parts::MessageContents;
 use pact_models::v4::sync_message::SynchronousMessage;
@@ -376,12 +385,13 @@ use pact_plugin_driver::plugin_models::PluginInteractionConfig;
 use serde_json::{json, Value};
 use tracing::{debug, error, info, warn};
 
+use crate::explain::{explain_path, Explainer, ExplainingMatchingContext, MatchExplanation};
 use crate::generators::{DefaultVariantMatcher, generators_process_body};
 use crate::headers::{match_header_value, match_headers};
 use crate::json::match_json;
 use crate::matchers::*;
 use crate::matchingrules::DisplayForMismatch;
-use crate::query::match_query_maps;
+use crate::query::{match_query_maps, match_structured_query_maps};
 
 /// Simple macro to convert a string slice to a `String` struct.
 #[macro_export]
@@ -394,13 +404,18 @@ pub const PACT_RUST_VERSION: Option<&'static str> = option_env!("CARGO_PKG_VERSI
 
 pub mod matchers;
 pub mod json;
+pub mod explain;
 pub mod logging;
 pub mod matchingrules;
 pub mod metrics;
 
 mod xml;
 mod binary_utils;
+mod form_urlencoded;
 mod headers;
+mod json_schema;
+mod jwt;
+mod ndjson;
 mod generators;
 mod query;
 
@@ -435,6 +450,12 @@ pub trait MatchingContext {
 
   /// Clones the current context with the provided matching rules
   fn clone_with(&self, matchers: &MatchingRuleCategory) -> Box<dyn MatchingContext>;
+
+  /// Returns the explainer used to record how the matching rules were applied, if explain mode
+  /// is enabled for the context
+  fn explainer(&self) -> Option<&Explainer> {
+    None
+  }
 }
 
 #[derive(Debug, Clone)]
@@ -572,7 +593,7 @@ impl MatchingContext for CoreMatchingContext {
                 Either::Left(rule) => {
                   for key in &actual_keys {
                     let key_path = path.join(key);
-                    if let Err(err) = String::default().matches_with(key, &rule, false) {
+                    if let Err(err) = match_rule(&String::default(), key, &rule, false) {
                       result.push(Mismatch::BodyMismatch {
                         path: key_path.to_string(),
                         expected: Some("".to_string().into()),
@@ -631,12 +652,14 @@ impl MatchingContext for CoreMatchingContext {
 lazy_static! {
   static ref BODY_MATCHERS: [
     (fn(content_type: &ContentType) -> bool,
-    fn(expected: &dyn HttpPart, actual: &dyn HttpPart, context: &dyn MatchingContext) -> Result<(), Vec<Mismatch>>); 4]
+    fn(expected: &dyn HttpPart, actual: &dyn HttpPart, context: &dyn MatchingContext) -> Result<(), Vec<Mismatch>>); 6]
      = [
       (|content_type| { content_type.is_json() }, json::match_json),
+      (|content_type| { content_type.is_ndjson() }, ndjson::match_ndjson),
       (|content_type| { content_type.is_xml() }, xml::match_xml),
       (|content_type| { content_type.base_type() == "application/octet-stream" }, binary_utils::match_octet_stream),
-      (|content_type| { content_type.base_type() == "multipart/form-data" }, binary_utils::match_mime_multipart)
+      (|content_type| { content_type.base_type() == "multipart/form-data" }, binary_utils::match_mime_multipart),
+      (|content_type| { content_type.base_type() == "application/x-www-form-urlencoded" }, form_urlencoded::match_form_urlencoded)
   ];
 }
 
@@ -992,6 +1015,34 @@ impl BodyMatchResult {
       _ => true
     }
   }
+
+  /// Returns an RFC 6902 JSON Patch document that will transform the actual body into the
+  /// expected one for the body mismatches. Returns `None` if there are no body mismatches, or
+  /// either of the bodies is not JSON.
+  pub fn json_patch(&self, expected: &OptionalBody, actual: &OptionalBody, config: DiffConfig) -> Option<Value> {
+    match self {
+      BodyMatchResult::BodyMismatches(_) => body_json_patch(expected, actual, &self.mismatches(), config),
+      _ => None
+    }
+  }
+}
+
+/// Returns an RFC 6902 JSON Patch document that will transform the actual body into the expected
+/// one for the given mismatches (see `json::json_patch`). Returns `None` if there are no body
+/// mismatches, or either of the bodies is not JSON.
+pub fn body_json_patch(
+  expected: &OptionalBody,
+  actual: &OptionalBody,
+  mismatches: &[Mismatch],
+  config: DiffConfig
+) -> Option<Value> {
+  if mismatches.iter().any(|mismatch| matches!(mismatch, Mismatch::BodyMismatch { .. })) {
+    let expected_json = expected.value().and_then(|body| serde_json::from_slice::<Value>(&body).ok())?;
+    let actual_json = actual.value().and_then(|body| serde_json::from_slice::<Value>(&body).ok())?;
+    Some(json::json_patch(&expected_json, &actual_json, mismatches, config))
+  } else {
+    None
+  }
 }
 
 /// Result of matching a request
@@ -1165,18 +1216,20 @@ pub fn match_method(expected: &str, actual: &str) -> Result<(), Mismatch> {
 /// Matches the actual request path to the expected one.
 pub fn match_path(expected: &str, actual: &str, context: &(dyn MatchingContext + Send + Sync)) -> Result<(), Vec<Mismatch>> {
   let path = DocPath::empty();
-  let matcher_result = if context.matcher_is_defined(&path) {
-    match_values(&path, &context.select_best_matcher(&path), expected.to_string(), actual.to_string())
-  } else {
-    expected.matches_with(actual, &MatchingRule::Equality, false).map_err(|err| vec![err])
-      .map_err(|errors| errors.iter().map(|err| err.to_string()).collect())
-  };
-  matcher_result.map_err(|messages| messages.iter().map(|message| {
-    Mismatch::PathMismatch {
-      expected: expected.to_string(),
-      actual: actual.to_string(), mismatch: message.clone()
-    }
-  }).collect())
+  explain_path(context, &path, || {
+    let matcher_result = if context.matcher_is_defined(&path) {
+      match_values(&path, &context.select_best_matcher(&path), expected.to_string(), actual.to_string())
+    } else {
+      expected.matches_with(actual, &MatchingRule::Equality, false).map_err(|err| vec![err])
+        .map_err(|errors| errors.iter().map(|err| err.to_string()).collect())
+    };
+    matcher_result.map_err(|messages| messages.iter().map(|message| {
+      Mismatch::PathMismatch {
+        expected: expected.to_string(),
+        actual: actual.to_string(), mismatch: message.clone()
+      }
+    }).collect())
+  })
 }
 
 /// Matches the actual query parameters to the expected ones.
@@ -1207,6 +1260,20 @@ pub fn match_query(
   }
 }
 
+/// Matches the actual query parameters to the expected ones as nested parameters (i.e.
+/// `filter[status]=open`). The matching rules in the context are applied with the JSON body
+/// matching, so the context needs to have the query matching rules in the body category.
+pub fn match_structured_query(
+  expected: Option<HashMap<String, Vec<String>>>,
+  actual: Option<HashMap<String, Vec<String>>>,
+  context: &(dyn MatchingContext + Send + Sync)
+) -> HashMap<String, Vec<Mismatch>> {
+  match (actual, expected) {
+    (Some(aqm), Some(eqm)) => match_structured_query_maps(eqm, aqm, context),
+    (actual, expected) => match_query(expected, actual, context)
+  }
+}
+
 fn group_by<I, F, K>(items: I, f: F) -> HashMap<K, Vec<I::Item>>
   where I: IntoIterator, F: Fn(&I::Item) -> K, K: Eq + Hash {
   let mut m = hashmap!{};
@@ -1230,8 +1297,9 @@ async fn compare_bodies(
       debug!("Using content matcher {} for content type '{}'", matcher.catalogue_entry_key(), content_type);
       if matcher.is_core() {
         if let Err(m) = match matcher.catalogue_entry_key().as_str() {
-          // TODO: "core/content-matcher/form-urlencoded" => ,
+          "core/content-matcher/form-urlencoded" => form_urlencoded::match_form_urlencoded(expected, actual, context),
           "core/content-matcher/json" => match_json(expected, actual, context),
+          "core/content-matcher/ndjson" => ndjson::match_ndjson(expected, actual, context),
           "core/content-matcher/multipart-form-data" => binary_utils::match_mime_multipart(expected, actual, context),
           "core/content-matcher/text" => match_text(&expected.body().value(), &actual.body().value(), context),
           "core/content-matcher/xml" => xml::match_xml(expected, actual, context),
@@ -1379,6 +1447,40 @@ pub async fn match_request<'a>(
   actual: HttpRequest,
   pact: &Box<dyn Pact + Send + Sync + 'a>,
   interaction: &Box<dyn Interaction + Send + Sync>
+) -> RequestMatchResult {
+  match_request_internal(expected, actual, pact, interaction, &None).await
+}
+
+/// Matches the expected and actual requests with explain mode enabled. Returns the match result
+/// along with an explanation of the matching rules that were applied at each path that was visited.
+pub async fn match_request_with_explanation<'a>(
+  expected: HttpRequest,
+  actual: HttpRequest,
+  pact: &Box<dyn Pact + Send + Sync + 'a>,
+  interaction: &Box<dyn Interaction + Send + Sync>
+) -> (RequestMatchResult, MatchExplanation) {
+  let explainer = Explainer::new();
+  let result = match_request_internal(expected, actual, pact, interaction, &Some(explainer.clone())).await;
+  (result, explainer.explanation())
+}
+
+fn context_with_explainer(
+  context: CoreMatchingContext,
+  explainer: &Option<Explainer>,
+  category: &str
+) -> Box<dyn MatchingContext + Send + Sync> {
+  match explainer {
+    Some(explainer) => Box::new(ExplainingMatchingContext::new(context, explainer.for_category(category))),
+    None => Box::new(context)
+  }
+}
+
+async fn match_request_internal<'a>(
+  expected: HttpRequest,
+  actual: HttpRequest,
+  pact: &Box<dyn Pact + Send + Sync + 'a>,
+  interaction: &Box<dyn Interaction + Send + Sync>,
+  explainer: &Option<Explainer>
 ) -> RequestMatchResult {
   info!("comparing to expected {}", expected);
   debug!("     body: '{}'", expected.body.str_value());
@@ -1386,24 +1488,34 @@ pub async fn match_request<'a>(
   debug!("     generators: {:?}", expected.generators);
 
   let plugin_data = setup_plugin_config(pact, interaction);
-  let path_context = CoreMatchingContext::new(DiffConfig::NoUnexpectedKeys,
+  let path_context = context_with_explainer(CoreMatchingContext::new(DiffConfig::NoUnexpectedKeys,
     &expected.matching_rules.rules_for_category("path").unwrap_or_default(),
-    &plugin_data);
-  let body_context = CoreMatchingContext::new(DiffConfig::NoUnexpectedKeys,
+    &plugin_data), explainer, "path");
+  let body_context = context_with_explainer(CoreMatchingContext::new(DiffConfig::NoUnexpectedKeys,
     &expected.matching_rules.rules_for_category("body").unwrap_or_default(),
-    &plugin_data);
-  let query_context = CoreMatchingContext::new(DiffConfig::NoUnexpectedKeys,
-    &expected.matching_rules.rules_for_category("query").unwrap_or_default(),
-    &plugin_data);
-  let header_context = CoreMatchingContext::new(DiffConfig::NoUnexpectedKeys,
+    &plugin_data), explainer, "body");
+  let query_structure = interaction.as_v4()
+    .map(|i| QueryStructure::from_comments(&i.comments()))
+    .unwrap_or_default();
+  let query_rules = expected.matching_rules.rules_for_category("query").unwrap_or_default();
+  let query_rules = match query_structure {
+    QueryStructure::Nested => MatchingRuleCategory { name: Category::BODY, .. query_rules },
+    QueryStructure::Flat => query_rules
+  };
+  let query_context = context_with_explainer(CoreMatchingContext::new(DiffConfig::NoUnexpectedKeys,
+    &query_rules, &plugin_data), explainer, "query");
+  let header_context = context_with_explainer(CoreMatchingContext::new(DiffConfig::NoUnexpectedKeys,
     &expected.matching_rules.rules_for_category("header").unwrap_or_default(),
-    &plugin_data);
+    &plugin_data), explainer, "header");
   let result = RequestMatchResult {
     method: match_method(&expected.method, &actual.method).err(),
-    path: match_path(&expected.path, &actual.path, &path_context).err(),
-    body: match_body(&expected, &actual, &body_context, &header_context).await,
-    query: match_query(expected.query, actual.query, &query_context),
-    headers: match_headers(expected.headers, actual.headers, &header_context)
+    path: match_path(&expected.path, &actual.path, path_context.as_ref()).err(),
+    body: match_body(&expected, &actual, body_context.as_ref(), header_context.as_ref()).await,
+    query: match query_structure {
+      QueryStructure::Nested => match_structured_query(expected.query, actual.query, query_context.as_ref()),
+      QueryStructure::Flat => match_query(expected.query, actual.query, query_context.as_ref())
+    },
+    headers: match_headers(expected.headers, actual.headers, header_context.as_ref())
   };
 
   debug!("--> Mismatches: {:?}", result.mismatches());
@@ -1413,24 +1525,26 @@ pub async fn match_request<'a>(
 /// Matches the actual response status to the expected one.
 pub fn match_status(expected: u16, actual: u16, context: &dyn MatchingContext) -> Result<(), Vec<Mismatch>> {
   let path = DocPath::empty();
-  if context.matcher_is_defined(&path) {
-    match_values(&path, &context.select_best_matcher(&path), expected, actual)
-      .map_err(|messages| messages.iter().map(|message| {
-        Mismatch::StatusMismatch {
-          expected,
-          actual,
-          mismatch: message.clone()
-        }
-      }).collect())
-  } else if expected != actual {
-    Err(vec![Mismatch::StatusMismatch {
-      expected,
-      actual,
-      mismatch: format!("expected {} but was {}", expected, actual)
-    }])
-  } else {
-    Ok(())
-  }
+  explain_path(context, &path, || {
+    if context.matcher_is_defined(&path) {
+      match_values(&path, &context.select_best_matcher(&path), expected, actual)
+        .map_err(|messages| messages.iter().map(|message| {
+          Mismatch::StatusMismatch {
+            expected,
+            actual,
+            mismatch: message.clone()
+          }
+        }).collect())
+    } else if expected != actual {
+      Err(vec![Mismatch::StatusMismatch {
+        expected,
+        actual,
+        mismatch: format!("expected {} but was {}", expected, actual)
+      }])
+    } else {
+      Ok(())
+    }
+  })
 }
 
 /// Matches the actual and expected responses.
@@ -1439,29 +1553,52 @@ pub async fn match_response<'a>(
   actual: HttpResponse,
   pact: &Box<dyn Pact + Send + Sync + 'a>,
   interaction: &Box<dyn Interaction + Send + Sync>
+) -> Vec<Mismatch> {
+  match_response_internal(expected, actual, pact, interaction, &None).await
+}
+
+/// Matches the actual and expected responses with explain mode enabled. Returns the mismatches
+/// along with an explanation of the matching rules that were applied at each path that was visited.
+pub async fn match_response_with_explanation<'a>(
+  expected: HttpResponse,
+  actual: HttpResponse,
+  pact: &Box<dyn Pact + Send + Sync + 'a>,
+  interaction: &Box<dyn Interaction + Send + Sync>
+) -> (Vec<Mismatch>, MatchExplanation) {
+  let explainer = Explainer::new();
+  let mismatches = match_response_internal(expected, actual, pact, interaction, &Some(explainer.clone())).await;
+  (mismatches, explainer.explanation())
+}
+
+async fn match_response_internal<'a>(
+  expected: HttpResponse,
+  actual: HttpResponse,
+  pact: &Box<dyn Pact + Send + Sync + 'a>,
+  interaction: &Box<dyn Interaction + Send + Sync>,
+  explainer: &Option<Explainer>
 ) -> Vec<Mismatch> {
   let mut mismatches = vec![];
 
   info!("comparing to expected response: {}", expected);
   let plugin_data = setup_plugin_config(pact, interaction);
 
-  let status_context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys,
+  let status_context = context_with_explainer(CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys,
     &expected.matching_rules.rules_for_category("status").unwrap_or_default(),
-    &plugin_data);
-  let body_context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys,
+    &plugin_data), explainer, "status");
+  let body_context = context_with_explainer(CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys,
     &expected.matching_rules.rules_for_category("body").unwrap_or_default(),
-    &plugin_data);
-  let header_context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys,
+    &plugin_data), explainer, "body");
+  let header_context = context_with_explainer(CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys,
     &expected.matching_rules.rules_for_category("header").unwrap_or_default(),
-    &plugin_data);
+    &plugin_data), explainer, "header");
 
-  mismatches.extend_from_slice(match_body(&expected, &actual, &body_context, &header_context).await
+  mismatches.extend_from_slice(match_body(&expected, &actual, body_context.as_ref(), header_context.as_ref()).await
     .mismatches().as_slice());
-  if let Err(m) = match_status(expected.status, actual.status, &status_context) {
+  if let Err(m) = match_status(expected.status, actual.status, status_context.as_ref()) {
     mismatches.extend_from_slice(&m);
   }
   let result = match_headers(expected.headers, actual.headers,
-                             &header_context);
+                             header_context.as_ref());
   for values in result.values() {
     mismatches.extend_from_slice(values.as_slice());
   }
@@ -1899,6 +2036,43 @@ pub async fn match_interaction(
   }
 }
 
+/// Validates that the examples in the interaction satisfy its own matching rules. The example
+/// request and response (or message) are matched against themselves, so any mismatch means the
+/// example does not satisfy a matching rule. Parts without any matching rules are not checked.
+/// Returns each mismatch along with the part of the interaction it came from (`request`,
+/// `response` or `message`).
+pub async fn validate_interaction_examples(
+  interaction: &Box<dyn Interaction + Send + Sync>,
+  pact: &Box<dyn Pact + Send + Sync>
+) -> Vec<(String, Mismatch)> {
+  let mut results = vec![];
+  if let Some(http) = interaction.as_v4_http() {
+    if http.request.matching_rules.is_not_empty() {
+      let request_result = match_request(http.request.clone(), http.request.clone(), pact, interaction).await;
+      results.extend(request_result.mismatches().iter().map(|m| ("request".to_string(), m.clone())));
+    }
+    if http.response.matching_rules.is_not_empty() {
+      let response_result = match_response(http.response.clone(), http.response.clone(), pact, interaction).await;
+      results.extend(response_result.iter().map(|m| ("response".to_string(), m.clone())));
+    }
+  } else if let Some(message) = interaction.as_v4_sync_message() {
+    if message.request.matching_rules.is_not_empty() {
+      let request_result = match_sync_message_request(&message, &message, pact).await;
+      results.extend(request_result.iter().map(|m| ("request".to_string(), m.clone())));
+    }
+    if message.response.iter().any(|response| response.matching_rules.is_not_empty()) {
+      let response_result = match_sync_message_response(&message, &message.response, &message.response, pact).await;
+      results.extend(response_result.iter().map(|m| ("response".to_string(), m.clone())));
+    }
+  } else if let Some(message) = interaction.as_v4_async_message() {
+    if message.contents.matching_rules.is_not_empty() {
+      let message_result = match_message(interaction, interaction, pact).await;
+      results.extend(message_result.iter().map(|m| ("message".to_string(), m.clone())));
+    }
+  }
+  results
+}
+
 #[cfg(test)]
 mod tests;
 #[cfg(test)]
@@ -8,7 +8,8 @@ use lazy_static::lazy_static;
 use maplit::hashmap;
 use onig::Regex;
 use pact_models::HttpStatus;
-use pact_models::matchingrules::{MatchingRule, RuleList, RuleLogic};
+use pact_models::json_utils::json_to_string;
+use pact_models::matchingrules::{MatchingRule, NumberTolerance, RuleList, RuleLogic};
 use pact_models::path_exp::DocPath;
 use pact_models::time_utils::validate_datetime;
 use pact_plugin_driver::catalogue_manager::{
@@ -18,9 +19,11 @@ use pact_plugin_driver::catalogue_manager::{
   register_core_entries
 };
 use semver::Version;
+use serde_json::{json, Value};
 use tracing::{debug, trace};
 
 use crate::binary_utils::match_content_type;
+use crate::jwt::{jwt_mismatch_messages, match_jwt};
 
 lazy_static! {
   /// Content matcher/generator entries to add to the plugin catalogue
@@ -44,6 +47,15 @@ lazy_static! {
         "content-types".to_string() => "application/.*json,application/json-rpc,application/jsonrequest".to_string()
       }
     });
+    entries.push(CatalogueEntry {
+      entry_type: CatalogueEntryType::CONTENT_MATCHER,
+      provider_type: CatalogueEntryProviderType::CORE,
+      plugin: None,
+      key: "ndjson".to_string(),
+      values: hashmap!{
+        "content-types".to_string() => "application/x-ndjson;application/ndjson;application/jsonl;application/x-jsonl;application/jsonlines;application/x-jsonlines".to_string()
+      }
+    });
     entries.push(CatalogueEntry {
       entry_type: CatalogueEntryType::CONTENT_MATCHER,
       provider_type: CatalogueEntryProviderType::CORE,
@@ -62,16 +74,15 @@ lazy_static! {
         "content-types".to_string() => "multipart/form-data,multipart/mixed".to_string()
       }
     });
-    // TODO:
-    // entries.push(CatalogueEntry {
-    //   entry_type: CatalogueEntryType::CONTENT_MATCHER,
-    //   provider_type: CatalogueEntryProviderType::CORE,
-    //   plugin: None,
-    //   key: "form-urlencoded".to_string(),
-    //   values: hashmap!{
-    //     "content-types".to_string() => "application/x-www-form-urlencoded".to_string()
-    //   }
-    // });
+    entries.push(CatalogueEntry {
+      entry_type: CatalogueEntryType::CONTENT_MATCHER,
+      provider_type: CatalogueEntryProviderType::CORE,
+      plugin: None,
+      key: "form-urlencoded".to_string(),
+      values: hashmap!{
+        "content-types".to_string() => "application/x-www-form-urlencoded".to_string()
+      }
+    });
     entries.push(CatalogueEntry {
       entry_type: CatalogueEntryType::CONTENT_GENERATOR,
       provider_type: CatalogueEntryProviderType::CORE,
@@ -81,6 +92,15 @@ lazy_static! {
         "content-types".to_string() => "application/.*json,application/json-rpc,application/jsonrequest".to_string()
       }
     });
+    entries.push(CatalogueEntry {
+      entry_type: CatalogueEntryType::CONTENT_GENERATOR,
+      provider_type: CatalogueEntryProviderType::CORE,
+      plugin: None,
+      key: "ndjson".to_string(),
+      values: hashmap!{
+        "content-types".to_string() => "application/x-ndjson;application/ndjson;application/jsonl;application/x-jsonl;application/jsonlines;application/x-jsonlines".to_string()
+      }
+    });
     entries.push(CatalogueEntry {
       entry_type: CatalogueEntryType::CONTENT_GENERATOR,
       provider_type: CatalogueEntryProviderType::CORE,
@@ -99,7 +119,9 @@ lazy_static! {
       "v3-date", "v3-time", "v3-datetime", "v2-min-type", "v2-max-type", "v2-minmax-type",
       "v3-includes", "v3-null", "v4-equals-ignore-order", "v4-min-equals-ignore-order",
       "v4-max-equals-ignore-order", "v4-minmax-equals-ignore-order", "v3-content-type",
-      "v4-array-contains", "v1-equality", "v4-not-empty", "v4-semver"] {
+      "v4-array-contains", "v1-equality", "v4-not-empty", "v4-semver", "v4-number-range",
+      "v4-string-length", "v4-one-of", "v4-not", "v4-any-of", "v4-all-of",
+      "v4-json-schema", "v4-format", "v4-approximately", "v4-jwt"] {
       entries.push(CatalogueEntry {
         entry_type: CatalogueEntryType::MATCHER,
         provider_type: CatalogueEntryProviderType::CORE,
@@ -169,7 +191,7 @@ impl Matches<&str> for &str {
           Err(err) => Err(anyhow!("'{}' is not a valid regular expression - {}", regex, err))
         }
       },
-      MatchingRule::Equality => {
+      MatchingRule::Equality | MatchingRule::EqualsIgnoreOrder => {
         if self == &actual {
           Ok(())
         } else {
@@ -243,6 +265,25 @@ impl Matches<&str> for &str {
           Err(err) => Err(anyhow!("'{}' is not a valid semantic version - {}", actual, err))
         }
       }
+      MatchingRule::NumberRange(min, max) => {
+        match actual.parse::<f64>() {
+          Ok(number) => match_number_range(number, min, max),
+          Err(_) => Err(anyhow!("Expected '{}' to match a number", actual))
+        }
+      }
+      MatchingRule::StringLength(min, max) => match_string_length(actual, min, max),
+      MatchingRule::Approximately(tolerance) => match actual.parse::<f64>() {
+        Ok(number) => match_approximately_from_str(self, number, tolerance),
+        Err(_) => Err(anyhow!("Expected '{}' to be a number", actual))
+      }
+      MatchingRule::Format(format) => format.validate(actual),
+      MatchingRule::Jwt(rules) => match_jwt(rules, self, actual)
+        .map_err(|mismatches| anyhow!(jwt_mismatch_messages(&mismatches).join("; "))),
+      MatchingRule::OneOf(values) => if values.iter().any(|value| json_to_string(value) == actual) {
+        Ok(())
+      } else {
+        Err(anyhow!("Expected '{}' to be one of {}", actual, Value::Array(values.clone())))
+      }
       _ => if !cascaded || matcher.can_cascade() {
         Err(anyhow!("Unable to match '{}' using {:?}", self, matcher))
       } else {
@@ -281,7 +322,7 @@ impl Matches<u64> for &str {
       MatchingRule::MaxType(_) |
       MatchingRule::MinMaxType(_, _) =>
         Err(anyhow!("Expected '{}' (String) to be the same type as {} (Number)", self, actual)),
-      MatchingRule::Equality => Err(anyhow!("Expected '{}' (String) to be equal to {} (Number)", self, actual)),
+      MatchingRule::Equality | MatchingRule::EqualsIgnoreOrder => Err(anyhow!("Expected '{}' (String) to be equal to {} (Number)", self, actual)),
       MatchingRule::Include(substr) => {
         if actual.to_string().contains(substr) {
           Ok(())
@@ -292,6 +333,9 @@ impl Matches<u64> for &str {
       MatchingRule::Number | MatchingRule::Integer => Ok(()),
       MatchingRule::Decimal => Err(anyhow!("Expected {} to match a decimal number", actual)),
       MatchingRule::StatusCode(status) => match_status_code(actual as u16, status),
+      MatchingRule::NumberRange(min, max) => match_number_range(actual as f64, min, max),
+      MatchingRule::Approximately(tolerance) => match_approximately_from_str(self, actual as f64, tolerance),
+      MatchingRule::OneOf(values) => match_one_of(&json!(actual), values),
       _ => if !cascaded || matcher.can_cascade() {
         Err(anyhow!("String: Unable to match {} using {:?}", self, matcher))
       } else {
@@ -321,7 +365,7 @@ impl Matches<u64> for u64 {
       MatchingRule::MinType(_) |
       MatchingRule::MaxType(_) |
       MatchingRule::MinMaxType(_, _) => Ok(()),
-      MatchingRule::Equality => {
+      MatchingRule::Equality | MatchingRule::EqualsIgnoreOrder => {
         if *self == actual {
           Ok(())
         } else {
@@ -338,6 +382,9 @@ impl Matches<u64> for u64 {
       MatchingRule::Number | MatchingRule::Integer => Ok(()),
       MatchingRule::Decimal => Err(anyhow!("Expected {} to match a decimal number", actual)),
       MatchingRule::StatusCode(status) => match_status_code(actual as u16, status),
+      MatchingRule::NumberRange(min, max) => match_number_range(actual as f64, min, max),
+      MatchingRule::Approximately(tolerance) => match_approximately(*self as f64, actual as f64, tolerance),
+      MatchingRule::OneOf(values) => match_one_of(&json!(actual), values),
       _ => if !cascaded || matcher.can_cascade() {
         Err(anyhow!("Unable to match {} using {:?}", self, matcher))
       } else {
@@ -368,7 +415,7 @@ impl Matches<f64> for u64 {
       MatchingRule::MaxType(_) |
       MatchingRule::MinMaxType(_, _) =>
         Err(anyhow!("Expected {} (Integer) to be the same type as {} (Decimal)", self, actual)),
-      MatchingRule::Equality => Err(anyhow!("Expected {} (Integer) to be equal to {} (Decimal)", self, actual)),
+      MatchingRule::Equality | MatchingRule::EqualsIgnoreOrder => Err(anyhow!("Expected {} (Integer) to be equal to {} (Decimal)", self, actual)),
       MatchingRule::Include(substr) => {
         if actual.to_string().contains(substr) {
           Ok(())
@@ -378,6 +425,9 @@ impl Matches<f64> for u64 {
       },
       MatchingRule::Number | MatchingRule::Decimal => Ok(()),
       MatchingRule::Integer => Err(anyhow!("Expected {} to match an integer number", actual)),
+      MatchingRule::NumberRange(min, max) => match_number_range(actual, min, max),
+      MatchingRule::Approximately(tolerance) => match_approximately(*self as f64, actual, tolerance),
+      MatchingRule::OneOf(values) => match_one_of(&json!(actual), values),
       _ => if !cascaded || matcher.can_cascade() {
         Err(anyhow!("Unable to match {} using {:?}", self, matcher))
       } else {
@@ -408,7 +458,7 @@ impl Matches<f64> for f64 {
       MatchingRule::MinType(_) |
       MatchingRule::MaxType(_) |
       MatchingRule::MinMaxType(_, _) => Ok(()),
-      MatchingRule::Equality => {
+      MatchingRule::Equality | MatchingRule::EqualsIgnoreOrder => {
         if *self == actual {
           Ok(())
         } else {
@@ -424,6 +474,9 @@ impl Matches<f64> for f64 {
       },
       MatchingRule::Number | MatchingRule::Decimal => Ok(()),
       MatchingRule::Integer => Err(anyhow!("Expected {} to match an integer number", actual)),
+      MatchingRule::NumberRange(min, max) => match_number_range(actual, min, max),
+      MatchingRule::Approximately(tolerance) => match_approximately(*self, actual, tolerance),
+      MatchingRule::OneOf(values) => match_one_of(&json!(actual), values),
       _ => if !cascaded || matcher.can_cascade() {
         Err(anyhow!("Unable to match {} using {:?}", self, matcher))
       } else {
@@ -454,7 +507,7 @@ impl Matches<u64> for f64 {
       MatchingRule::MaxType(_) |
       MatchingRule::MinMaxType(_, _) =>
         Err(anyhow!("Expected {} (Decimal) to be the same type as {} (Integer)", self, actual)),
-      MatchingRule::Equality => Err(anyhow!("Expected {} (Decimal) to be equal to {} (Integer)", self, actual)),
+      MatchingRule::Equality | MatchingRule::EqualsIgnoreOrder => Err(anyhow!("Expected {} (Decimal) to be equal to {} (Integer)", self, actual)),
       MatchingRule::Include(substr) => {
         if actual.to_string().contains(substr) {
           Ok(())
@@ -464,6 +517,9 @@ impl Matches<u64> for f64 {
       },
       MatchingRule::Number | MatchingRule::Integer => Ok(()),
       MatchingRule::Decimal => Err(anyhow!("Expected {} to match a decimal number", actual)),
+      MatchingRule::NumberRange(min, max) => match_number_range(actual as f64, min, max),
+      MatchingRule::Approximately(tolerance) => match_approximately(*self, actual as f64, tolerance),
+      MatchingRule::OneOf(values) => match_one_of(&json!(actual), values),
       _ => if !cascaded || matcher.can_cascade() {
         Err(anyhow!("Unable to match '{}' using {:?}", self, matcher))
       } else {
@@ -522,7 +578,7 @@ impl Matches<i64> for &str {
       MatchingRule::MaxType(_) |
       MatchingRule::MinMaxType(_, _) =>
         Err(anyhow!("Expected '{}' (String) to be the same type as {} (Number)", self, actual)),
-      MatchingRule::Equality => Err(anyhow!("Expected '{}' (String) to be equal to {} (Number)", self, actual)),
+      MatchingRule::Equality | MatchingRule::EqualsIgnoreOrder => Err(anyhow!("Expected '{}' (String) to be equal to {} (Number)", self, actual)),
       MatchingRule::Include(substr) => {
         if actual.to_string().contains(substr) {
           Ok(())
@@ -532,6 +588,9 @@ impl Matches<i64> for &str {
       },
       MatchingRule::Number | MatchingRule::Integer => Ok(()),
       MatchingRule::Decimal => Err(anyhow!("Expected {} to match a decimal number", actual)),
+      MatchingRule::NumberRange(min, max) => match_number_range(actual as f64, min, max),
+      MatchingRule::Approximately(tolerance) => match_approximately_from_str(self, actual as f64, tolerance),
+      MatchingRule::OneOf(values) => match_one_of(&json!(actual), values),
       _ => if !cascaded || matcher.can_cascade() {
         Err(anyhow!("Unable to match {} using {:?}", self, matcher))
       } else {
@@ -561,7 +620,7 @@ impl Matches<i64> for i64 {
       MatchingRule::MinType(_) |
       MatchingRule::MaxType(_) |
       MatchingRule::MinMaxType(_, _) => Ok(()),
-      MatchingRule::Equality => {
+      MatchingRule::Equality | MatchingRule::EqualsIgnoreOrder => {
         if *self == actual {
           Ok(())
         } else {
@@ -577,6 +636,9 @@ impl Matches<i64> for i64 {
       },
       MatchingRule::Number | MatchingRule::Integer => Ok(()),
       MatchingRule::Decimal => Err(anyhow!("Expected {} to match a decimal number", actual)),
+      MatchingRule::NumberRange(min, max) => match_number_range(actual as f64, min, max),
+      MatchingRule::Approximately(tolerance) => match_approximately(*self as f64, actual as f64, tolerance),
+      MatchingRule::OneOf(values) => match_one_of(&json!(actual), values),
       _ => if !cascaded || matcher.can_cascade() {
         Err(anyhow!("Unable to match {} using {:?}", self, matcher))
       } else {
@@ -624,12 +686,13 @@ impl Matches<bool> for bool {
       MatchingRule::MinType(_) |
       MatchingRule::MaxType(_) |
       MatchingRule::MinMaxType(_, _) => Ok(()),
-      MatchingRule::Equality => if actual == *self {
+      MatchingRule::Equality | MatchingRule::EqualsIgnoreOrder => if actual == *self {
         Ok(())
       } else {
         Err(anyhow!("Expected {} (Boolean) to be equal to {} (Boolean)", self, actual))
       },
       MatchingRule::Boolean => Ok(()),
+      MatchingRule::OneOf(values) => match_one_of(&json!(actual), values),
       _ => if !cascaded || matcher.can_cascade() {
         Err(anyhow!("Boolean: Unable to match {} using {:?}", self, matcher))
       } else {
@@ -664,7 +727,7 @@ impl Matches<&Bytes> for Bytes {
           Err(err) => Err(anyhow!("'{}' is not a valid regular expression - {}", regex, err))
         }
       },
-      MatchingRule::Equality => {
+      MatchingRule::Equality | MatchingRule::EqualsIgnoreOrder => {
         if self == actual {
           Ok(())
         } else {
@@ -711,7 +774,7 @@ pub fn match_values<E, A>(path: &DocPath, matching_rules: &RuleList, expected: E
     Err(vec![format!("No matcher found for path '{}'", path)])
   } else {
     let results = matching_rules.rules.iter().map(|rule| {
-      expected.matches_with(actual.clone(), rule, matching_rules.cascaded)
+      match_rule(&expected, actual.clone(), rule, matching_rules.cascaded)
     }).collect::<Vec<anyhow::Result<()>>>();
     match matching_rules.rule_logic {
       RuleLogic::And => {
@@ -752,11 +815,106 @@ fn match_status_code(status_code: u16, status: &HttpStatus) -> anyhow::Result<()
   }
 }
 
+/// Checks that the number is within the (inclusive) bounds of a number range matcher
+pub(crate) fn match_number_range(actual: f64, min: &Option<f64>, max: &Option<f64>) -> anyhow::Result<()> {
+  match (min, max) {
+    (Some(min), _) if actual < *min => Err(anyhow!("Expected {} to be greater than or equal to {}", actual, min)),
+    (_, Some(max)) if actual > *max => Err(anyhow!("Expected {} to be less than or equal to {}", actual, max)),
+    _ => Ok(())
+  }
+}
+
+/// Checks that the actual number is within the tolerance of the expected number. Differences due to
+/// floating point rounding are allowed for.
+pub(crate) fn match_approximately(expected: f64, actual: f64, tolerance: &NumberTolerance) -> anyhow::Result<()> {
+  let difference = (expected - actual).abs();
+  let rounding = f64::EPSILON * expected.abs().max(actual.abs());
+  if difference <= tolerance.allowed_difference(expected) + rounding {
+    Ok(())
+  } else {
+    Err(anyhow!("Expected {} to be within {} of {}", actual, tolerance, expected))
+  }
+}
+
+fn match_approximately_from_str(expected: &str, actual: f64, tolerance: &NumberTolerance) -> anyhow::Result<()> {
+  match expected.parse::<f64>() {
+    Ok(expected) => match_approximately(expected, actual, tolerance),
+    Err(_) => Err(anyhow!("Unable to match {} approximately as the expected value '{}' is not a number", actual, expected))
+  }
+}
+
+/// Checks that the length of the string (in characters) is within the bounds of a string length matcher
+pub(crate) fn match_string_length(actual: &str, min: &Option<usize>, max: &Option<usize>) -> anyhow::Result<()> {
+  let length = actual.chars().count();
+  match (min, max) {
+    (Some(min), _) if length < *min =>
+      Err(anyhow!("Expected '{}' to have a length of at least {} character(s)", actual, min)),
+    (_, Some(max)) if length > *max =>
+      Err(anyhow!("Expected '{}' to have a length of at most {} character(s)", actual, max)),
+    _ => Ok(())
+  }
+}
+
+/// Checks that the value is one of the values of a one-of matcher. Numbers are compared by their
+/// numeric value, so 100 will match 100.0
+pub(crate) fn match_one_of(actual: &Value, values: &[Value]) -> anyhow::Result<()> {
+  let matches = values.iter().any(|value| match (value, actual) {
+    (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+    _ => value == actual
+  });
+  if matches {
+    Ok(())
+  } else {
+    Err(anyhow!("Expected '{}' to be one of {}", json_to_string(actual), Value::Array(values.to_vec())))
+  }
+}
+
+/// Matches the actual value against the matching rule. Combinator rules (not, anyOf or allOf) are
+/// applied here by matching each of the nested rules, so the `Matches` implementations only need to
+/// handle the other rules. Mismatches will describe which of the nested rules failed.
+pub(crate) fn match_rule<E: Matches<A> + ?Sized, A: Clone>(
+  expected: &E,
+  actual: A,
+  matcher: &MatchingRule,
+  cascaded: bool
+) -> anyhow::Result<()> {
+  match matcher {
+    MatchingRule::Not(rule) => match match_rule(expected, actual, rule, cascaded) {
+      Ok(_) => Err(anyhow!("Expected value to not match {}", rule.to_json())),
+      Err(_) => Ok(())
+    }
+    MatchingRule::AnyOf(rules) => {
+      let mut failures = vec![];
+      for (index, rule) in rules.iter().enumerate() {
+        match match_rule(expected, actual.clone(), rule, cascaded) {
+          Ok(_) => return Ok(()),
+          Err(err) => failures.push(format!("rule {} ({}) - {}", index + 1, rule.name(), err))
+        }
+      }
+      Err(anyhow!("Expected value to match any of the rules, but none did: {}", failures.join("; ")))
+    }
+    MatchingRule::AllOf(rules) => {
+      let failures = rules.iter().enumerate()
+        .filter_map(|(index, rule)| match_rule(expected, actual.clone(), rule, cascaded).err()
+          .map(|err| format!("rule {} ({}) - {}", index + 1, rule.name(), err)))
+        .collect::<Vec<String>>();
+      if failures.is_empty() {
+        Ok(())
+      } else {
+        Err(anyhow!("Expected value to match all of the rules, but {} failed: {}",
+          if failures.len() == 1 { "one" } else { "some" }, failures.join("; ")))
+      }
+    }
+    _ => expected.matches_with(actual, matcher, cascaded)
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use expectest::expect;
   use expectest::prelude::*;
   use pact_models::{matchingrules, matchingrules::RuleList, matchingrules_list};
+  use pact_models::matchingrules::formats::StringFormat;
   use serde_json::json;
 
   use super::*;
@@ -1118,4 +1276,149 @@ mod tests {
     expect!(json!("1.0.0").matches_with(&json!("1.0.0"), &matcher, false)).to(be_ok());
     expect!(json!("1.0.0").matches_with(&json!("1"), &matcher, false)).to(be_err());
   }
+
+  #[test]
+  fn number_range_matcher_test() {
+    let matcher = MatchingRule::NumberRange(Some(1.0), Some(100.0));
+    expect!("50".to_string().matches_with("1", &matcher, false)).to(be_ok());
+    expect!("50".to_string().matches_with("100", &matcher, false)).to(be_ok());
+    expect!("50".to_string().matches_with("100.5", &matcher, false)).to(be_err());
+    expect!("50".to_string().matches_with("abc", &matcher, false)).to(be_err());
+    expect!(50.matches_with(0, &matcher, false)).to(be_err());
+    expect!(50_u64.matches_with(99, &matcher, false)).to(be_ok());
+    expect!(50.5.matches_with(99.9, &matcher, false)).to(be_ok());
+    expect!(50.5.matches_with(0.5, &matcher, false)).to(be_err());
+    expect!(json!(50).matches_with(&json!(75.5), &matcher, false)).to(be_ok());
+    expect!(json!(50).matches_with(&json!(101), &matcher, false)).to(be_err());
+    expect!(json!(50).matches_with(&json!("50"), &matcher, false)).to(be_err());
+
+    let matcher = MatchingRule::NumberRange(Some(0.0), None);
+    expect!(json!(50).matches_with(&json!(1000000), &matcher, false)).to(be_ok());
+    expect!(json!(50).matches_with(&json!(-1), &matcher, false).unwrap_err().to_string()).to(
+      be_equal_to("Expected -1 to be greater than or equal to 0"));
+  }
+
+  #[test]
+  fn string_length_matcher_test() {
+    let matcher = MatchingRule::StringLength(Some(3), Some(3));
+    expect!("USD".to_string().matches_with("AUD", &matcher, false)).to(be_ok());
+    expect!("USD".to_string().matches_with("AU", &matcher, false)).to(be_err());
+    expect!("USD".to_string().matches_with("€€€", &matcher, false)).to(be_ok());
+    expect!(json!("USD").matches_with(&json!("EUR"), &matcher, false)).to(be_ok());
+    expect!(json!("USD").matches_with(&json!("EURO"), &matcher, false).unwrap_err().to_string()).to(
+      be_equal_to("Expected 'EURO' to have a length of at most 3 character(s)"));
+    expect!(json!("USD").matches_with(&json!(100), &matcher, false)).to(be_err());
+  }
+
+  #[test]
+  fn format_matcher_test() {
+    let matcher = MatchingRule::Format(StringFormat::Email);
+    expect!("a@b.com".to_string().matches_with("fred@example.com", &matcher, false)).to(be_ok());
+    expect!("a@b.com".to_string().matches_with("fred", &matcher, false).unwrap_err().to_string()).to(
+      be_equal_to("Expected 'fred' to be an email address"));
+
+    let matcher = MatchingRule::Format(StringFormat::Ipv6);
+    expect!(json!("::1").matches_with(&json!("2001:db8::8a2e:370:7334"), &matcher, false)).to(be_ok());
+    expect!(json!("::1").matches_with(&json!("127.0.0.1"), &matcher, false)).to(be_err());
+    expect!(json!("::1").matches_with(&json!(100), &matcher, false).unwrap_err().to_string()).to(
+      be_equal_to("Expected '100' to be a string"));
+
+    let matcher = MatchingRule::Format(StringFormat::Duration);
+    expect!(json!("P1D").matches_with(&json!("PT1H30M"), &matcher, false)).to(be_ok());
+    expect!(json!("P1D").matches_with(&json!("1 hour"), &matcher, false)).to(be_err());
+  }
+
+  #[test]
+  fn approximately_matcher_test() {
+    let matcher = MatchingRule::Approximately(NumberTolerance::Absolute(0.01));
+    expect!(1.1_f64.matches_with(1.11_f64, &matcher, false)).to(be_ok());
+    expect!(1.1_f64.matches_with(1.12_f64, &matcher, false).unwrap_err().to_string()).to(
+      be_equal_to("Expected 1.12 to be within 0.01 of 1.1"));
+    expect!(100_u64.matches_with(100_u64, &matcher, false)).to(be_ok());
+    expect!(100_u64.matches_with(100.005_f64, &matcher, false)).to(be_ok());
+    expect!(100_i64.matches_with(101_i64, &matcher, false)).to(be_err());
+    expect!("12.345".matches_with("12.35", &matcher, false)).to(be_ok());
+    expect!("12.345".matches_with("12.4", &matcher, false)).to(be_err());
+    expect!("12.345".matches_with("twelve", &matcher, false).unwrap_err().to_string()).to(
+      be_equal_to("Expected 'twelve' to be a number"));
+    expect!("twelve".matches_with("12.4", &matcher, false).unwrap_err().to_string()).to(
+      be_equal_to("Unable to match 12.4 approximately as the expected value 'twelve' is not a number"));
+
+    let matcher = MatchingRule::Approximately(NumberTolerance::Relative(0.05));
+    expect!(json!(200.0).matches_with(&json!(209.5), &matcher, false)).to(be_ok());
+    expect!(json!(200).matches_with(&json!(190), &matcher, false)).to(be_ok());
+    expect!(json!(200).matches_with(&json!(189.9), &matcher, false).unwrap_err().to_string()).to(
+      be_equal_to("Expected 189.9 to be within a relative tolerance of 0.05 of 200"));
+    expect!(json!(200).matches_with(&json!("200"), &matcher, false).unwrap_err().to_string()).to(
+      be_equal_to("Expected '200' to be a number"));
+    expect!(json!(-10.0).matches_with(&json!(-10.4), &matcher, false)).to(be_ok());
+  }
+
+  #[test]
+  fn one_of_matcher_test() {
+    let matcher = MatchingRule::OneOf(vec![json!("AUD"), json!("EUR"), json!("USD")]);
+    expect!("USD".to_string().matches_with("AUD", &matcher, false)).to(be_ok());
+    expect!("USD".to_string().matches_with("GBP", &matcher, false)).to(be_err());
+    expect!(json!("USD").matches_with(&json!("EUR"), &matcher, false)).to(be_ok());
+    expect!(json!("USD").matches_with(&json!("GBP"), &matcher, false).unwrap_err().to_string()).to(
+      be_equal_to("Expected 'GBP' to be one of [\"AUD\",\"EUR\",\"USD\"]"));
+
+    let matcher = MatchingRule::OneOf(vec![json!(200), json!(201), json!(204)]);
+    expect!("200".to_string().matches_with("201", &matcher, false)).to(be_ok());
+    expect!(200_u64.matches_with(204, &matcher, false)).to(be_ok());
+    expect!(200_u64.matches_with(404, &matcher, false)).to(be_err());
+    expect!(200.0.matches_with(201.0, &matcher, false)).to(be_ok());
+    expect!(json!(200).matches_with(&json!(201), &matcher, false)).to(be_ok());
+    expect!(json!(200).matches_with(&json!("201"), &matcher, false)).to(be_err());
+
+    let matcher = MatchingRule::OneOf(vec![json!(true)]);
+    expect!(true.matches_with(true, &matcher, false)).to(be_ok());
+    expect!(true.matches_with(false, &matcher, false)).to(be_err());
+  }
+
+  #[test]
+  fn not_matcher_test() {
+    let matcher = MatchingRule::Not(Box::new(MatchingRule::Regex("^\\s*$".to_string())));
+    expect!(match_rule(&"Fred".to_string(), "Mary", &matcher, false)).to(be_ok());
+    expect!(match_rule(&"Fred".to_string(), "  ", &matcher, false).unwrap_err().to_string()).to(
+      be_equal_to("Expected value to not match {\"match\":\"regex\",\"regex\":\"^\\\\s*$\"}"));
+    expect!(match_rule(&json!("Fred"), &json!(""), &matcher, false)).to(be_err());
+    expect!(match_rule(&100_u64, 200, &MatchingRule::Not(Box::new(MatchingRule::Decimal)), false)).to(be_ok());
+    expect!(match_rule(&100.5, 200.5, &MatchingRule::Not(Box::new(MatchingRule::Decimal)), false)).to(be_err());
+  }
+
+  #[test]
+  fn any_of_matcher_test() {
+    let matcher = MatchingRule::AnyOf(vec![
+      MatchingRule::Null,
+      MatchingRule::Date("yyyy-MM-dd".to_string())
+    ]);
+    expect!(match_rule(&json!("2000-01-01"), &Value::Null, &matcher, false)).to(be_ok());
+    expect!(match_rule(&json!("2000-01-01"), &json!("2021-10-18"), &matcher, false)).to(be_ok());
+    expect!(match_rule(&json!("2000-01-01"), &json!("18/10/2021"), &matcher, false).unwrap_err().to_string()).to(
+      be_equal_to("Expected value to match any of the rules, but none did: \
+        rule 1 (null) - Expected '18/10/2021' to be a null value; \
+        rule 2 (date) - Expected '\"18/10/2021\"' to match a date format of 'yyyy-MM-dd': \
+        Error(Nom(\"/10/2021\", Tag))"));
+    expect!(match_rule(&"100".to_string(), "100", &MatchingRule::AnyOf(vec![MatchingRule::Integer]), false)).to(be_ok());
+  }
+
+  #[test]
+  fn all_of_matcher_test() {
+    let matcher = MatchingRule::AllOf(vec![
+      MatchingRule::StringLength(Some(1), Some(10)),
+      MatchingRule::Not(Box::new(MatchingRule::Include("admin".to_string())))
+    ]);
+    expect!(match_rule(&"Fred".to_string(), "Mary", &matcher, false)).to(be_ok());
+    expect!(match_rule(&"Fred".to_string(), "superadmin", &matcher, false).unwrap_err().to_string()).to(
+      be_equal_to("Expected value to match all of the rules, but one failed: \
+        rule 2 (not) - Expected value to not match {\"match\":\"include\",\"value\":\"admin\"}"));
+    expect!(match_rule(&"Fred".to_string(), "administrator", &matcher, false).unwrap_err().to_string()).to(
+      be_equal_to("Expected value to match all of the rules, but some failed: \
+        rule 1 (string-length) - Expected 'administrator' to have a length of at most 10 character(s); \
+        rule 2 (not) - Expected value to not match {\"match\":\"include\",\"value\":\"admin\"}"));
+    expect!(match_rule(&vec![1, 2].as_slice(), &[1, 2, 3][..], &MatchingRule::AllOf(vec![
+      MatchingRule::MinType(1), MatchingRule::MaxType(2)
+    ]), false)).to(be_err());
+  }
 }
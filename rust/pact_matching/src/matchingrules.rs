@@ -62,7 +62,7 @@ impl <T: Debug + Display + PartialEq + Clone> Matches<&[T]> for &[T] {
           Ok(())
         }
       }
-      MatchingRule::Equality => {
+      MatchingRule::Equality | MatchingRule::EqualsIgnoreOrder => {
         if *self == actual {
           Ok(())
         } else {
@@ -80,6 +80,7 @@ impl <T: Debug + Display + PartialEq + Clone> Matches<&[T]> for &[T] {
       MatchingRule::EachKey(_) => Ok(()),
       MatchingRule::EachValue(_) => Ok(()),
       MatchingRule::Values => Ok(()),
+      MatchingRule::Approximately(_) => Ok(()),
       _ => Err(anyhow!("Unable to match {} using {:?}", self.for_mismatch(), matcher))
     };
     debug!("Comparing '{:?}' to '{:?}' using {:?} -> {:?}", self, actual, matcher, result);
@@ -140,7 +141,7 @@ impl Matches<&[u8]> for Vec<u8> {
           Ok(())
         }
       }
-      MatchingRule::Equality => {
+      MatchingRule::Equality | MatchingRule::EqualsIgnoreOrder => {
         if *self == actual {
           Ok(())
         } else {
@@ -258,13 +259,16 @@ pub fn compare_lists_with_matchingrule<T: Display + Debug + PartialEq + Clone +
 ) -> Result<(), Vec<Mismatch>> {
   let mut result = vec![];
 
-  if !expected.is_empty() {
+  if !expected.is_empty() || *rule == MatchingRule::EqualsIgnoreOrder {
     match rule {
-      // TODO: need to implement the ignore order matchers (See Pact-JVM core/matchers/src/main/kotlin/au/com/dius/pact/core/matchers/Matchers.kt:133)
-      // is EqualsIgnoreOrderMatcher,
+      // TODO: need to implement the min/max ignore order matchers (See Pact-JVM core/matchers/src/main/kotlin/au/com/dius/pact/core/matchers/Matchers.kt:133)
       //         is MinEqualsIgnoreOrderMatcher,
       //         is MaxEqualsIgnoreOrderMatcher,
       //         is MinMaxEqualsIgnoreOrderMatcher -> {
+      MatchingRule::EqualsIgnoreOrder if !cascaded => {
+        debug!("Matching {} with EqualsIgnoreOrder", path);
+        result.extend(match_list_ignoring_order(path, expected, actual, context, callback));
+      }
       MatchingRule::ArrayContains(variants) => {
         debug!("Matching {} with ArrayContains", path);
         let variants = if variants.is_empty() {
@@ -392,6 +396,80 @@ fn match_list_contents<T: Display + Debug + PartialEq + Clone + Sized>(
   result
 }
 
+/// Matches the items of the lists ignoring their order. Each expected item must be matched by a different actual item
+/// (using the matching rules for the expected item), and every actual item must be matched by an expected item.
+fn match_list_ignoring_order<T: Display + Debug + PartialEq + Clone + Sized>(
+  path: &DocPath,
+  expected: &[T],
+  actual: &[T],
+  context: &dyn MatchingContext,
+  callback: &mut dyn FnMut(&DocPath, &T, &T, &dyn MatchingContext) -> Result<(), Vec<Mismatch>>
+) -> Vec<Mismatch> {
+  let mut candidates = vec![];
+  for (index, expected_value) in expected.iter().enumerate() {
+    let p = path.join(index.to_string());
+    let mut matches = vec![];
+    for (actual_index, actual_value) in actual.iter().enumerate() {
+      debug!("Comparing list item {} with value '{:?}' to '{:?}'", actual_index, actual_value, expected_value);
+      if callback(&p, expected_value, actual_value, context).is_ok() {
+        matches.push(actual_index);
+      }
+    }
+    candidates.push(matches);
+  }
+
+  let mut result = vec![];
+  let mut assigned = vec![None; actual.len()];
+  for (index, expected_value) in expected.iter().enumerate() {
+    let mut visited = vec![false; actual.len()];
+    if !assign_list_item(index, &candidates, &mut assigned, &mut visited) {
+      result.push(Mismatch::BodyMismatch {
+        path: path.to_string(),
+        expected: Some(expected_value.to_string().into()),
+        actual: Some(actual.for_mismatch().into()),
+        mismatch: format!("Expected item at index {} ({}) was not found in the actual list", index, expected_value)
+      });
+    }
+  }
+
+  for (index, actual_value) in actual.iter().enumerate() {
+    if assigned[index].is_none() {
+      result.push(Mismatch::BodyMismatch {
+        path: path.to_string(),
+        expected: Some(expected.for_mismatch().into()),
+        actual: Some(actual_value.to_string().into()),
+        mismatch: format!("Actual item at index {} ({}) was not expected", index, actual_value)
+      });
+    }
+  }
+
+  result
+}
+
+/// Tries to assign the expected item to one of the actual items it matches, re-assigning previously assigned expected
+/// items to other actual items where required (augmenting path search for a bipartite matching).
+fn assign_list_item(
+  index: usize,
+  candidates: &[Vec<usize>],
+  assigned: &mut [Option<usize>],
+  visited: &mut [bool]
+) -> bool {
+  for &actual_index in &candidates[index] {
+    if !visited[actual_index] {
+      visited[actual_index] = true;
+      let available = match assigned[actual_index] {
+        Some(other) => assign_list_item(other, candidates, assigned, visited),
+        None => true
+      };
+      if available {
+        assigned[actual_index] = Some(index);
+        return true;
+      }
+    }
+  }
+  false
+}
+
 #[cfg(test)]
 mod tests {
   use std::cell::RefCell;
@@ -0,0 +1,181 @@
+//! Module for functions dealing with matching newline-delimited JSON (NDJSON or JSON Lines) bodies
+
+use anyhow::anyhow;
+use itertools::Itertools;
+use pact_models::http_parts::HttpPart;
+use pact_models::path_exp::DocPath;
+use serde_json::Value;
+
+use crate::{MatchingContext, Mismatch};
+use crate::json::compare_json;
+
+/// Parses a newline-delimited JSON body into its records. Blank lines are ignored.
+pub(crate) fn parse_ndjson(body: &[u8]) -> anyhow::Result<Vec<Value>> {
+  let body = std::str::from_utf8(body)
+    .map_err(|err| anyhow!("body is not valid UTF-8 - {}", err))?;
+  body.lines().enumerate()
+    .filter(|(_, line)| !line.trim().is_empty())
+    .map(|(index, line)| serde_json::from_str(line)
+      .map_err(|err| anyhow!("line {} is not valid JSON - {}", index + 1, err)))
+    .collect()
+}
+
+/// Formats the records as a newline-delimited JSON body, with one record per line
+pub(crate) fn to_ndjson(records: &[Value]) -> String {
+  records.iter().map(|record| format!("{}\n", record)).join("")
+}
+
+/// Matches the bodies of the HTTP parts as newline-delimited JSON (NDJSON or JSON Lines) bodies.
+/// Each line of the body is parsed as a JSON record, and the records are then compared as if
+/// they were a JSON array. This means matching rules can be applied to a record by index (i.e.
+/// `$[0].id`) or to all the records (i.e. `$[*].id`), and the number of records can be
+/// constrained with min/max type matchers on the root (`$`).
+pub fn match_ndjson(
+  expected: &dyn HttpPart,
+  actual: &dyn HttpPart,
+  context: &dyn MatchingContext
+) -> Result<(), Vec<Mismatch>> {
+  let expected_records = parse_ndjson(&expected.body().value().unwrap_or_default());
+  let actual_records = parse_ndjson(&actual.body().value().unwrap_or_default());
+
+  match (expected_records, actual_records) {
+    (Ok(expected_records), Ok(actual_records)) => compare_json(&DocPath::root(),
+      &Value::Array(expected_records), &Value::Array(actual_records), context),
+    (expected_records, actual_records) => {
+      let mut mismatches = vec![];
+      if let Err(err) = expected_records {
+        mismatches.push(Mismatch::BodyMismatch {
+          path: "$".to_string(),
+          expected: expected.body().value(),
+          actual: actual.body().value(),
+          mismatch: format!("Failed to parse the expected body: '{}'", err)
+        });
+      }
+      if let Err(err) = actual_records {
+        mismatches.push(Mismatch::BodyMismatch {
+          path: "$".to_string(),
+          expected: expected.body().value(),
+          actual: actual.body().value(),
+          mismatch: format!("Failed to parse the actual body: '{}'", err)
+        });
+      }
+      Err(mismatches)
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use maplit::hashmap;
+  use pact_models::bodies::OptionalBody;
+  use pact_models::matchingrules;
+  use pact_models::matchingrules::MatchingRule;
+  use pact_models::request::Request;
+  use serde_json::json;
+
+  use crate::{CoreMatchingContext, DiffConfig, Mismatch};
+
+  use super::*;
+
+  fn request(body: &str) -> Request {
+    Request {
+      body: OptionalBody::Present(body.as_bytes().to_vec().into(), None, None),
+      .. Request::default()
+    }
+  }
+
+  fn mismatch_paths_and_messages(mismatches: Vec<Mismatch>) -> Vec<(String, String)> {
+    mismatches.iter().map(|mismatch| match mismatch {
+      Mismatch::BodyMismatch { path, mismatch, .. } => (path.clone(), mismatch.clone()),
+      _ => (String::default(), mismatch.description())
+    }).sorted().collect()
+  }
+
+  #[test]
+  fn parse_ndjson_test() {
+    expect!(parse_ndjson(b"").unwrap()).to(be_equal_to(Vec::<Value>::new()));
+    expect!(parse_ndjson(b"{\"a\":1}\r\n\n  \n[1,2]\n").unwrap()).to(be_equal_to(vec![
+      json!({"a": 1}), json!([1, 2])
+    ]));
+    expect!(parse_ndjson(b"{\"a\":1}\n{\"a\":\n").unwrap_err().to_string()).to(
+      be_equal_to("line 2 is not valid JSON - EOF while parsing a value at line 1 column 5"));
+  }
+
+  #[test]
+  fn to_ndjson_test() {
+    expect!(to_ndjson(&[])).to(be_equal_to(""));
+    expect!(to_ndjson(&[json!({"a": 1}), json!("b")])).to(be_equal_to("{\"a\":1}\n\"b\"\n"));
+  }
+
+  #[test]
+  fn match_ndjson_ignores_the_order_of_fields_in_each_record() {
+    let expected = request("{\"id\":1,\"name\":\"one\"}\n{\"id\":2,\"name\":\"two\"}\n");
+    let actual = request("{\"name\":\"one\",\"id\":1}\n{\"name\":\"two\",\"id\":2}");
+    let context = CoreMatchingContext::with_config(DiffConfig::AllowUnexpectedKeys);
+    expect!(match_ndjson(&expected, &actual, &context)).to(be_ok());
+
+    let actual = request("{\"name\":\"one\",\"id\":1}\n{\"name\":\"three\",\"id\":2}");
+    expect!(mismatch_paths_and_messages(match_ndjson(&expected, &actual, &context).unwrap_err())).to(be_equal_to(vec![
+      ("$[1].name".to_string(), "Expected 'two' to be equal to 'three'".to_string())
+    ]));
+  }
+
+  #[test]
+  fn match_ndjson_applies_the_matching_rules_to_the_records() {
+    let expected = request("{\"id\":1,\"status\":\"OK\"}\n{\"id\":2,\"status\":\"OK\"}\n");
+    let context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys,
+      &matchingrules! {
+        "body" => {
+          "$[*].id" => [ MatchingRule::Integer ],
+          "$[1].status" => [ MatchingRule::Regex("OK|FAILED".to_string()) ]
+        }
+      }.rules_for_category("body").unwrap_or_default(), &hashmap!{});
+
+    let actual = request("{\"id\":100,\"status\":\"OK\"}\n{\"id\":200,\"status\":\"FAILED\"}\n");
+    expect!(match_ndjson(&expected, &actual, &context)).to(be_ok());
+
+    let actual = request("{\"id\":\"100\",\"status\":\"FAILED\"}\n{\"id\":200,\"status\":\"PENDING\"}\n");
+    expect!(mismatch_paths_and_messages(match_ndjson(&expected, &actual, &context).unwrap_err())).to(be_equal_to(vec![
+      ("$[0].id".to_string(), "Expected '100' to be an integer value".to_string()),
+      ("$[0].status".to_string(), "Expected 'OK' to be equal to 'FAILED'".to_string()),
+      ("$[1].status".to_string(), "Expected 'PENDING' to match 'OK|FAILED'".to_string())
+    ]));
+  }
+
+  #[test]
+  fn match_ndjson_with_min_and_max_record_counts() {
+    let expected = request("{\"id\":1}\n");
+    let context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys,
+      &matchingrules! {
+        "body" => {
+          "$" => [ MatchingRule::MinMaxType(1, 3) ]
+        }
+      }.rules_for_category("body").unwrap_or_default(), &hashmap!{});
+
+    let actual = request("{\"id\":10}\n{\"id\":20}\n{\"id\":30}\n");
+    expect!(match_ndjson(&expected, &actual, &context)).to(be_ok());
+
+    let actual = request("");
+    expect!(match_ndjson(&expected, &actual, &context)).to(be_err());
+
+    let actual = request("{\"id\":10}\n{\"id\":20}\n{\"id\":30}\n{\"id\":40}\n");
+    expect!(match_ndjson(&expected, &actual, &context)).to(be_err());
+
+    let context = CoreMatchingContext::with_config(DiffConfig::AllowUnexpectedKeys);
+    expect!(mismatch_paths_and_messages(match_ndjson(&expected, &actual, &context).unwrap_err())).to(be_equal_to(vec![
+      ("$".to_string(), "Expected a List with 1 elements but received 4 elements".to_string()),
+      ("$[0].id".to_string(), "Expected '1' to be equal to '10'".to_string())
+    ]));
+  }
+
+  #[test]
+  fn match_ndjson_with_an_invalid_record() {
+    let expected = request("{\"id\":1}\n");
+    let actual = request("{\"id\":1}\nnot json\n");
+    let context = CoreMatchingContext::with_config(DiffConfig::AllowUnexpectedKeys);
+    expect!(mismatch_paths_and_messages(match_ndjson(&expected, &actual, &context).unwrap_err())).to(be_equal_to(vec![
+      ("$".to_string(), "Failed to parse the actual body: 'line 2 is not valid JSON - expected ident at line 1 column 2'".to_string())
+    ]));
+  }
+}
@@ -5,9 +5,12 @@ use std::collections::HashMap;
 use maplit::hashmap;
 use pact_models::matchingrules::MatchingRule;
 use pact_models::path_exp::DocPath;
+use pact_models::query_strings::structured_query;
 use tracing::debug;
 
 use crate::{matchers, Matches, MatchingContext, merge_result, Mismatch};
+use crate::explain::explain_path;
+use crate::json::compare_json;
 
 /// Match the query parameters as Maps
 pub(crate) fn match_query_maps(
@@ -45,6 +48,57 @@ pub(crate) fn match_query_maps(
   result
 }
 
+/// Match the query parameters as nested values (i.e. `filter[status]=open`), using the JSON
+/// body matching with paths like `$.filter.status`. The context needs to have the query matching rules
+/// in the body category.
+pub(crate) fn match_structured_query_maps(
+  expected: HashMap<String, Vec<String>>,
+  actual: HashMap<String, Vec<String>>,
+  context: &dyn MatchingContext
+) -> HashMap<String, Vec<Mismatch>> {
+  let expected_tree = structured_query(&expected);
+  let actual_tree = structured_query(&actual);
+  let expected_map = expected_tree.as_object().cloned().unwrap_or_default();
+  let actual_map = actual_tree.as_object().cloned().unwrap_or_default();
+
+  let mut result: HashMap<String, Vec<Mismatch>> = hashmap!{};
+  for (key, value) in &expected_map {
+    match actual_map.get(key) {
+      Some(actual_value) => {
+        let path = DocPath::root().join(key.as_str());
+        let mismatches = compare_json(&path, value, actual_value, context)
+          .err().unwrap_or_default();
+        result.entry(key.clone()).or_default().extend(mismatches.iter().map(|mismatch| match mismatch {
+          Mismatch::BodyMismatch { path, expected, actual, mismatch } => Mismatch::QueryMismatch {
+            parameter: key.clone(),
+            expected: expected.as_ref().map(|v| String::from_utf8_lossy(v).to_string()).unwrap_or_default(),
+            actual: actual.as_ref().map(|v| String::from_utf8_lossy(v).to_string()).unwrap_or_default(),
+            mismatch: format!("{} -> {}", path, mismatch)
+          },
+          _ => mismatch.clone()
+        }));
+      },
+      None => result.entry(key.clone()).or_default().push(Mismatch::QueryMismatch {
+        parameter: key.clone(),
+        expected: value.to_string(),
+        actual: "".to_string(),
+        mismatch: format!("Expected query parameter '{}' but was missing", key)
+      })
+    }
+  }
+  for (key, value) in &actual_map {
+    if !expected_map.contains_key(key) {
+      result.entry(key.clone()).or_default().push(Mismatch::QueryMismatch {
+        parameter: key.clone(),
+        expected: "".to_string(),
+        actual: value.to_string(),
+        mismatch: format!("Unexpected query parameter '{}' received", key)
+      });
+    }
+  }
+  result
+}
+
 fn match_query_values(
   key: &str,
   expected: &[String],
@@ -52,10 +106,20 @@ fn match_query_values(
   context: &dyn MatchingContext
 ) -> Result<(), Vec<Mismatch>> {
   let path = DocPath::root().join(key);
-  if context.matcher_is_defined(&path) {
+  explain_path(context, &path, || match_query_parameter(&path, key, expected, actual, context))
+}
+
+fn match_query_parameter(
+  path: &DocPath,
+  key: &str,
+  expected: &[String],
+  actual: &[String],
+  context: &dyn MatchingContext
+) -> Result<(), Vec<Mismatch>> {
+  if context.matcher_is_defined(path) {
     debug!("match_query_values: Matcher defined for query parameter '{}", key);
     merge_result(
-      matchers::match_values(&path, &context.select_best_matcher(&path), expected, actual)
+      matchers::match_values(path, &context.select_best_matcher(path), expected, actual)
         .map_err(|err| err.iter().map(|msg| {
           Mismatch::QueryMismatch {
             parameter: key.to_string(),
@@ -64,7 +128,7 @@ fn match_query_values(
             mismatch: msg.clone()
           }
         }).collect()),
-      compare_query_parameter_values(&path, expected, actual, context)
+      compare_query_parameter_values(path, expected, actual, context)
     )
   } else {
     if expected.is_empty() && !actual.is_empty() {
@@ -87,7 +151,7 @@ fn match_query_values(
       } else {
         Ok(())
       };
-      merge_result(compare_query_parameter_values(&path, expected, actual, context), mismatch)
+      merge_result(compare_query_parameter_values(path, expected, actual, context), mismatch)
     }
   }
 }
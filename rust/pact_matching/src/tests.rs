@@ -4,9 +4,13 @@ use expectest::prelude::*;
 
 use pact_models::{matchingrules, matchingrules_list};
 use pact_models::bodies::OptionalBody;
-use pact_models::content_types::TEXT;
+use pact_models::content_types::{ContentType, TEXT};
 use pact_models::HttpStatus;
+use pact_models::matchingrules::formats::StringFormat;
+use pact_models::matchingrules::NumberTolerance;
 use pact_models::request::Request;
+use pact_models::v4::pact::V4Pact;
+use pact_models::v4::synch_http::SynchronousHttp;
 
 use super::*;
 
@@ -288,6 +292,103 @@ fn match_query_returns_a_mismatch_if_the_values_do_not_match_by_a_matcher() {
   });
 }
 
+#[test]
+fn match_query_with_an_approximately_matcher() {
+  let context = CoreMatchingContext::new(
+    DiffConfig::AllowUnexpectedKeys,
+    &matchingrules! {
+      "query" => {
+        "amount" => [ MatchingRule::Approximately(NumberTolerance::Absolute(0.01)) ]
+      }
+    }.rules_for_category("query").unwrap_or_default(), &hashmap!{}
+  );
+  let expected = Some(hashmap!{ "amount".to_string() => vec!["10.50".to_string()] });
+  let actual = Some(hashmap!{ "amount".to_string() => vec!["10.495".to_string()] });
+  let result = match_query(expected.clone(), actual, &context);
+  expect!(result.values().flatten()).to(be_empty());
+
+  let actual = Some(hashmap!{ "amount".to_string() => vec!["10.6".to_string()] });
+  let result = match_query(expected, actual, &context);
+  expect!(result.get("amount").unwrap().iter().map(|m| m.description()).collect::<Vec<_>>()).to(be_equal_to(vec![
+    "Expected 10.6 to be within 0.01 of 10.5".to_string()
+  ]));
+}
+
+#[test]
+fn match_headers_with_a_jwt_matcher() {
+  let context = CoreMatchingContext::new(
+    DiffConfig::AllowUnexpectedKeys,
+    &matchingrules! {
+      "header" => {
+        "Authorization" => [ MatchingRule::Jwt(matchingrules_list! {
+          "body";
+          "$.claims.sub" => [ MatchingRule::Format(StringFormat::Uuid(None)) ],
+          "$.claims.exp" => [ MatchingRule::Integer ]
+        }) ]
+      }
+    }.rules_for_category("header").unwrap_or_default(), &hashmap!{}
+  );
+  let token = |claims: serde_json::Value| format!("Bearer {}",
+    pact_models::jwt::encode_jwt(&json!({ "claims": claims }), None).unwrap());
+  let expected = Some(hashmap!{
+    "Authorization".to_string() => vec![token(json!({ "sub": "c1e6f2b8-0d2e-4a57-a2a8-6a9a0b9d6c1e", "exp": 1700000000 }))]
+  });
+
+  let actual = Some(hashmap!{
+    "Authorization".to_string() => vec![token(json!({ "sub": "7d0c1c8e-6f5b-4b1a-9a43-5b4f6f0f2d11", "exp": 1800000000 }))]
+  });
+  let result = match_headers(expected.clone(), actual, &context);
+  expect!(result.values().flatten()).to(be_empty());
+
+  let actual = Some(hashmap!{
+    "Authorization".to_string() => vec![token(json!({ "sub": "1234", "exp": 1800000000 }))]
+  });
+  let result = match_headers(expected, actual, &context);
+  expect!(result.get("Authorization").unwrap().iter().map(|m| m.description()).collect::<Vec<_>>()).to(be_equal_to(vec![
+    "Mismatch with header 'Authorization': header.Authorization.jwt.claims.sub -> Expected '1234' to be a UUID".to_string()
+  ]));
+}
+
+#[test]
+fn match_structured_query_compares_the_nested_parameters() {
+  let context = CoreMatchingContext::new(
+    DiffConfig::NoUnexpectedKeys,
+    &matchingrules! {
+      "body" => {
+        "$.filter.status" => [ MatchingRule::Regex("open|closed".to_string()) ],
+        "$.ids" => [ MatchingRule::MinType(1) ]
+      }
+    }.rules_for_category("body").unwrap_or_default(), &hashmap!{}
+  );
+  let expected = Some(hashmap!{
+    "filter[status]".to_string() => vec!["open".to_string()],
+    "filter[owner]".to_string() => vec!["me".to_string()],
+    "ids[]".to_string() => vec!["1".to_string()]
+  });
+  let actual = Some(hashmap!{
+    "filter[owner]".to_string() => vec!["me".to_string()],
+    "filter[status]".to_string() => vec!["closed".to_string()],
+    "ids[]".to_string() => vec!["2".to_string(), "3".to_string()]
+  });
+  let result = match_structured_query(expected.clone(), actual, &context);
+  expect!(result.values().flatten()).to(be_empty());
+
+  let actual = Some(hashmap!{
+    "filter[owner]".to_string() => vec!["you".to_string()],
+    "filter[status]".to_string() => vec!["pending".to_string()],
+    "ids[]".to_string() => vec!["2".to_string()],
+    "page".to_string() => vec!["2".to_string()]
+  });
+  let result = match_structured_query(expected, actual, &context);
+  let mut descriptions = result.values().flatten().map(|m| m.description()).collect::<Vec<_>>();
+  descriptions.sort();
+  expect!(descriptions).to(be_equal_to(vec![
+    "$.filter.owner -> Expected 'me' to be equal to 'you'".to_string(),
+    "$.filter.status -> Expected 'pending' to match 'open|closed'".to_string(),
+    "Unexpected query parameter 'page' received".to_string()
+  ]));
+}
+
 #[tokio::test]
 async fn body_does_not_match_if_different_content_types() {
   let expected = Request {
@@ -348,6 +449,28 @@ async fn body_matching_uses_any_matcher_for_content_type_header() {
   expect!(mismatches.iter()).to(be_empty());
 }
 
+#[tokio::test]
+async fn body_mismatches_provide_a_json_patch() {
+  let expected = Request {
+    headers: Some(hashmap! { s!("Content-Type") => vec![s!("application/json")] }),
+    body: OptionalBody::Present(r#"{"id": 100, "tags": ["a"]}"#.into(), None, None),
+    ..Request::default()
+  };
+  let actual = Request {
+    headers: Some(hashmap! { s!("Content-Type") => vec![s!("application/json")] }),
+    body: OptionalBody::Present(r#"{"id": 200, "tags": ["a", "b"]}"#.into(), None, None),
+    ..Request::default()
+  };
+  let result = match_body(&expected, &actual, &CoreMatchingContext::default(), &CoreMatchingContext::default()).await;
+  expect!(result.json_patch(&expected.body, &actual.body, DiffConfig::AllowUnexpectedKeys)).to(be_some().value(json!([
+    { "op": "replace", "path": "/id", "value": 100 },
+    { "op": "remove", "path": "/tags/1" }
+  ])));
+
+  let result = match_body(&expected, &expected, &CoreMatchingContext::default(), &CoreMatchingContext::default()).await;
+  expect!(result.json_patch(&expected.body, &expected.body, DiffConfig::AllowUnexpectedKeys)).to(be_none());
+}
+
 #[tokio::test]
 async fn body_matches_if_expected_is_missing() {
   let expected = Request {
@@ -392,6 +515,72 @@ async fn body_matches_with_extended_mime_types() {
   expect!(result.mismatches().iter()).to(be_empty());
 }
 
+#[tokio::test]
+async fn body_matches_form_urlencoded_bodies_regardless_of_field_order() {
+  let expected = Request {
+    method: s!("POST"),
+    path: s!("/"),
+    query: None,
+    headers: Some(hashmap! { s!("Content-Type") => vec![s!("application/x-www-form-urlencoded")] }),
+    body: OptionalBody::Present("amount=100&currency=AUD".into(), None, None),
+    ..Request::default()
+  };
+  let actual = Request {
+    method: s!("POST"),
+    path: s!("/"),
+    query: None,
+    headers: Some(hashmap! { s!("Content-Type") => vec![s!("application/x-www-form-urlencoded")] }),
+    body: OptionalBody::Present("currency=AUD&amount=100".into(), None, None),
+    ..Request::default()
+  };
+  let result = match_body(&expected, &actual, &CoreMatchingContext::default(), &CoreMatchingContext::default()).await;
+  expect!(result.mismatches().iter()).to(be_empty());
+
+  let actual = Request {
+    body: OptionalBody::Present("currency=USD&amount=100".into(), None, None),
+    .. actual
+  };
+  let result = match_body(&expected, &actual, &CoreMatchingContext::default(), &CoreMatchingContext::default()).await;
+  expect!(result.mismatches().iter().map(|m| match m {
+    Mismatch::BodyMismatch { path, .. } => path.clone(),
+    _ => String::default()
+  }).collect::<Vec<_>>()).to(be_equal_to(vec!["$.currency[0]".to_string()]));
+}
+
+#[tokio::test]
+async fn body_matches_ndjson_bodies_record_by_record() {
+  crate::matchers::configure_core_catalogue();
+  for content_type in ["application/x-ndjson", "application/jsonl", "application/x-jsonlines"] {
+    let matcher = pact_plugin_driver::catalogue_manager::find_content_matcher(&ContentType::parse(content_type).unwrap());
+    expect!(matcher.map(|matcher| matcher.catalogue_entry_key())).to(
+      be_some().value("core/content-matcher/ndjson".to_string()));
+  }
+  let expected = Request {
+    method: s!("POST"),
+    path: s!("/"),
+    query: None,
+    headers: Some(hashmap! { s!("Content-Type") => vec![s!("application/x-ndjson")] }),
+    body: OptionalBody::Present("{\"id\":1,\"status\":\"OK\"}\n{\"id\":2,\"status\":\"OK\"}\n".into(), None, None),
+    ..Request::default()
+  };
+  let actual = Request {
+    body: OptionalBody::Present("{\"status\":\"OK\",\"id\":1}\n{\"status\":\"OK\",\"id\":2}\n".into(), None, None),
+    ..expected.clone()
+  };
+  let result = match_body(&expected, &actual, &CoreMatchingContext::default(), &CoreMatchingContext::default()).await;
+  expect!(result.mismatches().iter()).to(be_empty());
+
+  let actual = Request {
+    body: OptionalBody::Present("{\"status\":\"OK\",\"id\":1}\n{\"status\":\"FAILED\",\"id\":2}\n".into(), None, None),
+    .. actual
+  };
+  let result = match_body(&expected, &actual, &CoreMatchingContext::default(), &CoreMatchingContext::default()).await;
+  expect!(result.mismatches().iter().map(|m| match m {
+    Mismatch::BodyMismatch { path, .. } => path.clone(),
+    _ => String::default()
+  }).collect::<Vec<_>>()).to(be_equal_to(vec!["$[1].status".to_string()]));
+}
+
 #[test]
 fn partial_equal_for_method_mismatch() {
   let mismatch = Mismatch::MethodMismatch { expected: s!("get"), actual: s!("post") };
@@ -659,3 +848,94 @@ fn values_matcher_defined() {
   expect!(context.values_matcher_defined(&path_x.join("0").join("z"))).to(be_false());
   expect!(context.values_matcher_defined(&path_y.join("0").join("y"))).to(be_false());
 }
+
+#[tokio::test]
+async fn match_request_with_explanation_records_the_rules_applied_at_each_path() {
+  let expected = HttpRequest {
+    path: "/orders".to_string(),
+    query: Some(hashmap!{ "page".to_string() => vec!["1".to_string()] }),
+    headers: Some(hashmap!{ "Content-Type".to_string() => vec!["application/json".to_string()] }),
+    body: OptionalBody::Present(r#"{"id": 100, "name": "Fred"}"#.into(), None, None),
+    matching_rules: matchingrules! {
+      "body" => { "$.id" => [ MatchingRule::Integer ] },
+      "query" => { "page" => [ MatchingRule::Regex("\\d+".to_string()) ] }
+    },
+    .. HttpRequest::default()
+  };
+  let actual = HttpRequest {
+    path: "/orders".to_string(),
+    query: Some(hashmap!{ "page".to_string() => vec!["2".to_string()] }),
+    headers: Some(hashmap!{ "Content-Type".to_string() => vec!["application/json".to_string()] }),
+    body: OptionalBody::Present(r#"{"id": 200, "name": "Mary"}"#.into(), None, None),
+    .. HttpRequest::default()
+  };
+  let interaction = SynchronousHttp { request: expected.clone(), .. SynchronousHttp::default() };
+  let pact: Box<dyn Pact + Send + Sync> = Box::new(V4Pact::default());
+
+  let (result, explanation) = match_request_with_explanation(expected, actual, &pact, &interaction.boxed()).await;
+  expect!(result.body.mismatches().len()).to(be_equal_to(1));
+  let entries = explanation.entries.iter()
+    .map(|entry| (entry.category.as_str(), entry.path.as_str(), entry.selected.is_some(), entry.matched()))
+    .collect::<Vec<_>>();
+  expect!(entries).to(be_equal_to(vec![
+    ("path", "", false, true),
+    ("body", "$", false, false),
+    ("body", "$.id", true, true),
+    ("body", "$.name", false, false),
+    ("query", "$.page", true, true),
+    ("header", "$['Content-Type']", false, true)
+  ]));
+}
+
+#[tokio::test]
+async fn validate_interaction_examples_returns_the_examples_that_do_not_satisfy_the_matching_rules() {
+  let interaction = SynchronousHttp {
+    request: HttpRequest {
+      path: "/orders/abc".to_string(),
+      matching_rules: matchingrules! {
+        "path" => { "" => [ MatchingRule::Regex("/orders/\\d+".to_string()) ] }
+      },
+      .. HttpRequest::default()
+    },
+    response: HttpResponse {
+      headers: Some(hashmap!{ "Content-Type".to_string() => vec!["application/json".to_string()] }),
+      body: OptionalBody::Present(r#"{"id": "abc", "name": "Fred"}"#.into(), None, None),
+      matching_rules: matchingrules! {
+        "body" => {
+          "$.id" => [ MatchingRule::Regex("\\d+".to_string()) ],
+          "$.name" => [ MatchingRule::Type ]
+        }
+      },
+      .. HttpResponse::default()
+    },
+    .. SynchronousHttp::default()
+  }.boxed();
+  let pact: Box<dyn Pact + Send + Sync> = Box::new(V4Pact::default());
+
+  let results = validate_interaction_examples(&interaction, &pact).await;
+  let results = results.iter()
+    .map(|(part, mismatch)| (part.as_str(), mismatch.mismatch_type()))
+    .collect::<Vec<_>>();
+  expect!(results).to(be_equal_to(vec![
+    ("request", "PathMismatch"),
+    ("response", "BodyMismatch")
+  ]));
+}
+
+#[tokio::test]
+async fn validate_interaction_examples_with_consistent_examples() {
+  let interaction = SynchronousHttp {
+    response: HttpResponse {
+      headers: Some(hashmap!{ "Content-Type".to_string() => vec!["application/json".to_string()] }),
+      body: OptionalBody::Present(r#"{"id": "100"}"#.into(), None, None),
+      matching_rules: matchingrules! {
+        "body" => { "$.id" => [ MatchingRule::Regex("\\d+".to_string()) ] }
+      },
+      .. HttpResponse::default()
+    },
+    .. SynchronousHttp::default()
+  }.boxed();
+  let pact: Box<dyn Pact + Send + Sync> = Box::new(V4Pact::default());
+
+  expect!(validate_interaction_examples(&interaction, &pact).await).to(be_equal_to(vec![]));
+}
@@ -15,6 +15,7 @@ use pact_models::path_exp::DocPath;
 use pact_models::xml_utils::parse_bytes;
 use tracing::debug;
 
+use crate::explain::explain_path;
 use crate::matchers::*;
 use crate::MatchingContext;
 
@@ -122,7 +123,7 @@ impl<'a> Matches<&'a Element<'a>> for &'a Element<'a> {
           } else {
             Ok(())
           },
-          MatchingRule::Equality => {
+          MatchingRule::Equality | MatchingRule::EqualsIgnoreOrder => {
              if self.name() == actual.name() {
                  Ok(())
              } else {
@@ -147,6 +148,27 @@ fn compare_element(
   actual: &Element,
   mismatches: &mut Vec<super::Mismatch>,
   context: &dyn MatchingContext
+) {
+  let result = explain_path(context, path, || {
+    let mut element_mismatches = vec![];
+    compare_element_contents(path, expected, actual, &mut element_mismatches, context);
+    if element_mismatches.is_empty() {
+      Ok(())
+    } else {
+      Err(element_mismatches)
+    }
+  });
+  if let Err(element_mismatches) = result {
+    mismatches.extend(element_mismatches);
+  }
+}
+
+fn compare_element_contents(
+  path: &DocPath,
+  expected: &Element,
+  actual: &Element,
+  mismatches: &mut Vec<super::Mismatch>,
+  context: &dyn MatchingContext
 ) {
   let matcher_result = if context.matcher_is_defined(path) {
     debug!("calling match_values {:?} on {:?}", path, actual);
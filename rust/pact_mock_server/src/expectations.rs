@@ -0,0 +1,220 @@
+//!
+//! Support for verifying the number of times the interactions were called, and the order in which
+//! they were called.
+//!
+//! The expected number of calls for an interaction can be configured in the `MockServerConfig`
+//! (keyed by the interaction description, or `*` for all interactions). In the JSON config, the
+//! expected calls can either be a number (exactly that number of calls), or one of
+//! `{ "exactly": 1 }`, `{ "atLeast": 1 }` or `{ "atMost": 1 }`. Interactions without any expected
+//! calls must be called at least once.
+//!
+
+use std::fmt::{Display, Formatter};
+
+use pact_models::v4::interaction::V4Interaction;
+use pact_models::v4::synch_http::SynchronousHttp;
+use serde_json::{json, Value};
+
+use crate::matching::{KeyedMatchResult, MatchResult};
+use crate::mock_server::MockServerConfig;
+
+/// Key to use in the `MockServerConfig` expected calls to apply to all interactions
+pub const ALL_INTERACTIONS: &str = "*";
+
+/// Expected number of calls for an interaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpectedCalls {
+  /// Interaction must be called exactly this number of times
+  Exactly(usize),
+  /// Interaction must be called at least this number of times
+  AtLeast(usize),
+  /// Interaction must be called at most this number of times
+  AtMost(usize)
+}
+
+impl ExpectedCalls {
+  /// If the number of calls satisfies the expected calls
+  pub fn is_satisfied_by(&self, calls: usize) -> bool {
+    match self {
+      ExpectedCalls::Exactly(n) => calls == *n,
+      ExpectedCalls::AtLeast(n) => calls >= *n,
+      ExpectedCalls::AtMost(n) => calls <= *n
+    }
+  }
+
+  /// Parses the expected calls from JSON. This can either be a number (exactly that number of
+  /// calls), or an object with one of `exactly`, `atLeast` or `atMost` attributes.
+  pub fn from_json(value: &Value) -> Option<ExpectedCalls> {
+    match value {
+      Value::Number(n) => n.as_u64().map(|n| ExpectedCalls::Exactly(n as usize)),
+      Value::Object(map) => {
+        if let Some(n) = map.get("exactly").and_then(|n| n.as_u64()) {
+          Some(ExpectedCalls::Exactly(n as usize))
+        } else if let Some(n) = map.get("atLeast").and_then(|n| n.as_u64()) {
+          Some(ExpectedCalls::AtLeast(n as usize))
+        } else {
+          map.get("atMost").and_then(|n| n.as_u64()).map(|n| ExpectedCalls::AtMost(n as usize))
+        }
+      }
+      _ => None
+    }
+  }
+
+  /// Converts the expected calls to JSON
+  pub fn to_json(&self) -> Value {
+    match self {
+      ExpectedCalls::Exactly(n) => json!({ "exactly": n }),
+      ExpectedCalls::AtLeast(n) => json!({ "atLeast": n }),
+      ExpectedCalls::AtMost(n) => json!({ "atMost": n })
+    }
+  }
+}
+
+impl Display for ExpectedCalls {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ExpectedCalls::Exactly(n) => write!(f, "exactly {} call(s)", n),
+      ExpectedCalls::AtLeast(n) => write!(f, "at least {} call(s)", n),
+      ExpectedCalls::AtMost(n) => write!(f, "at most {} call(s)", n)
+    }
+  }
+}
+
+/// Returns the expected calls for the interaction. Expected calls configured for the interaction
+/// description in the mock server config take precedence over expected calls configured for all
+/// interactions.
+pub(crate) fn expected_calls_for_interaction(interaction: &SynchronousHttp, config: &MockServerConfig) -> Option<ExpectedCalls> {
+  config.expected_calls.get(&interaction.description).cloned()
+    .or_else(|| config.expected_calls.get(ALL_INTERACTIONS).cloned())
+}
+
+/// Returns the number of times the interaction was called
+pub(crate) fn call_count(interaction: &SynchronousHttp, matches: &[KeyedMatchResult]) -> usize {
+  let interaction_key = interaction.unique_key();
+  matches.iter()
+    .filter(|(m, key)| m.matched() && key.as_ref() == Some(&interaction_key))
+    .count()
+}
+
+/// Checks that the interactions were called in the order they are defined in. Each request that
+/// was received after a request for a later interaction is returned as out of order.
+pub(crate) fn out_of_order_requests(interactions: &[SynchronousHttp], matches: &[KeyedMatchResult]) -> Vec<MatchResult> {
+  let mut result = vec![];
+  let mut last: Option<(usize, &SynchronousHttp)> = None;
+  for m in matches {
+    if let (MatchResult::RequestMatch(request, _), Some(key)) = m {
+      let index = interactions.iter()
+        .position(|i| &i.unique_key() == key);
+      match (index, last) {
+        (Some(index), Some((last_index, last_interaction))) if index < last_index => {
+          result.push(MatchResult::RequestOutOfOrder(request.clone(), last_interaction.request.clone()));
+        }
+        (Some(index), _) => last = Some((index, &interactions[index])),
+        _ => ()
+      }
+    }
+  }
+  result
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use maplit::hashmap;
+  use pact_models::v4::http_parts::{HttpRequest, HttpResponse};
+  use pact_models::v4::synch_http::SynchronousHttp;
+  use serde_json::json;
+
+  use crate::matching::MatchResult;
+  use crate::mock_server::MockServerConfig;
+
+  use super::*;
+
+  #[test]
+  fn expected_calls_from_json() {
+    expect!(ExpectedCalls::from_json(&json!(2))).to(be_some().value(ExpectedCalls::Exactly(2)));
+    expect!(ExpectedCalls::from_json(&json!({ "exactly": 1 }))).to(be_some().value(ExpectedCalls::Exactly(1)));
+    expect!(ExpectedCalls::from_json(&json!({ "atLeast": 1 }))).to(be_some().value(ExpectedCalls::AtLeast(1)));
+    expect!(ExpectedCalls::from_json(&json!({ "atMost": 3 }))).to(be_some().value(ExpectedCalls::AtMost(3)));
+    expect!(ExpectedCalls::from_json(&json!({}))).to(be_none());
+    expect!(ExpectedCalls::from_json(&json!("1"))).to(be_none());
+    expect!(ExpectedCalls::from_json(&ExpectedCalls::AtMost(3).to_json())).to(be_some().value(ExpectedCalls::AtMost(3)));
+  }
+
+  #[test]
+  fn expected_calls_is_satisfied_by() {
+    expect!(ExpectedCalls::Exactly(1).is_satisfied_by(1)).to(be_true());
+    expect!(ExpectedCalls::Exactly(1).is_satisfied_by(2)).to(be_false());
+    expect!(ExpectedCalls::AtLeast(2).is_satisfied_by(3)).to(be_true());
+    expect!(ExpectedCalls::AtLeast(2).is_satisfied_by(1)).to(be_false());
+    expect!(ExpectedCalls::AtMost(1).is_satisfied_by(0)).to(be_true());
+    expect!(ExpectedCalls::AtMost(1).is_satisfied_by(2)).to(be_false());
+  }
+
+  #[test]
+  fn expected_calls_for_interaction_test() {
+    let interaction = SynchronousHttp {
+      description: "a request".to_string(),
+      .. SynchronousHttp::default()
+    };
+
+    expect!(expected_calls_for_interaction(&interaction, &MockServerConfig::default())).to(be_none());
+
+    let config = MockServerConfig {
+      expected_calls: hashmap!{ "*".to_string() => ExpectedCalls::Exactly(1) },
+      .. MockServerConfig::default()
+    };
+    expect!(expected_calls_for_interaction(&interaction, &config)).to(be_some().value(ExpectedCalls::Exactly(1)));
+
+    let config = MockServerConfig {
+      expected_calls: hashmap!{
+        "*".to_string() => ExpectedCalls::Exactly(1),
+        "a request".to_string() => ExpectedCalls::AtMost(1)
+      },
+      .. MockServerConfig::default()
+    };
+    expect!(expected_calls_for_interaction(&interaction, &config)).to(be_some().value(ExpectedCalls::AtMost(1)));
+    expect!(expected_calls_for_interaction(&SynchronousHttp::default(), &config))
+      .to(be_some().value(ExpectedCalls::Exactly(1)));
+  }
+
+  #[test]
+  fn call_count_uses_the_interaction_key() {
+    let interaction1 = SynchronousHttp { description: "first".to_string(), .. SynchronousHttp::default() };
+    let interaction2 = SynchronousHttp { description: "second".to_string(), .. SynchronousHttp::default() };
+    let called = |interaction: &SynchronousHttp| (MatchResult::RequestMatch(interaction.request.clone(),
+      interaction.response.clone()), Some(interaction.unique_key()));
+    let matches = vec![called(&interaction1), called(&interaction1), called(&interaction2)];
+
+    expect!(call_count(&interaction1, &matches)).to(be_equal_to(2));
+    expect!(call_count(&interaction2, &matches)).to(be_equal_to(1));
+    expect!(call_count(&SynchronousHttp::default(), &matches)).to(be_equal_to(0));
+  }
+
+  #[test]
+  fn out_of_order_requests_test() {
+    let interaction = |path: &str| SynchronousHttp {
+      request: HttpRequest { path: path.to_string(), .. HttpRequest::default() },
+      .. SynchronousHttp::default()
+    };
+    let interactions = vec![interaction("/one"), interaction("/two"), interaction("/three")];
+    let called = |path: &str| {
+      let interaction = interaction(path);
+      let key = interaction.unique_key();
+      (MatchResult::RequestMatch(interaction.request, HttpResponse::default()), Some(key))
+    };
+
+    expect!(out_of_order_requests(&interactions, &[])).to(be_equal_to(vec![]));
+    expect!(out_of_order_requests(&interactions, &[called("/one"), called("/two"), called("/two"), called("/three")]))
+      .to(be_equal_to(vec![]));
+    expect!(out_of_order_requests(&interactions, &[called("/two"), called("/one"), called("/three")]))
+      .to(be_equal_to(vec![
+        MatchResult::RequestOutOfOrder(interaction("/one").request, interaction("/two").request)
+      ]));
+    expect!(out_of_order_requests(&interactions, &[called("/three"), called("/one"), called("/two")]))
+      .to(be_equal_to(vec![
+        MatchResult::RequestOutOfOrder(interaction("/one").request, interaction("/three").request),
+        MatchResult::RequestOutOfOrder(interaction("/two").request, interaction("/three").request)
+      ]));
+  }
+}
@@ -0,0 +1,197 @@
+//!
+//! Support for injecting latency and faults into the responses from the mock server, so that
+//! client timeouts and retry logic can be tested.
+//!
+//! Faults can be configured for an interaction in the `MockServerConfig` (keyed by the
+//! interaction description, or `*` for all interactions), or with a `faults` entry in the
+//! comments of a V4 interaction. The JSON form of the faults is:
+//!
+//! ```json
+//! {
+//!   "delay": 500,
+//!   "closeConnection": false,
+//!   "truncateBody": 10
+//! }
+//! ```
+//!
+//! where the delay is in milliseconds, and can also be a random delay in the form
+//! `{ "min": 100, "max": 500 }`.
+//!
+
+use std::time::Duration;
+
+use pact_models::v4::synch_http::SynchronousHttp;
+use rand::Rng;
+use serde_json::Value;
+
+use crate::mock_server::MockServerConfig;
+use crate::utils::json_to_bool;
+
+/// Key to use in the `MockServerConfig` faults to apply the faults to all interactions
+pub const ALL_INTERACTIONS: &str = "*";
+
+/// Key used in the comments of a V4 interaction to configure the faults for the interaction
+pub const FAULTS_COMMENT_KEY: &str = "faults";
+
+/// Delay to apply before a response is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseDelay {
+  /// Fixed delay
+  Fixed(Duration),
+  /// Random delay between the minimum and maximum values (inclusive)
+  Random(Duration, Duration)
+}
+
+impl ResponseDelay {
+  /// Returns the duration to delay the response for
+  pub fn duration(&self) -> Duration {
+    match self {
+      ResponseDelay::Fixed(delay) => *delay,
+      ResponseDelay::Random(min, max) => if min < max {
+        rand::thread_rng().gen_range(*min..=*max)
+      } else {
+        *min
+      }
+    }
+  }
+
+  /// Parses a delay from JSON. The delay can either be a number of milliseconds, or an object
+  /// with `min` and `max` attributes in milliseconds.
+  pub fn from_json(value: &Value) -> Option<ResponseDelay> {
+    match value {
+      Value::Number(delay) => delay.as_u64()
+        .map(|delay| ResponseDelay::Fixed(Duration::from_millis(delay))),
+      Value::Object(map) => {
+        let min = map.get("min").and_then(|min| min.as_u64()).unwrap_or_default();
+        let max = map.get("max").and_then(|max| max.as_u64()).unwrap_or(min);
+        Some(ResponseDelay::Random(Duration::from_millis(min), Duration::from_millis(max)))
+      }
+      _ => None
+    }
+  }
+}
+
+/// Faults to inject into the response for an interaction
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FaultConfig {
+  /// Delay before the response is sent
+  pub delay: Option<ResponseDelay>,
+  /// Close the connection without sending a response (after any delay)
+  pub close_connection: bool,
+  /// Only send this number of bytes of the response body before closing the connection. The
+  /// Content-Length header will still be for the full body.
+  pub truncate_body: Option<usize>
+}
+
+impl FaultConfig {
+  /// Parses the fault config from JSON. This method is tolerant of invalid JSON formats.
+  pub fn from_json(value: &Value) -> FaultConfig {
+    let mut config = FaultConfig::default();
+
+    if let Value::Object(map) = value {
+      if let Some(delay) = map.get("delay") {
+        config.delay = ResponseDelay::from_json(delay);
+      }
+      if let Some(close_connection) = map.get("closeConnection") {
+        config.close_connection = json_to_bool(close_connection).unwrap_or_default();
+      }
+      if let Some(truncate_body) = map.get("truncateBody") {
+        config.truncate_body = truncate_body.as_u64().map(|len| len as usize);
+      }
+    }
+
+    config
+  }
+
+  /// If there are no faults configured
+  pub fn is_empty(&self) -> bool {
+    self.delay.is_none() && !self.close_connection && self.truncate_body.is_none()
+  }
+}
+
+/// Returns the faults to apply to the response for the interaction. Faults configured for the
+/// interaction description in the mock server config take precedence over faults from the
+/// comments of the interaction, which take precedence over faults configured for all interactions.
+pub(crate) fn faults_for_interaction(interaction: &SynchronousHttp, config: &MockServerConfig) -> Option<FaultConfig> {
+  config.faults.get(&interaction.description).cloned()
+    .or_else(|| interaction.comments.get(FAULTS_COMMENT_KEY).map(FaultConfig::from_json))
+    .or_else(|| config.faults.get(ALL_INTERACTIONS).cloned())
+    .filter(|faults| !faults.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+  use std::time::Duration;
+
+  use expectest::prelude::*;
+  use maplit::hashmap;
+  use pact_models::v4::synch_http::SynchronousHttp;
+  use serde_json::json;
+
+  use crate::mock_server::MockServerConfig;
+
+  use super::{faults_for_interaction, FaultConfig, ResponseDelay};
+
+  #[test]
+  fn fault_config_from_json() {
+    expect!(FaultConfig::from_json(&json!({}))).to(be_equal_to(FaultConfig::default()));
+    expect!(FaultConfig::from_json(&json!({ "delay": 500 }))).to(be_equal_to(FaultConfig {
+      delay: Some(ResponseDelay::Fixed(Duration::from_millis(500))),
+      .. FaultConfig::default()
+    }));
+    expect!(FaultConfig::from_json(&json!({
+      "delay": { "min": 100, "max": 200 },
+      "closeConnection": true,
+      "truncateBody": 10
+    }))).to(be_equal_to(FaultConfig {
+      delay: Some(ResponseDelay::Random(Duration::from_millis(100), Duration::from_millis(200))),
+      close_connection: true,
+      truncate_body: Some(10)
+    }));
+  }
+
+  #[test]
+  fn random_delay_is_within_the_range() {
+    let delay = ResponseDelay::Random(Duration::from_millis(100), Duration::from_millis(200));
+    for _ in 0..10 {
+      let duration = delay.duration();
+      expect!(duration >= Duration::from_millis(100) && duration <= Duration::from_millis(200)).to(be_true());
+    }
+    expect!(ResponseDelay::Random(Duration::from_millis(100), Duration::from_millis(50)).duration())
+      .to(be_equal_to(Duration::from_millis(100)));
+  }
+
+  #[test]
+  fn faults_for_interaction_test() {
+    let fixed = |ms| FaultConfig {
+      delay: Some(ResponseDelay::Fixed(Duration::from_millis(ms))),
+      .. FaultConfig::default()
+    };
+    let interaction = SynchronousHttp {
+      description: "a request".to_string(),
+      .. SynchronousHttp::default()
+    };
+    let interaction_with_comments = SynchronousHttp {
+      description: "a request".to_string(),
+      comments: hashmap!{ "faults".to_string() => json!({ "delay": 200 }) },
+      .. SynchronousHttp::default()
+    };
+
+    let config = MockServerConfig::default();
+    expect!(faults_for_interaction(&interaction, &config)).to(be_none());
+    expect!(faults_for_interaction(&interaction_with_comments, &config)).to(be_some().value(fixed(200)));
+
+    let config = MockServerConfig {
+      faults: hashmap!{ "*".to_string() => fixed(100) },
+      .. MockServerConfig::default()
+    };
+    expect!(faults_for_interaction(&interaction, &config)).to(be_some().value(fixed(100)));
+    expect!(faults_for_interaction(&interaction_with_comments, &config)).to(be_some().value(fixed(200)));
+
+    let config = MockServerConfig {
+      faults: hashmap!{ "*".to_string() => fixed(100), "a request".to_string() => fixed(300) },
+      .. MockServerConfig::default()
+    };
+    expect!(faults_for_interaction(&interaction_with_comments, &config)).to(be_some().value(fixed(300)));
+  }
+}
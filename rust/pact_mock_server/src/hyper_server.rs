@@ -5,11 +5,12 @@ use std::net::SocketAddr;
 use std::pin::Pin;
 use std::sync::{Arc, Mutex};
 
+use chrono::Utc;
 use futures::prelude::*;
 use futures::StreamExt;
 use futures::task::{Context, Poll};
-use hyper::{Body, Error, Response, Server};
-use hyper::http::header::{HeaderName, HeaderValue};
+use hyper::{Body, Response, Server};
+use hyper::http::header::{HeaderName, HeaderValue, SET_COOKIE};
 use hyper::http::response::Builder as ResponseBuilder;
 use hyper::service::make_service_fn;
 use hyper::service::service_fn;
@@ -19,7 +20,8 @@ use pact_models::generators::GeneratorTestMode;
 use pact_models::http_parts::HttpPart;
 use pact_models::pact::Pact;
 use pact_models::query_strings::parse_query_string;
-use pact_models::v4::http_parts::HttpRequest;
+use pact_models::v4::http_parts::{HttpRequest, HttpResponse};
+use pact_models::v4::interaction::V4Interaction;
 use rustls::ServerConfig;
 use serde_json::json;
 use tokio::net::{TcpListener, TcpStream};
@@ -29,15 +31,24 @@ use tracing::{debug, error, info, trace, warn};
 
 use pact_matching::logging::LOG_ID;
 
-use crate::matching::{match_request, MatchResult};
+use crate::faults::{self, FaultConfig};
+use crate::journal::JournalEntry;
+use crate::matching::{KeyedMatchResult, match_interaction, MatchResult, PROVIDER_STATE_HEADER};
 use crate::mock_server::MockServer;
+use crate::proxy;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, thiserror::Error)]
 enum InteractionError {
+    #[error("Found an invalid header encoding")]
     RequestHeaderEncodingError,
+    #[error("Could not process request body")]
     RequestBodyError,
+    #[error("Could not set response header")]
     ResponseHeaderEncodingError,
-    ResponseBodyError
+    #[error("Could not process response body")]
+    ResponseBodyError,
+    #[error("Connection closed by fault injection")]
+    ConnectionClosed
 }
 
 fn extract_path(uri: &hyper::Uri) -> String {
@@ -75,7 +86,12 @@ fn extract_headers(headers: &hyper::HeaderMap) -> Result<Option<HashMap<String,
         } else {
           Ok((name.as_str().into(), parsed_vals.iter().cloned()
             .map(|val| val.unwrap_or_default())
-            .flat_map(|val| val.split(",").map(|v| v.to_string()).collect::<Vec<String>>())
+            .flat_map(|val| if name == SET_COOKIE ||
+              name.as_str().eq_ignore_ascii_case(PROVIDER_STATE_HEADER) {
+              vec![val]
+            } else {
+              val.split(",").map(|v| v.to_string()).collect::<Vec<String>>()
+            })
             .map(|val| val.trim().to_string())
             .collect()))
         }
@@ -120,6 +136,26 @@ async fn hyper_request_to_pact_request(req: hyper::Request<Body>) -> Result<Http
     })
 }
 
+/// Removes the provider state header from the request, returning the provider states from it
+fn extract_provider_states(request: HttpRequest) -> (HttpRequest, Vec<String>) {
+  match request.headers {
+    Some(ref headers) => {
+      let mut headers = headers.clone();
+      let key = headers.keys()
+        .find(|key| key.eq_ignore_ascii_case(PROVIDER_STATE_HEADER))
+        .cloned();
+      match key.and_then(|key| headers.remove(&key)) {
+        Some(states) => (HttpRequest {
+          headers: if headers.is_empty() { None } else { Some(headers) },
+          .. request
+        }, states),
+        None => (request, vec![])
+      }
+    }
+    None => (request, vec![])
+  }
+}
+
 fn set_hyper_headers(builder: &mut ResponseBuilder, headers: &Option<HashMap<String, Vec<String>>>) -> Result<(), InteractionError> {
     let hyper_headers = builder.headers_mut().unwrap();
     match headers {
@@ -156,9 +192,10 @@ fn error_body(request: &HttpRequest, error: &String) -> String {
 async fn match_result_to_hyper_response(
   request: &HttpRequest,
   match_result: MatchResult,
+  faults: Option<FaultConfig>,
   mock_server: Arc<Mutex<MockServer>>
-) -> Result<Response<Body>, InteractionError> {
-  let (context, cors_preflight) = {
+) -> Result<(Response<Body>, HttpResponse), InteractionError> {
+  let (context, cors_preflight, unmatched_status) = {
     let ms = mock_server.lock().unwrap();
     (
       hashmap!{
@@ -167,7 +204,8 @@ async fn match_result_to_hyper_response(
           "port": ms.port
         })
       },
-      ms.config.cors_preflight
+      ms.config.cors_preflight,
+      ms.config.unmatched_status.unwrap_or(500)
     )
   };
 
@@ -180,6 +218,17 @@ async fn match_result_to_hyper_response(
         debug!("     body: '{}'", response.body.str_value());
       }
 
+      let faults = faults.unwrap_or_default();
+      if let Some(delay) = faults.delay {
+        let duration = delay.duration();
+        info!("Delaying the response by {:?}", duration);
+        tokio::time::sleep(duration).await;
+      }
+      if faults.close_connection {
+        info!("Closing the connection without sending a response");
+        return Err(InteractionError::ConnectionClosed);
+      }
+
       let mut builder = Response::builder()
         .status(response.status)
         .header(hyper::header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")
@@ -189,11 +238,17 @@ async fn match_result_to_hyper_response(
 
       set_hyper_headers(&mut builder, &response.headers)?;
 
-      builder.body(match response.body {
-        OptionalBody::Present(ref s, _, _) => Body::from(s.clone()),
+      let body = match response.body {
+        OptionalBody::Present(ref s, _, _) => match faults.truncate_body {
+          Some(len) if len < s.len() => truncated_body(&mut builder, s.clone(), len),
+          _ => Body::from(s.clone())
+        },
         _ => Body::empty()
-      })
-        .map_err(|_| InteractionError::ResponseBodyError)
+      };
+      let hyper_response = builder.body(body)
+        .map_err(|_| InteractionError::ResponseBodyError)?;
+      let sent = journal_response(&hyper_response, response.body.clone());
+      Ok((hyper_response, sent))
     },
     _ => {
       debug!("Request did not match: {}", match_result);
@@ -212,34 +267,70 @@ async fn match_result_to_hyper_response(
           None => "*".to_string()
         };
 
-        Response::builder()
+        let hyper_response = Response::builder()
           .status(204)
           .header(hyper::header::ACCESS_CONTROL_ALLOW_ORIGIN, origin)
           .header(hyper::header::ACCESS_CONTROL_ALLOW_METHODS, "GET, HEAD, POST, PUT, DELETE, CONNECT, OPTIONS, TRACE, PATCH")
           .header(hyper::header::ACCESS_CONTROL_ALLOW_HEADERS, cors_headers)
           .header(hyper::header::ACCESS_CONTROL_EXPOSE_HEADERS, "Location, Link")
           .body(Body::empty())
-          .map_err(|_| InteractionError::ResponseBodyError)
+          .map_err(|_| InteractionError::ResponseBodyError)?;
+        let sent = journal_response(&hyper_response, OptionalBody::Empty);
+        Ok((hyper_response, sent))
       } else {
-        Response::builder()
-          .status(500)
+        let body = error_body(request, &match_result.match_key());
+        let hyper_response = Response::builder()
+          .status(unmatched_status)
           .header(hyper::header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")
           .header(hyper::header::CONTENT_TYPE, "application/json; charset=utf-8")
           .header("X-Pact", match_result.match_key())
-          .body(Body::from(error_body(&request, &match_result.match_key())))
-          .map_err(|_| InteractionError::ResponseBodyError)
+          .body(Body::from(body.clone()))
+          .map_err(|_| InteractionError::ResponseBodyError)?;
+        let sent = journal_response(&hyper_response, OptionalBody::Present(body.into(), None, None));
+        Ok((hyper_response, sent))
       }
     }
   }
 }
 
+/// Converts the response sent back for the request to a Pact response for the request journal
+fn journal_response(response: &Response<Body>, body: OptionalBody) -> HttpResponse {
+  let mut headers: HashMap<String, Vec<String>> = HashMap::new();
+  for (name, value) in response.headers() {
+    headers.entry(name.to_string()).or_default().push(value.to_str().unwrap_or_default().to_string());
+  }
+  HttpResponse {
+    status: response.status().as_u16(),
+    headers: if headers.is_empty() { None } else { Some(headers) },
+    body,
+    .. HttpResponse::default()
+  }
+}
+
+/// Returns a body that will only send the first `len` bytes of the body, and then abort. The
+/// Content-Length header is set for the full body, so the client will see a truncated response.
+fn truncated_body(builder: &mut ResponseBuilder, body: bytes::Bytes, len: usize) -> Body {
+  info!("Truncating the response body to {} of {} bytes", len, body.len());
+  if let Some(headers) = builder.headers_mut() {
+    headers.insert(hyper::header::CONTENT_LENGTH, HeaderValue::from(body.len()));
+  }
+  let (mut sender, truncated) = Body::channel();
+  tokio::spawn(async move {
+    if sender.send_data(body.slice(..len)).await.is_ok() {
+      sender.abort();
+    }
+  });
+  truncated
+}
+
 async fn handle_request(
   req: hyper::Request<Body>,
   pact: Arc<Mutex<dyn Pact + Send + Sync>>,
-  matches: Arc<Mutex<Vec<MatchResult>>>,
+  matches: Arc<Mutex<Vec<KeyedMatchResult>>>,
   mock_server: Arc<Mutex<MockServer>>
 ) -> Result<Response<Body>, InteractionError> {
   debug!("Creating pact request from hyper request");
+  let timestamp = Utc::now();
 
   {
     let mut guard = mock_server.lock().unwrap();
@@ -248,44 +339,113 @@ async fn handle_request(
   }
 
   let pact_request = hyper_request_to_pact_request(req).await?;
+  let (pact_request, request_provider_states) = extract_provider_states(pact_request);
   info!("Received request {}", pact_request);
   if pact_request.has_text_body() {
     debug!("     body: '{}'", pact_request.body.str_value());
   }
 
+  let pact_ref = pact.clone();
   let pact = {
     let inner = pact.lock().unwrap();
     inner.as_v4_pact().unwrap()
   };
-  let match_result = match_request(&pact_request, &pact).await;
+  let provider_states = if request_provider_states.is_empty() {
+    mock_server.lock().unwrap().provider_states()
+  } else {
+    request_provider_states
+  };
+  if !provider_states.is_empty() {
+    debug!("Matching request using provider states {:?}", provider_states);
+  }
+  let (match_result, key) = match_interaction(&pact_request, &provider_states, &pact).await;
+  let (upstream_url, stub_mode) = {
+    let ms = mock_server.lock().unwrap();
+    (ms.config.upstream_url.clone(), ms.config.stub_mode)
+  };
+  let (match_result, key) = match upstream_url {
+    Some(upstream_url) if !match_result.matched() => {
+      record_request(&pact_request, &upstream_url, &pact_ref, &mock_server).await
+        .unwrap_or((match_result, key))
+    }
+    _ => (match_result, key)
+  };
+
+  // Stub servers can run for a long time, and never report the match results
+  if !stub_mode {
+    matches.lock().unwrap().push((match_result.clone(), key.clone()));
+  }
 
-  matches.lock().unwrap().push(match_result.clone());
+  let interaction = key.and_then(|key| pact.interactions.iter()
+    .find(|interaction| interaction.unique_key() == key)
+    .and_then(|interaction| interaction.as_v4_http()));
+  let faults = interaction.as_ref().and_then(|interaction| {
+    faults::faults_for_interaction(interaction, &mock_server.lock().unwrap().config)
+  });
 
-  match_result_to_hyper_response(&pact_request, match_result, mock_server).await
+  let result = match_result.match_key();
+  let response = match_result_to_hyper_response(&pact_request, match_result, faults, mock_server.clone()).await;
+  mock_server.lock().unwrap().add_journal_entry(JournalEntry {
+    timestamp,
+    request: pact_request,
+    result,
+    interaction: interaction.map(|interaction| interaction.description),
+    response: response.as_ref().ok().map(|(_, sent)| sent.clone())
+  });
+  response.map(|(response, _)| response)
 }
 
-// TODO: Should instead use some form of X-Pact headers
-fn handle_mock_request_error(result: Result<Response<Body>, InteractionError>) -> Result<Response<Body>, Error> {
-    match result {
-        Ok(response) => Ok(response),
-        Err(error) => {
-            let response = match error {
-                InteractionError::RequestHeaderEncodingError => Response::builder()
-                    .status(400)
-                    .body(Body::from("Found an invalid header encoding")),
-                InteractionError::RequestBodyError => Response::builder()
-                    .status(500)
-                    .body(Body::from("Could not process request body")),
-                InteractionError::ResponseBodyError => Response::builder()
-                    .status(500)
-                    .body(Body::from("Could not process response body")),
-                InteractionError::ResponseHeaderEncodingError => Response::builder()
-                    .status(500)
-                    .body(Body::from("Could not set response header"))
-            };
-            Ok(response.unwrap())
-        }
+/// Forwards the request to the upstream server, and records the request and response as a new
+/// interaction in both the Pact used for matching and the Pact for the mock server
+async fn record_request(
+  request: &HttpRequest,
+  upstream_url: &str,
+  pact: &Arc<Mutex<dyn Pact + Send + Sync>>,
+  mock_server: &Arc<Mutex<MockServer>>
+) -> Option<KeyedMatchResult> {
+  match proxy::forward_request(upstream_url, request).await {
+    Ok(response) => {
+      let ms_pact = mock_server.lock().unwrap().pact.clone();
+      let mut ms_pact = ms_pact.lock().unwrap();
+      let descriptions = ms_pact.interactions().iter()
+        .map(|i| i.description())
+        .collect::<Vec<_>>();
+      let interaction = proxy::recorded_interaction(request, &response, &descriptions);
+      info!("Recording new interaction '{}'", interaction.description);
+      if let Err(err) = ms_pact.add_interaction(&interaction)
+        .and_then(|_| pact.lock().unwrap().add_interaction(&interaction)) {
+        error!("Failed to record the interaction - {}", err);
+      }
+      let key = interaction.unique_key();
+      Some((MatchResult::RequestMatch(interaction.request, interaction.response), Some(key)))
     }
+    Err(err) => {
+      error!("Failed to forward request to the upstream server - {}", err);
+      None
+    }
+  }
+}
+
+// TODO: Should instead use some form of X-Pact headers
+fn handle_mock_request_error(result: Result<Response<Body>, InteractionError>) -> Result<Response<Body>, InteractionError> {
+    let response = match result {
+        Ok(response) => return Ok(response),
+        // Returning the error from the service closes the connection without sending a response
+        Err(InteractionError::ConnectionClosed) => return Err(InteractionError::ConnectionClosed),
+        Err(InteractionError::RequestHeaderEncodingError) => Response::builder()
+            .status(400)
+            .body(Body::from("Found an invalid header encoding")),
+        Err(InteractionError::RequestBodyError) => Response::builder()
+            .status(500)
+            .body(Body::from("Could not process request body")),
+        Err(InteractionError::ResponseBodyError) => Response::builder()
+            .status(500)
+            .body(Body::from("Could not process response body")),
+        Err(InteractionError::ResponseHeaderEncodingError) => Response::builder()
+            .status(500)
+            .body(Body::from("Could not set response header"))
+    };
+    Ok(response.unwrap())
 }
 
 // Create and bind the server, but do not start it.
@@ -296,7 +456,7 @@ pub(crate) async fn create_and_bind(
   pact: Arc<Mutex<dyn Pact + Send + Sync>>,
   addr: SocketAddr,
   shutdown: impl std::future::Future<Output = ()>,
-  matches: Arc<Mutex<Vec<MatchResult>>>,
+  matches: Arc<Mutex<Vec<KeyedMatchResult>>>,
   mock_server: Arc<Mutex<MockServer>>,
   mock_server_id: &String
 ) -> Result<(impl std::future::Future<Output = ()>, SocketAddr), hyper::Error> {
@@ -362,7 +522,7 @@ pub(crate) async fn create_and_bind_tls(
   pact: Arc<Mutex<dyn Pact + Send + Sync>>,
   addr: SocketAddr,
   shutdown: impl std::future::Future<Output = ()>,
-  matches: Arc<Mutex<Vec<MatchResult>>>,
+  matches: Arc<Mutex<Vec<KeyedMatchResult>>>,
   tls_cfg: ServerConfig,
   mock_server: Arc<Mutex<MockServer>>
 ) -> Result<(impl std::future::Future<Output = ()>, SocketAddr), io::Error> {
@@ -468,4 +628,14 @@ mod tests {
       "content-type".to_string() => vec!["text/plain".to_string()]
     })));
   }
+  #[test]
+  fn handle_hyper_headers_does_not_split_set_cookie_headers() {
+    let mut headers = HeaderMap::new();
+    headers.append(SET_COOKIE, "session=1234; Expires=Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap());
+    headers.append(SET_COOKIE, "theme=dark".parse().unwrap());
+    let result = extract_headers(&headers);
+    expect!(result).to(be_ok().value(Some(hashmap! {
+      "set-cookie".to_string() => vec!["session=1234; Expires=Wed, 21 Oct 2015 07:28:00 GMT".to_string(), "theme=dark".to_string()]
+    })));
+  }
 }
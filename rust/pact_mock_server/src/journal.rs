@@ -0,0 +1,79 @@
+//!
+//! The request journal records every request received by a mock server, in the order they were
+//! received, along with the interaction that matched and the response that was sent.
+//!
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use pact_models::v4::http_parts::{HttpRequest, HttpResponse};
+use serde_json::{json, Value};
+
+/// Entry in the request journal of a mock server
+#[derive(Debug, Clone, PartialEq)]
+pub struct JournalEntry {
+  /// When the request was received
+  pub timestamp: DateTime<Utc>,
+  /// Request that was received
+  pub request: HttpRequest,
+  /// Result of matching the request (the match key of the match result, i.e. `Request-Matched`)
+  pub result: String,
+  /// Description of the interaction that matched the request, if any
+  pub interaction: Option<String>,
+  /// Response that was sent. This will be `None` if no response was sent (i.e. the connection was
+  /// closed by fault injection).
+  pub response: Option<HttpResponse>
+}
+
+impl JournalEntry {
+  /// Converts this journal entry to a JSON value
+  pub fn to_json(&self) -> Value {
+    json!({
+      "timestamp": self.timestamp.to_rfc3339_opts(SecondsFormat::Millis, true),
+      "request": self.request.to_json(),
+      "result": self.result,
+      "interaction": self.interaction,
+      "response": self.response.as_ref().map(|response| response.to_json())
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::{TimeZone, Utc};
+  use expectest::prelude::*;
+  use pact_models::v4::http_parts::{HttpRequest, HttpResponse};
+  use serde_json::json;
+
+  use super::JournalEntry;
+
+  #[test]
+  fn journal_entry_to_json() {
+    let entry = JournalEntry {
+      timestamp: Utc.ymd(2022, 5, 1).and_hms_milli(10, 20, 30, 400),
+      request: HttpRequest { path: "/orders".to_string(), .. HttpRequest::default() },
+      result: "Request-Matched".to_string(),
+      interaction: Some("a request for orders".to_string()),
+      response: Some(HttpResponse::default())
+    };
+    expect!(entry.to_json()).to(be_equal_to(json!({
+      "timestamp": "2022-05-01T10:20:30.400Z",
+      "request": { "method": "GET", "path": "/orders" },
+      "result": "Request-Matched",
+      "interaction": "a request for orders",
+      "response": { "status": 200 }
+    })));
+
+    let entry = JournalEntry {
+      result: "Unexpected-Request".to_string(),
+      interaction: None,
+      response: None,
+      .. entry
+    };
+    expect!(entry.to_json()).to(be_equal_to(json!({
+      "timestamp": "2022-05-01T10:20:30.400Z",
+      "request": { "method": "GET", "path": "/orders" },
+      "result": "Unexpected-Request",
+      "interaction": null,
+      "response": null
+    })));
+  }
+}
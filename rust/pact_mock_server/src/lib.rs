@@ -33,10 +33,14 @@ use uuid::Uuid;
 use crate::mock_server::MockServerConfig;
 use crate::server_manager::ServerManager;
 
+pub mod expectations;
+pub mod faults;
+pub mod journal;
 pub mod matching;
 pub mod mock_server;
 pub mod server_manager;
 mod hyper_server;
+mod proxy;
 pub mod tls;
 mod utils;
 
@@ -354,6 +358,37 @@ pub fn mock_server_mismatches(mock_server_port: i32) -> Option<String> {
     })
 }
 
+/// Gets the journal of all the requests received by a mock server in JSON format. The port number
+/// of the mock server is passed in, and the journal entries are returned as a JSON array in the
+/// order the requests were received.
+///
+/// If there is no mock server with the provided port number, `None` is returned.
+///
+/// Mock servers provided by plugins do not record a journal, so a JSON value with an error
+/// attribute will be returned.
+pub fn mock_server_journal(mock_server_port: i32) -> Option<String> {
+  MANAGER.lock().unwrap()
+    .get_or_insert_with(ServerManager::new)
+    .find_mock_server_by_port(mock_server_port as u16, &|_, mock_server| {
+      match mock_server {
+        Either::Left(mock_server) => {
+          mock_server.journal_to_json().to_string()
+        }
+        Either::Right(_) => json!({ "error": "Plugin mock servers do not record a request journal" }).to_string()
+      }
+    })
+}
+
+/// Clears the journal of the requests received by a mock server, given the port number of the
+/// mock server. Returns false if there is no mock server with the provided port number, or the
+/// mock server is provided by a plugin.
+pub fn clear_mock_server_journal(mock_server_port: i32) -> bool {
+  MANAGER.lock().unwrap()
+    .get_or_insert_with(ServerManager::new)
+    .find_mock_server_by_port_mut(mock_server_port as u16, &|mock_server| mock_server.clear_journal())
+    .is_some()
+}
+
 /// Write Pact File Errors
 pub enum WritePactFileErr {
   /// IO Error occurred
@@ -15,9 +15,22 @@ use pact_models::PactSpecification;
 use pact_models::prelude::Pact;
 use pact_models::prelude::v4::SynchronousHttp;
 use pact_models::v4::http_parts::{HttpRequest, HttpResponse};
+use pact_models::v4::interaction::V4Interaction;
 use pact_models::v4::V4InteractionType;
 use pact_models::v4::pact::V4Pact;
 
+use crate::expectations::ExpectedCalls;
+
+/// Request header that can be used to select the provider states to match a request against. The
+/// header is repeated once for each state, and the values are not split on commas, as provider
+/// state names can contain them.
+pub const PROVIDER_STATE_HEADER: &str = "X-Pact-Provider-State";
+
+/// Match result, along with the unique key of the interaction that matched the request (if any).
+/// The key is used to count the calls to each interaction, as different interactions can have the
+/// same request.
+pub(crate) type KeyedMatchResult = (MatchResult, Option<String>);
+
 /// Enum to define a match result
 #[derive(Debug, Clone, PartialEq)]
 pub enum MatchResult {
@@ -28,7 +41,13 @@ pub enum MatchResult {
   /// Match result where the request was not expected
   RequestNotFound(HttpRequest),
   /// Match result where an expected request was not received
-  MissingRequest(HttpRequest)
+  MissingRequest(HttpRequest),
+  /// Match result where the request for an interaction was not received the expected number of
+  /// times (the request, expected calls and actual number of calls)
+  IncorrectCallCount(HttpRequest, ExpectedCalls, usize),
+  /// Match result where the request was received out of order when strict ordering is enabled
+  /// (the request, and the request for the later interaction that was received before it)
+  RequestOutOfOrder(HttpRequest, HttpRequest)
 }
 
 impl MatchResult {
@@ -38,7 +57,9 @@ impl MatchResult {
             &MatchResult::RequestMatch(_, _) => "Request-Matched",
             &MatchResult::RequestMismatch(_, _) => "Request-Mismatch",
             &MatchResult::RequestNotFound(_) => "Unexpected-Request",
-            &MatchResult::MissingRequest(_) => "Missing-Request"
+            &MatchResult::MissingRequest(_) => "Missing-Request",
+            &MatchResult::IncorrectCallCount(_, _, _) => "Incorrect-Call-Count",
+            &MatchResult::RequestOutOfOrder(_, _) => "Request-Out-Of-Order"
         }.to_string()
     }
 
@@ -74,6 +95,21 @@ impl MatchResult {
                 "method": request.method,
                 "path": request.path,
                 "request": request.as_v3_request().to_json(&PactSpecification::V3)
+            }),
+            &MatchResult::IncorrectCallCount(ref request, ref expected, calls) => json!({
+                "type": "incorrect-call-count",
+                "method": request.method,
+                "path": request.path,
+                "expected": expected.to_json(),
+                "actual": calls,
+                "request": request.as_v3_request().to_json(&PactSpecification::V3)
+            }),
+            &MatchResult::RequestOutOfOrder(ref request, ref after) => json!({
+                "type": "request-out-of-order",
+                "method": request.method,
+                "path": request.path,
+                "request": request.as_v3_request().to_json(&PactSpecification::V3),
+                "receivedAfter": after.as_v3_request().to_json(&PactSpecification::V3)
             })
         }
     }
@@ -97,11 +133,23 @@ impl Display for MatchResult {
       },
       MatchResult::MissingRequest(request) => {
         write!(f, "Request was not received - {}", request)
+      },
+      MatchResult::IncorrectCallCount(request, expected, calls) => {
+        write!(f, "Request was received {} time(s), expected {} - {}", calls, expected, request)
+      },
+      MatchResult::RequestOutOfOrder(request, after) => {
+        write!(f, "Request was received out of order - {} was received after {}", request, after)
       }
     }
   }
 }
 
+fn interaction_has_provider_states(interaction: &dyn Interaction, provider_states: &[String]) -> bool {
+  let interaction_states = interaction.provider_states();
+  interaction_states.is_empty() || provider_states.iter()
+    .all(|state| interaction_states.iter().any(|ps| &ps.name == state))
+}
+
 fn mismatches_to_json(request: &HttpRequest, mismatches: &Vec<Mismatch>) -> serde_json::Value {
     json!({
         "type" : "request-mismatch",
@@ -118,7 +166,34 @@ pub async fn match_request(
   req: &HttpRequest,
   pact: &V4Pact,
 ) -> MatchResult {
-  let interactions = pact.filter_interactions(V4InteractionType::Synchronous_HTTP);
+  match_request_with_provider_states(req, &[], pact).await
+}
+
+///
+/// Matches a request against the list of interactions that are valid for the active provider
+/// states. If there are active provider states, only interactions that have all of those states
+/// (or interactions with no provider states) are considered. This allows interactions with the
+/// same request but different provider states to be selected.
+///
+pub async fn match_request_with_provider_states(
+  req: &HttpRequest,
+  provider_states: &[String],
+  pact: &V4Pact,
+) -> MatchResult {
+  match_interaction(req, provider_states, pact).await.0
+}
+
+/// Matches a request against the interactions that are valid for the active provider states,
+/// returning the match result and the unique key of the interaction that matched the request.
+pub(crate) async fn match_interaction(
+  req: &HttpRequest,
+  provider_states: &[String],
+  pact: &V4Pact,
+) -> KeyedMatchResult {
+  let interactions = pact.filter_interactions(V4InteractionType::Synchronous_HTTP)
+    .into_iter()
+    .filter(|i| interaction_has_provider_states(i.as_ref(), provider_states))
+    .collect::<Vec<_>>();
   let match_results = futures::stream::iter(interactions)
     .filter(|i| future::ready(i.is_request_response()))
     .then(|i| async move {
@@ -133,13 +208,14 @@ pub async fn match_request(
     Some((interaction, result)) => {
       let request_response_interaction = interaction.as_v4_http().unwrap();
       if result.all_matched() {
-        MatchResult::RequestMatch(request_response_interaction.request, request_response_interaction.response)
+        (MatchResult::RequestMatch(request_response_interaction.request, request_response_interaction.response),
+          Some(interaction.unique_key()))
       } else if result.method_or_path_mismatch() {
-        MatchResult::RequestNotFound(req.clone())
+        (MatchResult::RequestNotFound(req.clone()), None)
       } else {
-        MatchResult::RequestMismatch(request_response_interaction.request, result.mismatches())
+        (MatchResult::RequestMismatch(request_response_interaction.request, result.mismatches()), None)
       }
     },
-    None => MatchResult::RequestNotFound(req.clone())
+    None => (MatchResult::RequestNotFound(req.clone()), None)
   }
 }
@@ -4,7 +4,7 @@
 //!
 
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::ffi::CString;
 use std::ops::{Deref, DerefMut};
 use std::path::PathBuf;
@@ -22,8 +22,11 @@ use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use tracing::{debug, info, trace, warn};
 
+use crate::expectations::{self, ExpectedCalls};
+use crate::faults::FaultConfig;
 use crate::hyper_server;
-use crate::matching::MatchResult;
+use crate::journal::JournalEntry;
+use crate::matching::{KeyedMatchResult, MatchResult};
 use crate::utils::json_to_bool;
 
 /// Mock server configuration
@@ -34,7 +37,28 @@ pub struct MockServerConfig {
   /// Pact specification to use
   pub pact_specification: PactSpecification,
   /// Configuration required for the transport used
-  pub transport_config: HashMap<String, Value>
+  pub transport_config: HashMap<String, Value>,
+  /// Upstream server to forward any requests that do not match an interaction to. The request
+  /// and response will be recorded as a new interaction in the Pact (record mode).
+  pub upstream_url: Option<String>,
+  /// If the mock server is running as a stub server. In stub mode, mismatches and missing
+  /// requests are not treated as failures, and the match results are not recorded.
+  pub stub_mode: bool,
+  /// HTTP status to respond with for requests that do not match any interaction (defaults to 500)
+  pub unmatched_status: Option<u16>,
+  /// Latency and faults to inject into the responses, keyed by the interaction description
+  /// (`*` can be used to apply the faults to all interactions)
+  pub faults: HashMap<String, FaultConfig>,
+  /// Expected number of calls for the interactions, keyed by the interaction description (`*` can
+  /// be used to apply to all interactions). Interactions without any expected calls must be
+  /// called at least once.
+  pub expected_calls: HashMap<String, ExpectedCalls>,
+  /// If the interactions must be called in the order they are defined in the Pact
+  pub strict_ordering: bool,
+  /// Maximum number of entries to keep in the request journal, with the oldest entries being
+  /// dropped first. If not set, the journal is not limited, except in stub mode where a journal is
+  /// only recorded if this is set.
+  pub journal_size: Option<usize>
 }
 
 impl MockServerConfig {
@@ -48,6 +72,29 @@ impl MockServerConfig {
           config.cors_preflight = json_to_bool(v).unwrap_or_default();
         } else if k == "pactSpecification" {
           config.pact_specification = PactSpecification::from(json_to_string(v));
+        } else if k == "upstreamUrl" {
+          config.upstream_url = Some(json_to_string(v));
+        } else if k == "stubMode" {
+          config.stub_mode = json_to_bool(v).unwrap_or_default();
+        } else if k == "unmatchedStatus" {
+          config.unmatched_status = v.as_u64().and_then(|status| u16::try_from(status).ok());
+        } else if k == "faults" {
+          if let Value::Object(faults) = v {
+            config.faults = faults.iter()
+              .map(|(description, faults)| (description.clone(), FaultConfig::from_json(faults)))
+              .collect();
+          }
+        } else if k == "expectedCalls" {
+          if let Value::Object(expected_calls) = v {
+            config.expected_calls = expected_calls.iter()
+              .filter_map(|(description, calls)| ExpectedCalls::from_json(calls)
+                .map(|calls| (description.clone(), calls)))
+              .collect();
+          }
+        } else if k == "strictOrdering" {
+          config.strict_ordering = json_to_bool(v).unwrap_or_default();
+        } else if k == "journalSize" {
+          config.journal_size = v.as_u64().map(|size| size as usize);
         } else {
           config.transport_config.insert(k.clone(), v.clone());
         }
@@ -106,7 +153,7 @@ pub struct MockServer {
   /// Pact that this mock server is based on
   pub pact: Arc<Mutex<dyn Pact + Send + Sync>>,
   /// Receiver of match results
-  matches: Arc<Mutex<Vec<MatchResult>>>,
+  matches: Arc<Mutex<Vec<KeyedMatchResult>>>,
   /// Shutdown signal
   shutdown_tx: RefCell<Option<futures::channel::oneshot::Sender<()>>>,
   /// Mock server config
@@ -114,7 +161,11 @@ pub struct MockServer {
   /// Metrics collected by the mock server
   pub metrics: MockServerMetrics,
   /// Pact spec version to use
-  pub spec_version: PactSpecification
+  pub spec_version: PactSpecification,
+  /// Provider states that are active for matching requests
+  provider_states: Vec<String>,
+  /// Journal of the requests received by the mock server
+  journal: VecDeque<JournalEntry>
 }
 
 impl MockServer {
@@ -139,7 +190,9 @@ impl MockServer {
       shutdown_tx: RefCell::new(Some(shutdown_tx)),
       config: config.clone(),
       metrics: MockServerMetrics::default(),
-      spec_version: pact_specification(config.pact_specification, pact.specification_version())
+      spec_version: pact_specification(config.pact_specification, pact.specification_version()),
+      provider_states: vec![],
+      journal: VecDeque::new()
     }));
 
     let (future, socket_addr) = hyper_server::create_and_bind(
@@ -187,7 +240,9 @@ impl MockServer {
       shutdown_tx: RefCell::new(Some(shutdown_tx)),
       config: config.clone(),
       metrics: MockServerMetrics::default(),
-      spec_version: pact_specification(config.pact_specification, pact.specification_version())
+      spec_version: pact_specification(config.pact_specification, pact.specification_version()),
+      provider_states: vec![],
+      journal: VecDeque::new()
     }));
 
     let (future, socket_addr) = hyper_server::create_and_bind_tls(
@@ -242,13 +297,13 @@ impl MockServer {
 
     /// Converts this mock server to a `Value` struct
     pub fn to_json(&self) -> serde_json::Value {
-      let pact = self.pact.lock().unwrap();
+      let provider = self.pact.lock().unwrap().provider().name.clone();
       json!({
         "id" : self.id.clone(),
         "port" : self.port.unwrap_or_default() as u64,
         "address" : self.address.clone().unwrap_or_default(),
         "scheme" : self.scheme.to_string(),
-        "provider" : pact.provider().name.clone(),
+        "provider" : provider,
         "status" : if self.mismatches().is_empty() { "ok" } else { "error" },
         "metrics" : self.metrics
       })
@@ -256,11 +311,19 @@ impl MockServer {
 
     /// Returns all collected matches
     pub fn matches(&self) -> Vec<MatchResult> {
-        self.matches.lock().unwrap().clone()
+        self.matches.lock().unwrap().iter().map(|(m, _)| m.clone()).collect()
     }
 
-    /// Returns all the mismatches that have occurred with this mock server
+    /// Returns all the mismatches that have occurred with this mock server. This includes any
+    /// interactions that were not called the expected number of times, and any requests received
+    /// out of order if strict ordering is enabled. Mock servers running in stub mode will not
+    /// return any mismatches.
     pub fn mismatches(&self) -> Vec<MatchResult> {
+      if self.config.stub_mode {
+        return vec![];
+      }
+
+      let keyed_matches = self.matches.lock().unwrap().clone();
       let matches = self.matches();
       let mismatches = matches.iter()
         .filter(|m| !m.matched() && !m.cors_preflight())
@@ -269,18 +332,37 @@ impl MockServer {
         match m {
           MatchResult::RequestMatch(request, _) => Some(request),
           MatchResult::RequestMismatch(request, _) => Some(request),
-          MatchResult::RequestNotFound(_) => None,
-          MatchResult::MissingRequest(_) => None
+          _ => None
         }
       }).filter(|o| o.is_some()).map(|o| o.unwrap().clone()).collect();
 
       let pact = self.pact.lock().unwrap();
-      let interactions = pact.interactions();
+      let interactions = pact.interactions().iter()
+        .map(|i| i.as_v4_http().unwrap())
+        .collect::<Vec<_>>();
       let missing = interactions.iter()
-        .map(|i| i.as_v4_http().unwrap().request)
-        .filter(|req| !requests.contains(req))
-        .map(|req| MatchResult::MissingRequest(req.clone()));
-      mismatches.chain(missing).collect()
+        .filter_map(|interaction| {
+          match expectations::expected_calls_for_interaction(interaction, &self.config) {
+            Some(expected) => {
+              let calls = expectations::call_count(interaction, &keyed_matches);
+              if expected.is_satisfied_by(calls) {
+                None
+              } else {
+                Some(MatchResult::IncorrectCallCount(interaction.request.clone(), expected, calls))
+              }
+            }
+            None if !requests.contains(&interaction.request) =>
+              Some(MatchResult::MissingRequest(interaction.request.clone())),
+            None => None
+          }
+        })
+        .collect::<Vec<_>>();
+      let out_of_order = if self.config.strict_ordering {
+        expectations::out_of_order_requests(&interactions, &keyed_matches)
+      } else {
+        vec![]
+      };
+      mismatches.chain(missing).chain(out_of_order).collect()
     }
 
   /// Mock server writes its pact out to the provided directory
@@ -323,6 +405,57 @@ impl MockServer {
     }
   }
 
+  /// Sets the active provider states. Once set, requests will only be matched against interactions
+  /// that have all of these provider states (or interactions without any provider states). This
+  /// can be used to select between interactions that have the same request but different
+  /// provider states. Provider states can also be set for a single request with the
+  /// `X-Pact-Provider-State` header.
+  pub fn set_provider_states(&mut self, provider_states: &[&str]) {
+    debug!("Mock server {} - setting active provider states to {:?}", self.id, provider_states);
+    self.provider_states = provider_states.iter().map(|state| state.to_string()).collect();
+  }
+
+  /// Clears the active provider states, so that requests are matched against all interactions
+  pub fn clear_provider_states(&mut self) {
+    self.provider_states.clear();
+  }
+
+  /// Returns the active provider states
+  pub fn provider_states(&self) -> Vec<String> {
+    self.provider_states.clone()
+  }
+
+  /// Returns the journal of the requests received by the mock server, in the order they were
+  /// received. If a journal size is configured, only the most recent requests are returned.
+  pub fn journal(&self) -> Vec<JournalEntry> {
+    self.journal.iter().cloned().collect()
+  }
+
+  /// Returns the journal of the requests received by the mock server as a JSON array
+  pub fn journal_to_json(&self) -> Value {
+    Value::Array(self.journal.iter().map(|entry| entry.to_json()).collect())
+  }
+
+  /// Clears the journal of the requests received by the mock server
+  pub fn clear_journal(&mut self) {
+    self.journal.clear();
+  }
+
+  pub(crate) fn add_journal_entry(&mut self, entry: JournalEntry) {
+    match self.config.journal_size {
+      Some(size) => {
+        while !self.journal.is_empty() && self.journal.len() >= size {
+          self.journal.pop_front();
+        }
+        if size > 0 {
+          self.journal.push_back(entry);
+        }
+      }
+      None if self.config.stub_mode => (),
+      None => self.journal.push_back(entry)
+    }
+  }
+
     /// Returns the URL of the mock server
     pub fn url(&self) -> String {
       let addr = self.address.clone().unwrap_or_else(|| "127.0.0.1".to_string());
@@ -356,7 +489,9 @@ impl Clone for MockServer {
       shutdown_tx: RefCell::new(None),
       config: self.config.clone(),
       metrics: self.metrics.clone(),
-      spec_version: self.spec_version
+      spec_version: self.spec_version,
+      provider_states: self.provider_states.clone(),
+      journal: self.journal.clone()
     }
   }
 }
@@ -374,18 +509,29 @@ impl Default for MockServer {
       shutdown_tx: RefCell::new(None),
       config: Default::default(),
       metrics: Default::default(),
-      spec_version: Default::default()
+      spec_version: Default::default(),
+      provider_states: vec![],
+      journal: VecDeque::new()
     }
   }
 }
 
 #[cfg(test)]
 mod tests {
+  use std::time::Duration;
+
   use expectest::prelude::*;
   use maplit::hashmap;
   use pact_models::PactSpecification;
   use serde_json::{json, Value};
 
+  use chrono::Utc;
+  use pact_models::v4::http_parts::HttpRequest;
+
+  use crate::expectations::ExpectedCalls;
+  use crate::faults::{FaultConfig, ResponseDelay};
+  use crate::journal::JournalEntry;
+  use crate::mock_server::MockServer;
   use crate::MockServerConfig;
 
   #[test]
@@ -407,7 +553,115 @@ mod tests {
       transport_config: hashmap! {
         "tlsKey".to_string() => json!("key"),
         "tlsCertificate".to_string() => json!("cert")
+      },
+      upstream_url: None,
+      stub_mode: false,
+      unmatched_status: None,
+      faults: hashmap!{},
+      expected_calls: hashmap!{},
+      strict_ordering: false,
+      journal_size: None
+    }));
+
+    expect!(MockServerConfig::from_json(&json!({
+      "upstreamUrl": "http://localhost:8080"
+    }))).to(be_equal_to(MockServerConfig {
+      upstream_url: Some("http://localhost:8080".to_string()),
+      .. MockServerConfig::default()
+    }));
+
+    expect!(MockServerConfig::from_json(&json!({
+      "stubMode": true,
+      "unmatchedStatus": 404
+    }))).to(be_equal_to(MockServerConfig {
+      stub_mode: true,
+      unmatched_status: Some(404),
+      .. MockServerConfig::default()
+    }));
+
+    expect!(MockServerConfig::from_json(&json!({
+      "faults": {
+        "*": { "delay": 100 },
+        "a request": { "closeConnection": true }
       }
+    }))).to(be_equal_to(MockServerConfig {
+      faults: hashmap! {
+        "*".to_string() => FaultConfig {
+          delay: Some(ResponseDelay::Fixed(Duration::from_millis(100))),
+          .. FaultConfig::default()
+        },
+        "a request".to_string() => FaultConfig { close_connection: true, .. FaultConfig::default() }
+      },
+      .. MockServerConfig::default()
+    }));
+
+    expect!(MockServerConfig::from_json(&json!({
+      "expectedCalls": {
+        "*": 1,
+        "a request": { "atLeast": 2 },
+        "invalid": "1"
+      },
+      "strictOrdering": true
+    }))).to(be_equal_to(MockServerConfig {
+      expected_calls: hashmap! {
+        "*".to_string() => ExpectedCalls::Exactly(1),
+        "a request".to_string() => ExpectedCalls::AtLeast(2)
+      },
+      strict_ordering: true,
+      .. MockServerConfig::default()
     }));
+
+    expect!(MockServerConfig::from_json(&json!({
+      "journalSize": 100
+    }))).to(be_equal_to(MockServerConfig {
+      journal_size: Some(100),
+      .. MockServerConfig::default()
+    }));
+  }
+
+  #[test]
+  fn add_journal_entry_keeps_the_configured_number_of_entries() {
+    let entry = |path: &str| JournalEntry {
+      timestamp: Utc::now(),
+      request: HttpRequest { path: path.to_string(), .. HttpRequest::default() },
+      result: "Request-Matched".to_string(),
+      interaction: None,
+      response: None
+    };
+    let paths = |mock_server: &MockServer| mock_server.journal().iter()
+      .map(|entry| entry.request.path.clone())
+      .collect::<Vec<_>>();
+
+    let mut mock_server = MockServer::default();
+    mock_server.add_journal_entry(entry("/one"));
+    mock_server.add_journal_entry(entry("/two"));
+    mock_server.add_journal_entry(entry("/three"));
+    expect!(paths(&mock_server)).to(be_equal_to(vec!["/one", "/two", "/three"]));
+    mock_server.clear_journal();
+    expect!(paths(&mock_server).is_empty()).to(be_true());
+
+    let mut mock_server = MockServer {
+      config: MockServerConfig { journal_size: Some(2), .. MockServerConfig::default() },
+      .. MockServer::default()
+    };
+    mock_server.add_journal_entry(entry("/one"));
+    mock_server.add_journal_entry(entry("/two"));
+    mock_server.add_journal_entry(entry("/three"));
+    expect!(paths(&mock_server)).to(be_equal_to(vec!["/two", "/three"]));
+
+    let mut mock_server = MockServer {
+      config: MockServerConfig { stub_mode: true, .. MockServerConfig::default() },
+      .. MockServer::default()
+    };
+    mock_server.add_journal_entry(entry("/one"));
+    expect!(paths(&mock_server).is_empty()).to(be_true());
+
+    let mut mock_server = MockServer {
+      config: MockServerConfig { stub_mode: true, journal_size: Some(1), .. MockServerConfig::default() },
+      .. MockServer::default()
+    };
+    mock_server.add_journal_entry(entry("/one"));
+    mock_server.add_journal_entry(entry("/two"));
+    expect!(paths(&mock_server)).to(be_equal_to(vec!["/two"]));
   }
 }
@@ -0,0 +1,178 @@
+//!
+//! Support for forwarding requests that do not match any interaction to an upstream server, and
+//! recording the request and response as a new interaction (record mode).
+//!
+
+use std::collections::HashMap;
+
+use anyhow::anyhow;
+use hyper::{Body, Client, Method, Request};
+use hyper::http::header::{HeaderName, HeaderValue};
+use pact_models::bodies::OptionalBody;
+use pact_models::query_strings::build_query_string;
+use pact_models::v4::http_parts::{HttpRequest, HttpResponse};
+use pact_models::v4::synch_http::SynchronousHttp;
+use tracing::{debug, info};
+
+/// Headers that only apply to a single connection, and are not forwarded or recorded
+const HOP_BY_HOP_HEADERS: [&str; 10] = [
+  "connection", "keep-alive", "proxy-authenticate", "proxy-authorization", "te", "trailers",
+  "transfer-encoding", "upgrade", "host", "content-length"
+];
+
+fn is_hop_by_hop_header(name: &str) -> bool {
+  HOP_BY_HOP_HEADERS.iter().any(|header| name.eq_ignore_ascii_case(header))
+}
+
+/// Builds the URL to forward the request to, using the path and query of the request
+pub(crate) fn upstream_request_url(upstream_url: &str, request: &HttpRequest) -> String {
+  let mut url = upstream_url.trim_end_matches('/').to_string();
+  if !request.path.starts_with('/') {
+    url.push('/');
+  }
+  url.push_str(&request.path);
+  if let Some(query) = &request.query {
+    url.push('?');
+    url.push_str(&build_query_string(query.clone()));
+  }
+  url
+}
+
+/// Removes any hop-by-hop headers from the headers, returning None if there are none left
+pub(crate) fn recordable_headers(headers: &Option<HashMap<String, Vec<String>>>) -> Option<HashMap<String, Vec<String>>> {
+  headers.as_ref()
+    .map(|headers| headers.iter()
+      .filter(|(name, _)| !is_hop_by_hop_header(name))
+      .map(|(name, values)| (name.clone(), values.clone()))
+      .collect::<HashMap<_, _>>())
+    .filter(|headers| !headers.is_empty())
+}
+
+/// Forwards the request to the upstream server, returning the response from it
+pub(crate) async fn forward_request(upstream_url: &str, request: &HttpRequest) -> anyhow::Result<HttpResponse> {
+  let url = upstream_request_url(upstream_url, request);
+  info!("Forwarding request to upstream server {} {}", request.method, url);
+
+  let mut builder = Request::builder()
+    .method(Method::from_bytes(request.method.to_uppercase().as_bytes())?)
+    .uri(url.as_str());
+  if let Some(headers) = recordable_headers(&request.headers) {
+    let request_headers = builder.headers_mut()
+      .ok_or_else(|| anyhow!("Failed to build request to upstream server"))?;
+    for (name, values) in headers {
+      for value in values {
+        request_headers.append(HeaderName::from_bytes(name.as_bytes())?, HeaderValue::from_str(&value)?);
+      }
+    }
+  }
+  let body = match &request.body {
+    OptionalBody::Present(bytes, _, _) => Body::from(bytes.clone()),
+    _ => Body::empty()
+  };
+
+  let connector = hyper_rustls::HttpsConnectorBuilder::new()
+    .with_native_roots()
+    .https_or_http()
+    .enable_http1()
+    .build();
+  let client = Client::builder().build::<_, Body>(connector);
+  let response = client.request(builder.body(body)?).await
+    .map_err(|err| anyhow!("Failed to forward request to upstream server '{}' - {}", url, err))?;
+
+  let status = response.status().as_u16();
+  let mut headers: HashMap<String, Vec<String>> = HashMap::new();
+  for (name, value) in response.headers() {
+    if !is_hop_by_hop_header(name.as_str()) {
+      headers.entry(name.to_string()).or_default().push(value.to_str()?.to_string());
+    }
+  }
+  let body = hyper::body::to_bytes(response.into_body()).await?;
+  debug!("Received response from upstream server with status {}", status);
+
+  let mut response = HttpResponse {
+    status,
+    headers: if headers.is_empty() { None } else { Some(headers) },
+    .. HttpResponse::default()
+  };
+  response.body = if body.is_empty() {
+    OptionalBody::Empty
+  } else {
+    OptionalBody::Present(body, response.content_type(), None)
+  };
+  Ok(response)
+}
+
+/// Creates the interaction to record for the request and response. The description will be the
+/// method and path of the request, with a counter appended if it is not unique.
+pub(crate) fn recorded_interaction(
+  request: &HttpRequest,
+  response: &HttpResponse,
+  existing_descriptions: &[String]
+) -> SynchronousHttp {
+  let base_description = format!("{} {}", request.method.to_uppercase(), request.path);
+  let mut description = base_description.clone();
+  let mut count = 1;
+  while existing_descriptions.contains(&description) {
+    count += 1;
+    description = format!("{} ({})", base_description, count);
+  }
+
+  SynchronousHttp {
+    description,
+    request: HttpRequest {
+      headers: recordable_headers(&request.headers),
+      .. request.clone()
+    },
+    response: response.clone(),
+    .. SynchronousHttp::default()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use maplit::hashmap;
+  use pact_models::v4::http_parts::{HttpRequest, HttpResponse};
+
+  use super::{recordable_headers, recorded_interaction, upstream_request_url};
+
+  #[test]
+  fn upstream_request_url_test() {
+    let request = HttpRequest { path: "/users".to_string(), .. HttpRequest::default() };
+    expect!(upstream_request_url("http://localhost:8080", &request)).to(be_equal_to("http://localhost:8080/users"));
+    expect!(upstream_request_url("http://localhost:8080/api/", &request)).to(be_equal_to("http://localhost:8080/api/users"));
+
+    let request = HttpRequest {
+      path: "/users".to_string(),
+      query: Some(hashmap!{ "name".to_string() => vec!["a b".to_string()] }),
+      .. HttpRequest::default()
+    };
+    expect!(upstream_request_url("http://localhost:8080", &request)).to(be_equal_to("http://localhost:8080/users?name=a+b"));
+  }
+
+  #[test]
+  fn recordable_headers_removes_hop_by_hop_headers() {
+    expect!(recordable_headers(&None)).to(be_none());
+    expect!(recordable_headers(&Some(hashmap!{
+      "Host".to_string() => vec!["localhost".to_string()]
+    }))).to(be_none());
+    expect!(recordable_headers(&Some(hashmap!{
+      "host".to_string() => vec!["localhost".to_string()],
+      "Content-Length".to_string() => vec!["100".to_string()],
+      "accept".to_string() => vec!["application/json".to_string()]
+    }))).to(be_some().value(hashmap!{
+      "accept".to_string() => vec!["application/json".to_string()]
+    }));
+  }
+
+  #[test]
+  fn recorded_interaction_uses_a_unique_description() {
+    let request = HttpRequest { method: "get".to_string(), path: "/users".to_string(), .. HttpRequest::default() };
+    let response = HttpResponse::default();
+    expect!(recorded_interaction(&request, &response, &[]).description).to(be_equal_to("GET /users"));
+    expect!(recorded_interaction(&request, &response, &["GET /users".to_string()]).description)
+      .to(be_equal_to("GET /users (2)"));
+    expect!(recorded_interaction(&request, &response, &["GET /users".to_string(), "GET /users (2)".to_string()]).description)
+      .to(be_equal_to("GET /users (3)"));
+  }
+}
@@ -11,9 +11,12 @@ use pact_models::matchingrules::MatchingRule;
 use pact_models::prelude::v4::{SynchronousHttp, V4Pact};
 use pact_models::v4::http_parts::{HttpRequest, HttpResponse};
 
-use crate::matching::{match_request, MatchResult};
+use crate::expectations::ExpectedCalls;
+use crate::faults::FaultConfig;
+use crate::matching::{match_request, match_request_with_provider_states, MatchResult};
 
 use super::*;
+use pact_models::provider_states::ProviderState;
 use pact_models::v4::interaction::V4Interaction;
 
 #[tokio::test]
@@ -286,3 +289,369 @@ fn basic_mock_server_test() {
   expect!(mismatches).to(be_some().value("[]"));
   expect!(response.unwrap().status()).to(be_equal_to(200));
 }
+
+fn provider_state_pact() -> V4Pact {
+  let request = HttpRequest { path: "/users/100".into(), .. HttpRequest::default() };
+  V4Pact {
+    interactions: vec![
+      SynchronousHttp {
+        description: "a request for a user".into(),
+        provider_states: vec![ProviderState::default("user exists")],
+        request: request.clone(),
+        response: HttpResponse { status: 200, .. HttpResponse::default() },
+        .. SynchronousHttp::default()
+      }.boxed_v4(),
+      SynchronousHttp {
+        description: "a request for a user that does not exist".into(),
+        provider_states: vec![ProviderState::default("no user")],
+        request,
+        response: HttpResponse { status: 404, .. HttpResponse::default() },
+        .. SynchronousHttp::default()
+      }.boxed_v4(),
+      SynchronousHttp {
+        description: "a request for the users".into(),
+        request: HttpRequest { path: "/users".into(), .. HttpRequest::default() },
+        .. SynchronousHttp::default()
+      }.boxed_v4()
+    ],
+    .. V4Pact::default()
+  }
+}
+
+#[tokio::test]
+async fn match_request_with_provider_states_only_considers_interactions_with_those_states() {
+  let pact = provider_state_pact();
+  let request = HttpRequest { path: "/users/100".into(), .. HttpRequest::default() };
+
+  let result = match_request_with_provider_states(&request, &["no user".to_string()], &pact).await;
+  expect!(result).to(be_equal_to(MatchResult::RequestMatch(request.clone(),
+    HttpResponse { status: 404, .. HttpResponse::default() })));
+
+  let result = match_request_with_provider_states(&request, &["user exists".to_string()], &pact).await;
+  expect!(result).to(be_equal_to(MatchResult::RequestMatch(request.clone(),
+    HttpResponse { status: 200, .. HttpResponse::default() })));
+
+  let result = match_request_with_provider_states(&request, &["other state".to_string()], &pact).await;
+  expect!(result).to(be_equal_to(MatchResult::RequestNotFound(request.clone())));
+
+  let request = HttpRequest { path: "/users".into(), .. HttpRequest::default() };
+  let result = match_request_with_provider_states(&request, &["no user".to_string()], &pact).await;
+  expect!(result.matched()).to(be_true());
+}
+
+#[test]
+fn mock_server_selects_interactions_using_the_active_provider_states() {
+  let mut manager = ServerManager::new();
+  let id = "mock_server_selects_interactions_using_the_active_provider_states".to_string();
+  let port = manager.start_mock_server(id.clone(), provider_state_pact().boxed(), 0, MockServerConfig::default()).unwrap();
+  let url = format!("http://127.0.0.1:{}/users/100", port);
+  let client = reqwest::blocking::Client::new();
+
+  let response = client.get(url.as_str())
+    .header(crate::matching::PROVIDER_STATE_HEADER, "no user").send();
+  expect!(response.unwrap().status()).to(be_equal_to(404));
+
+  manager.find_mock_server_by_port_mut(port, &|ms| ms.set_provider_states(&["user exists"]));
+  let response = client.get(url.as_str()).send();
+  expect!(response.unwrap().status()).to(be_equal_to(200));
+
+  let response = client.get(url.as_str())
+    .header(crate::matching::PROVIDER_STATE_HEADER, "no user").send();
+  expect!(response.unwrap().status()).to(be_equal_to(404));
+
+  let mismatches = manager.find_mock_server_by_id(&id, &|_, ms| {
+    ms.unwrap_left().mismatches()
+  });
+  manager.shutdown_mock_server_by_port(port);
+
+  expect!(mismatches.map(|m| m.len())).to(be_some().value(1));
+}
+
+#[test]
+fn mock_server_does_not_split_the_provider_state_header_on_commas() {
+  let request = HttpRequest { path: "/users/100".into(), .. HttpRequest::default() };
+  let pact = V4Pact {
+    interactions: vec![
+      SynchronousHttp {
+        description: "a request for a user with orders".into(),
+        provider_states: vec![ProviderState::default("user exists, with orders")],
+        request: request.clone(),
+        response: HttpResponse { status: 200, .. HttpResponse::default() },
+        .. SynchronousHttp::default()
+      }.boxed_v4(),
+      SynchronousHttp {
+        description: "a request for a user that does not exist".into(),
+        provider_states: vec![ProviderState::default("no user"), ProviderState::default("service is up, and running")],
+        request,
+        response: HttpResponse { status: 404, .. HttpResponse::default() },
+        .. SynchronousHttp::default()
+      }.boxed_v4()
+    ],
+    .. V4Pact::default()
+  };
+  let mut manager = ServerManager::new();
+  let port = manager.start_mock_server("mock_server_does_not_split_the_provider_state_header_on_commas".to_string(),
+    pact.boxed(), 0, MockServerConfig::default()).unwrap();
+  let url = format!("http://127.0.0.1:{}/users/100", port);
+  let client = reqwest::blocking::Client::new();
+
+  let response = client.get(url.as_str())
+    .header(crate::matching::PROVIDER_STATE_HEADER, "user exists, with orders").send();
+  expect!(response.unwrap().status()).to(be_equal_to(200));
+  let response = client.get(url.as_str())
+    .header(crate::matching::PROVIDER_STATE_HEADER, "no user")
+    .header(crate::matching::PROVIDER_STATE_HEADER, "service is up, and running").send();
+  expect!(response.unwrap().status()).to(be_equal_to(404));
+
+  manager.shutdown_mock_server_by_port(port);
+}
+
+#[test]
+fn mock_server_records_requests_forwarded_to_the_upstream_server() {
+  let upstream_pact = V4Pact {
+    interactions: vec![
+      SynchronousHttp {
+        request: HttpRequest { path: "/users/100".into(), .. HttpRequest::default() },
+        response: HttpResponse {
+          status: 200,
+          headers: Some(hashmap! { "content-type".to_string() => vec!["application/json".to_string()] }),
+          body: OptionalBody::Present("{\"id\":100}".into(), None, None),
+          .. HttpResponse::default()
+        },
+        .. SynchronousHttp::default()
+      }.boxed_v4()
+    ],
+    .. V4Pact::default()
+  };
+  let mut manager = ServerManager::new();
+  let upstream_port = manager.start_mock_server("mock_server_records_requests_upstream".to_string(),
+    upstream_pact.boxed(), 0, MockServerConfig::default()).unwrap();
+  let config = MockServerConfig {
+    upstream_url: Some(format!("http://127.0.0.1:{}", upstream_port)),
+    .. MockServerConfig::default()
+  };
+  let port = manager.start_mock_server("mock_server_records_requests".to_string(),
+    V4Pact::default().boxed(), 0, config).unwrap();
+
+  let client = reqwest::blocking::Client::new();
+  let url = format!("http://127.0.0.1:{}/users/100", port);
+  let response = client.get(url.as_str()).send().unwrap();
+  expect!(response.status()).to(be_equal_to(200));
+  expect!(response.text().unwrap()).to(be_equal_to("{\"id\":100}"));
+
+  // The second request will be matched against the recorded interaction
+  let response = client.get(url.as_str()).send().unwrap();
+  expect!(response.status()).to(be_equal_to(200));
+
+  let upstream_requests = manager.find_mock_server_by_port(upstream_port, &|_, ms| {
+    ms.unwrap_left().metrics.requests
+  });
+  let (interactions, mismatches) = manager.find_mock_server_by_port(port, &|_, ms| {
+    let ms = ms.unwrap_left();
+    let interactions = ms.pact.lock().unwrap().interactions().iter()
+      .map(|i| {
+        let interaction = i.as_v4_http().unwrap();
+        (interaction.description, interaction.request.path, interaction.response.status)
+      })
+      .collect::<Vec<_>>();
+    (interactions, ms.mismatches())
+  }).unwrap();
+  manager.shutdown_mock_server_by_port(port);
+  manager.shutdown_mock_server_by_port(upstream_port);
+
+  expect!(upstream_requests).to(be_some().value(1));
+  expect!(interactions).to(be_equal_to(vec![("GET /users/100".to_string(), "/users/100".to_string(), 200)]));
+  expect!(mismatches).to(be_equal_to(vec![]));
+}
+
+#[test]
+fn stub_mock_server_does_not_record_mismatches() {
+  let mut manager = ServerManager::new();
+  let config = MockServerConfig {
+    stub_mode: true,
+    unmatched_status: Some(404),
+    .. MockServerConfig::default()
+  };
+  let port = manager.start_mock_server("stub_mock_server_does_not_record_mismatches".to_string(),
+    provider_state_pact().boxed(), 0, config).unwrap();
+
+  let client = reqwest::blocking::Client::new();
+  let response = client.get(format!("http://127.0.0.1:{}/users", port).as_str()).send();
+  expect!(response.unwrap().status()).to(be_equal_to(200));
+  let response = client.get(format!("http://127.0.0.1:{}/orders", port).as_str()).send();
+  expect!(response.unwrap().status()).to(be_equal_to(404));
+
+  let (mismatches, matches) = manager.find_mock_server_by_port(port, &|_, ms| {
+    let ms = ms.unwrap_left();
+    (ms.mismatches(), ms.matches())
+  }).unwrap();
+  manager.shutdown_mock_server_by_port(port);
+
+  expect!(mismatches.len()).to(be_equal_to(0));
+  expect!(matches.len()).to(be_equal_to(0));
+}
+
+#[test]
+fn mock_server_injects_faults_into_the_responses() {
+  let interaction = |path: &str| SynchronousHttp {
+    description: format!("a request for {}", path),
+    request: HttpRequest { path: path.into(), .. HttpRequest::default() },
+    response: HttpResponse {
+      status: 200,
+      body: OptionalBody::Present("0123456789".into(), None, None),
+      .. HttpResponse::default()
+    },
+    .. SynchronousHttp::default()
+  };
+  let pact = V4Pact {
+    interactions: vec![
+      SynchronousHttp {
+        comments: hashmap!{ "faults".to_string() => serde_json::json!({ "delay": 200 }) },
+        .. interaction("/slow")
+      }.boxed_v4(),
+      interaction("/closed").boxed_v4(),
+      interaction("/truncated").boxed_v4(),
+      interaction("/ok").boxed_v4()
+    ],
+    .. V4Pact::default()
+  };
+  let config = MockServerConfig {
+    faults: hashmap!{
+      "a request for /closed".to_string() => FaultConfig { close_connection: true, .. FaultConfig::default() },
+      "a request for /truncated".to_string() => FaultConfig { truncate_body: Some(4), .. FaultConfig::default() }
+    },
+    .. MockServerConfig::default()
+  };
+  let mut manager = ServerManager::new();
+  let port = manager.start_mock_server("mock_server_injects_faults_into_the_responses".to_string(),
+    pact.boxed(), 0, config).unwrap();
+  let client = reqwest::blocking::Client::new();
+  let url = |path: &str| format!("http://127.0.0.1:{}{}", port, path);
+
+  let start = std::time::Instant::now();
+  let response = client.get(url("/slow")).send().unwrap();
+  expect!(response.status()).to(be_equal_to(200));
+  expect!(start.elapsed() >= std::time::Duration::from_millis(200)).to(be_true());
+
+  expect!(client.get(url("/closed")).send()).to(be_err());
+
+  let response = client.get(url("/truncated")).send().unwrap();
+  expect!(response.status()).to(be_equal_to(200));
+  expect!(response.bytes()).to(be_err());
+
+  let response = client.get(url("/ok")).send().unwrap();
+  expect!(response.text().unwrap()).to(be_equal_to("0123456789"));
+
+  manager.shutdown_mock_server_by_port(port);
+}
+
+#[test]
+fn mock_server_injects_faults_for_the_matched_interaction_only() {
+  let interaction = |description: &str, state: &str| SynchronousHttp {
+    description: description.into(),
+    provider_states: vec![ProviderState::default(state)],
+    request: HttpRequest { path: "/users".into(), .. HttpRequest::default() },
+    .. SynchronousHttp::default()
+  };
+  let pact = V4Pact {
+    interactions: vec![
+      interaction("a request for the users", "users exist").boxed_v4(),
+      interaction("a request for the users when the service is down", "service is down").boxed_v4()
+    ],
+    .. V4Pact::default()
+  };
+  let config = MockServerConfig {
+    faults: hashmap!{
+      "a request for the users when the service is down".to_string() => FaultConfig { close_connection: true, .. FaultConfig::default() }
+    },
+    .. MockServerConfig::default()
+  };
+  let mut manager = ServerManager::new();
+  let port = manager.start_mock_server("mock_server_injects_faults_for_the_matched_interaction_only".to_string(),
+    pact.boxed(), 0, config).unwrap();
+  let client = reqwest::blocking::Client::new();
+  let url = format!("http://127.0.0.1:{}/users", port);
+
+  let response = client.get(url.as_str())
+    .header(crate::matching::PROVIDER_STATE_HEADER, "users exist").send();
+  expect!(response.unwrap().status()).to(be_equal_to(200));
+  let response = client.get(url.as_str())
+    .header(crate::matching::PROVIDER_STATE_HEADER, "service is down").send();
+  expect!(response).to(be_err());
+
+  manager.shutdown_mock_server_by_port(port);
+}
+
+#[test]
+fn mock_server_verifies_the_expected_calls_and_ordering() {
+  let interaction = |description: &str, method: &str| SynchronousHttp {
+    description: description.into(),
+    request: HttpRequest { method: method.into(), path: "/orders".into(), .. HttpRequest::default() },
+    .. SynchronousHttp::default()
+  };
+  let pact = V4Pact {
+    interactions: vec![
+      interaction("create an order", "POST").boxed_v4(),
+      interaction("get the orders", "GET").boxed_v4(),
+      interaction("delete the orders", "DELETE").boxed_v4()
+    ],
+    .. V4Pact::default()
+  };
+  let config = MockServerConfig {
+    expected_calls: hashmap!{
+      "create an order".to_string() => ExpectedCalls::Exactly(1),
+      "delete the orders".to_string() => ExpectedCalls::AtMost(1)
+    },
+    strict_ordering: true,
+    .. MockServerConfig::default()
+  };
+  let mut manager = ServerManager::new();
+  let port = manager.start_mock_server("mock_server_verifies_the_expected_calls_and_ordering".to_string(),
+    pact.boxed(), 0, config).unwrap();
+  let client = reqwest::blocking::Client::new();
+  let url = format!("http://127.0.0.1:{}/orders", port);
+
+  expect!(client.get(url.as_str()).send().unwrap().status()).to(be_equal_to(200));
+  expect!(client.post(url.as_str()).send().unwrap().status()).to(be_equal_to(200));
+  expect!(client.post(url.as_str()).send().unwrap().status()).to(be_equal_to(200));
+
+  let mismatches = manager.find_mock_server_by_port(port, &|_, ms| {
+    ms.unwrap_left().mismatches()
+  }).unwrap();
+  manager.shutdown_mock_server_by_port(port);
+
+  let post = HttpRequest { method: "POST".into(), path: "/orders".into(), .. HttpRequest::default() };
+  let get = HttpRequest { method: "GET".into(), path: "/orders".into(), .. HttpRequest::default() };
+  expect!(mismatches).to(be_equal_to(vec![
+    MatchResult::IncorrectCallCount(post.clone(), ExpectedCalls::Exactly(1), 2),
+    MatchResult::RequestOutOfOrder(post.clone(), get.clone()),
+    MatchResult::RequestOutOfOrder(post, get)
+  ]));
+}
+
+#[test]
+fn mock_server_records_a_journal_of_the_requests() {
+  let mut manager = ServerManager::new();
+  let port = manager.start_mock_server("mock_server_records_a_journal_of_the_requests".to_string(),
+    provider_state_pact().boxed(), 0, MockServerConfig::default()).unwrap();
+  let client = reqwest::blocking::Client::new();
+
+  expect!(client.get(format!("http://127.0.0.1:{}/users", port)).send().unwrap().status()).to(be_equal_to(200));
+  expect!(client.get(format!("http://127.0.0.1:{}/orders", port)).send().unwrap().status()).to(be_equal_to(500));
+
+  let journal = manager.find_mock_server_by_port(port, &|_, ms| {
+    ms.unwrap_left().journal()
+  }).unwrap();
+  manager.shutdown_mock_server_by_port(port);
+
+  expect!(journal.len()).to(be_equal_to(2));
+  expect!(journal[0].timestamp <= journal[1].timestamp).to(be_true());
+  expect!(journal[0].request.path.as_str()).to(be_equal_to("/users"));
+  expect!(journal[0].result.as_str()).to(be_equal_to("Request-Matched"));
+  expect!(journal[0].interaction.clone()).to(be_some().value("a request for the users"));
+  expect!(journal[0].response.as_ref().map(|r| r.status)).to(be_some().value(200));
+  expect!(journal[1].request.path.as_str()).to(be_equal_to("/orders"));
+  expect!(journal[1].result.as_str()).to(be_equal_to("Unexpected-Request"));
+  expect!(journal[1].interaction.clone()).to(be_none());
+  expect!(journal[1].response.as_ref().map(|r| r.status)).to(be_some().value(500));
+}
@@ -39,6 +39,8 @@ pub(crate) fn handle_error(error: &str) -> i32 {
 
 mod server;
 mod create_mock;
+mod record;
+mod stub;
 mod list;
 mod verify;
 mod shutdown;
@@ -114,6 +116,14 @@ fn uuid_value(v: String) -> Result<(), String> {
     Uuid::parse_str(v.as_str()).map(|_| ()).map_err(|e| format!("'{}' is not a valid UUID value: {}", v, e) )
 }
 
+fn url_value(v: String) -> Result<(), String> {
+  url::Url::parse(v.as_str()).map(|_| ()).map_err(|e| format!("'{}' is not a valid URL value: {}", v, e) )
+}
+
+fn glob_value(v: String) -> Result<(), String> {
+  glob::Pattern::new(v.as_str()).map(|_| ()).map_err(|e| format!("'{}' is not a valid glob pattern: {}", v, e) )
+}
+
 #[tokio::main]
 async fn main() {
   match handle_command_args().await {
@@ -223,6 +233,79 @@ async fn handle_command_args() -> Result<(), i32> {
           .long("tls")
           .help("Enable TLS with the mock server (will use a self-signed certificate)"))
         .setting(AppSettings::ColoredHelp))
+      .subcommand(SubCommand::with_name("record")
+        .about("Creates a new mock server that forwards requests to an upstream server and records them as interactions")
+        .arg(Arg::with_name("upstream")
+          .short("u")
+          .long("upstream")
+          .takes_value(true)
+          .use_delimiter(false)
+          .required(true)
+          .help("the URL of the upstream server to forward requests to")
+          .validator(url_value))
+        .arg(Arg::with_name("consumer")
+          .long("consumer")
+          .takes_value(true)
+          .use_delimiter(false)
+          .help("the consumer name for the recorded pact (defaults to consumer)"))
+        .arg(Arg::with_name("provider")
+          .long("provider")
+          .takes_value(true)
+          .use_delimiter(false)
+          .help("the provider name for the recorded pact (defaults to provider)"))
+        .arg(Arg::with_name("cors")
+          .short("c")
+          .long("cors-preflight")
+          .help("Handle CORS pre-flight requests"))
+        .arg(Arg::with_name("tls")
+          .long("tls")
+          .help("Enable TLS with the mock server (will use a self-signed certificate)"))
+        .setting(AppSettings::ColoredHelp))
+      .subcommand(SubCommand::with_name("stub")
+        .about("Starts a stub server that responds to requests with the best matching interaction from a set of pact files (uses the port option for the stub server)")
+        .arg(Arg::with_name("file")
+          .short("f")
+          .long("file")
+          .takes_value(true)
+          .use_delimiter(false)
+          .multiple(true)
+          .number_of_values(1)
+          .required_unless_one(&["dir", "glob"])
+          .help("a pact file to load, can be repeated"))
+        .arg(Arg::with_name("dir")
+          .short("d")
+          .long("dir")
+          .takes_value(true)
+          .use_delimiter(false)
+          .multiple(true)
+          .number_of_values(1)
+          .help("a directory of pact files to load, can be repeated"))
+        .arg(Arg::with_name("glob")
+          .short("g")
+          .long("glob")
+          .takes_value(true)
+          .use_delimiter(false)
+          .multiple(true)
+          .number_of_values(1)
+          .help("a glob pattern to match pact files to load, can be repeated")
+          .validator(glob_value))
+        .arg(Arg::with_name("provider")
+          .long("provider")
+          .takes_value(true)
+          .use_delimiter(false)
+          .help("only use the pacts for this provider (required if the pacts are for more than one provider)"))
+        .arg(Arg::with_name("unmatched-status")
+          .long("unmatched-status")
+          .takes_value(true)
+          .use_delimiter(false)
+          .possible_values(&["404", "500"])
+          .default_value("404")
+          .help("the status code to respond with when no interaction matches the request"))
+        .arg(Arg::with_name("cors")
+          .short("c")
+          .long("cors-preflight")
+          .help("Handle CORS pre-flight requests"))
+        .setting(AppSettings::ColoredHelp))
       .subcommand(SubCommand::with_name("verify")
               .about("Verify the mock server by id or port number, and generate a pact file if all ok")
               .arg(Arg::with_name("mock-server-id")
@@ -314,6 +397,8 @@ async fn handle_command_args() -> Result<(), i32> {
             },
             ("list", Some(sub_matches)) => list::list_mock_servers(host, p, sub_matches).await,
             ("create", Some(sub_matches)) => create_mock::create_mock_server(host, p, sub_matches).await,
+            ("record", Some(sub_matches)) => record::record_mock_server(host, p, sub_matches).await,
+            ("stub", Some(sub_matches)) => stub::start_stub_server(p, sub_matches).await,
             ("verify", Some(sub_matches)) => verify::verify_mock_server(host, p, sub_matches).await,
             ("shutdown", Some(sub_matches)) => shutdown::shutdown_mock_server(host, p, sub_matches).await,
             ("shutdown-master", Some(sub_matches)) => shutdown::shutdown_master_server(host, p, sub_matches).await,
@@ -351,7 +436,7 @@ mod test {
 
   use pact_matching::s;
 
-  use super::{integer_value, uuid_value};
+  use super::{glob_value, integer_value, url_value, uuid_value};
 
   #[test]
     fn validates_integer_value() {
@@ -393,4 +478,18 @@ mod test {
         expect!(uuid_value(s!("1234x"))).to(be_err());
     }
 
+  #[test]
+  fn validates_url_value() {
+    expect!(url_value(s!("http://localhost:8080"))).to(be_ok());
+    expect!(url_value(s!("https://api.example.com/v1"))).to(be_ok());
+    expect!(url_value(s!("not a url"))).to(be_err());
+  }
+
+  #[test]
+  fn validates_glob_value() {
+    expect!(glob_value(s!("pacts/*.json"))).to(be_ok());
+    expect!(glob_value(s!("pacts/**/*.json"))).to(be_ok());
+    expect!(glob_value(s!("pacts/[*.json"))).to(be_err());
+  }
+
 }
@@ -0,0 +1,30 @@
+use clap::ArgMatches;
+use log::*;
+
+use pact_models::{Consumer, PactSpecification, Provider};
+use pact_models::pact::Pact;
+use pact_models::v4::pact::V4Pact;
+
+use crate::create_mock::start_mock_server_on_master;
+
+pub async fn record_mock_server(host: &str, port: u16, matches: &ArgMatches<'_>) -> Result<(), i32> {
+  let upstream = matches.value_of("upstream").unwrap();
+  let pact = V4Pact {
+    consumer: Consumer { name: matches.value_of("consumer").unwrap_or("consumer").to_string() },
+    provider: Provider { name: matches.value_of("provider").unwrap_or("provider").to_string() },
+    .. V4Pact::default()
+  };
+  info!("Creating mock server to record requests to {}", upstream);
+
+  let json = match pact.to_json(PactSpecification::V4) {
+    Ok(json) => json,
+    Err(err) => {
+      crate::display_error(format!("Failed to create the pact JSON: {}", err), matches);
+    }
+  };
+  let (id, port) = start_mock_server_on_master(host, port, &json,
+    vec![("upstream", upstream.to_string())], matches).await?;
+  info!("Mock server {} will forward requests to {}", id, upstream);
+  println!("Mock server {} started on port {}, recording requests to {}", id, port, upstream);
+  Ok(())
+}
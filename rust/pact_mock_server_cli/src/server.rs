@@ -68,7 +68,12 @@ fn start_provider(context: &mut WebmachineContext, options: ServerOpts) -> Resul
           let config = MockServerConfig {
             cors_preflight: query_param_set(context, "cors"),
             pact_specification: PactSpecification::default(),
-            transport_config: Default::default()
+            transport_config: Default::default(),
+            upstream_url: context.request.query.get("upstream")
+              .and_then(|values| values.first())
+              .filter(|url| !url.is_empty())
+              .cloned(),
+            .. MockServerConfig::default()
           };
           debug!("Mock server config = {:?}", config);
 
@@ -223,7 +228,7 @@ fn mock_server_resource<'a>() -> WebmachineResource<'a> {
             context.metadata.insert("port".to_string(), ms.port.unwrap_or_default().to_string());
             if paths.len() > 1 {
               context.metadata.insert("subpath".to_string(), paths[1].clone());
-              paths[1] == "verify"
+              paths[1] == "verify" || paths[1] == "journal"
             } else {
               true
             }
@@ -242,6 +247,13 @@ fn mock_server_resource<'a>() -> WebmachineResource<'a> {
           SERVER_MANAGER.lock().unwrap().find_mock_server_by_id(&id, &|_, ms| ms.unwrap_left().to_json())
             .map(|json| json.to_string())
         }
+        Some(subpath) if subpath == "journal" && context.request.method == "GET" => {
+          let id = context.metadata.get("id").unwrap().clone();
+          SERVER_MANAGER.lock().unwrap().find_mock_server_by_id(&id, &|_, ms| {
+            ms.unwrap_left().journal_to_json()
+          })
+            .map(|json| json.to_string())
+        }
         Some(_) => {
           context.response.status = 405;
           None
@@ -270,6 +282,12 @@ fn mock_server_resource<'a>() -> WebmachineResource<'a> {
             }
           }).join().expect("Could not spawn thread to shut down mock server")
         }
+        Some(subpath) if subpath == "journal" => {
+          let port = context.metadata.get("port").unwrap().parse::<u16>().unwrap_or_default();
+          SERVER_MANAGER.lock().unwrap().find_mock_server_by_port_mut(port, &|ms| ms.clear_journal())
+            .map(|_| true)
+            .ok_or(404)
+        }
         Some(_) => Err(405)
       }
     }),
@@ -0,0 +1,159 @@
+use std::fs;
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use clap::ArgMatches;
+use glob::glob;
+use log::*;
+use uuid::Uuid;
+
+use pact_mock_server::mock_server::{MockServer, MockServerConfig};
+use pact_models::Consumer;
+use pact_models::pact::{Pact, read_pact};
+use pact_models::v4::pact::V4Pact;
+
+/// Starts a long running stub server from all the pact files provided by the command line
+/// arguments. Requests are responded to with the best matching interaction from all the pacts.
+pub async fn start_stub_server(port: u16, matches: &ArgMatches<'_>) -> Result<(), i32> {
+  let files = match pact_files(matches) {
+    Ok(files) => files,
+    Err(err) => crate::display_error(format!("Failed to find the pact files - {}", err), matches)
+  };
+  let mut pacts = vec![];
+  for file in &files {
+    match read_pact(file) {
+      Ok(pact) => pacts.push(pact),
+      Err(err) => crate::display_error(format!("Failed to load pact file '{}': {}", file.display(), err), matches)
+    }
+  }
+
+  let provider = matches.value_of("provider");
+  let pact = match combine_pacts(&pacts, provider) {
+    Ok(pact) => pact,
+    Err(err) => crate::display_error(err.to_string(), matches)
+  };
+  info!("Loaded {} interactions from {} pact file(s)", pact.interactions.len(), files.len());
+
+  let config = MockServerConfig {
+    cors_preflight: matches.is_present("cors"),
+    stub_mode: true,
+    unmatched_status: matches.value_of("unmatched-status").and_then(|status| status.parse().ok()),
+    .. MockServerConfig::default()
+  };
+  let addr = SocketAddr::new(IpAddr::from([0, 0, 0, 0]), port);
+  match MockServer::new(Uuid::new_v4().to_string(), pact.boxed(), addr, config).await {
+    Ok((mock_server, future)) => {
+      let port = mock_server.lock().unwrap().port.unwrap_or_default();
+      println!("Stub server started on port {} for provider '{}'", port, pact.provider.name);
+      future.await;
+      Ok(())
+    }
+    Err(err) => {
+      error!("Could not start the stub server: {}", err);
+      Err(1)
+    }
+  }
+}
+
+fn pact_files(matches: &ArgMatches) -> anyhow::Result<Vec<PathBuf>> {
+  let mut files = vec![];
+  if let Some(values) = matches.values_of("file") {
+    files.extend(values.map(PathBuf::from));
+  }
+  if let Some(values) = matches.values_of("dir") {
+    for value in values {
+      for entry in fs::read_dir(value)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().unwrap_or_default() == "json" {
+          files.push(path);
+        }
+      }
+    }
+  }
+  if let Some(values) = matches.values_of("glob") {
+    for value in values {
+      for entry in glob(value)? {
+        files.push(entry?);
+      }
+    }
+  }
+
+  if files.is_empty() {
+    Err(anyhow!("No pact files were found"))
+  } else {
+    Ok(files)
+  }
+}
+
+/// Combines the HTTP interactions from all the pacts into one Pact. If a provider name is given,
+/// only pacts for that provider are used, otherwise all the pacts must be for the same provider.
+fn combine_pacts(pacts: &[Box<dyn Pact + Send + Sync>], provider: Option<&str>) -> anyhow::Result<V4Pact> {
+  let pacts = pacts.iter()
+    .filter(|pact| provider.map(|name| pact.provider().name == name).unwrap_or(true))
+    .collect::<Vec<_>>();
+
+  let provider = match pacts.first() {
+    Some(pact) => pact.provider(),
+    None => return Err(anyhow!("There are no pacts for provider '{}'", provider.unwrap_or_default()))
+  };
+  if let Some(pact) = pacts.iter().find(|pact| pact.provider().name != provider.name) {
+    return Err(anyhow!("The pact files are for more than one provider ('{}' and '{}'), use the provider option to select one",
+      provider.name, pact.provider().name));
+  }
+
+  let mut combined = V4Pact {
+    consumer: Consumer { name: "stub".to_string() },
+    provider,
+    .. V4Pact::default()
+  };
+  for pact in pacts {
+    let pact = pact.as_v4_pact()?;
+    combined.interactions.extend(pact.interactions.iter()
+      .filter(|interaction| interaction.as_v4_http().is_some())
+      .cloned());
+  }
+  Ok(combined)
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use pact_models::{Consumer, Provider};
+  use pact_models::pact::Pact;
+  use pact_models::prelude::v4::SynchronousHttp;
+  use pact_models::v4::interaction::V4Interaction;
+  use pact_models::v4::pact::V4Pact;
+
+  use super::combine_pacts;
+
+  fn pact(consumer: &str, provider: &str, descriptions: &[&str]) -> Box<dyn Pact + Send + Sync> {
+    V4Pact {
+      consumer: Consumer { name: consumer.to_string() },
+      provider: Provider { name: provider.to_string() },
+      interactions: descriptions.iter().map(|description| SynchronousHttp {
+        description: description.to_string(),
+        .. SynchronousHttp::default()
+      }.boxed_v4()).collect(),
+      .. V4Pact::default()
+    }.boxed()
+  }
+
+  #[test]
+  fn combine_pacts_combines_the_interactions_for_the_provider() {
+    let pacts = vec![
+      pact("a", "provider", &["one", "two"]),
+      pact("b", "provider", &["three"]),
+      pact("c", "other", &["four"])
+    ];
+
+    let result = combine_pacts(&pacts, Some("provider")).unwrap();
+    expect!(result.provider.name).to(be_equal_to("provider"));
+    expect!(result.interactions.iter().map(|i| i.description()).collect::<Vec<_>>())
+      .to(be_equal_to(vec!["one", "two", "three"]));
+
+    expect!(combine_pacts(&pacts, Some("unknown"))).to(be_err());
+    expect!(combine_pacts(&pacts, None)).to(be_err());
+    expect!(combine_pacts(&pacts[0..2], None)).to(be_ok());
+  }
+}
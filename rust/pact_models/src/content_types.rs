@@ -83,13 +83,20 @@ impl ContentType {
     }
   }
 
-  /// If it is a JSON type
+  /// If it is a JSON type. Newline-delimited JSON types (see `is_ndjson`) are not JSON types, as
+  /// the body is a sequence of JSON documents.
   pub fn is_json(&self) -> bool {
-    self.main_type == "application" && (self.sub_type.starts_with("json") ||
+    self.main_type == "application" && !self.is_ndjson() && (self.sub_type.starts_with("json") ||
       self.suffix.as_ref().unwrap_or(&String::default()) == "json" ||
       self.sub_type == "graphql")
   }
 
+  /// If it is a newline-delimited JSON (NDJSON or JSON Lines) type
+  pub fn is_ndjson(&self) -> bool {
+    self.main_type == "application" && matches!(self.sub_type.as_str(),
+      "x-ndjson" | "ndjson" | "jsonl" | "x-jsonl" | "jsonlines" | "x-jsonlines")
+  }
+
   /// If it is a XML type
   pub fn is_xml(&self) -> bool {
     (self.main_type == "application" || self.main_type == "text") && (self.sub_type == "xml" ||
@@ -103,7 +110,7 @@ impl ContentType {
 
   /// If it is a text type
   pub fn is_text(&self) -> bool {
-    self.main_type == "text" || self.is_xml() || self.is_json()
+    self.main_type == "text" || self.is_xml() || self.is_json() || self.is_ndjson()
   }
 
   /// If it is a known binary type
@@ -312,7 +319,7 @@ mod tests {
   use expectest::prelude::*;
   use maplit::btreemap;
 
-  use super::ContentType;
+  use super::{ContentType, JSON};
 
   #[test]
   fn parse_test() {
@@ -407,6 +414,20 @@ mod tests {
     expect!(content_type.is_json()).to(be_true());
   }
 
+  #[test]
+  fn is_ndjson_test() {
+    for content_type in ["application/x-ndjson", "application/ndjson", "application/jsonl",
+      "application/x-jsonlines", "application/x-ndjson; charset=UTF-8"] {
+      let content_type = ContentType::parse(content_type).unwrap();
+      expect!(content_type.is_ndjson()).to(be_true());
+      expect!(content_type.is_json()).to(be_false());
+      expect!(content_type.is_text()).to(be_true());
+    }
+
+    expect!(JSON.is_ndjson()).to(be_false());
+    expect!(ContentType::parse("text/x-ndjson").unwrap().is_ndjson()).to(be_false());
+  }
+
   #[test]
   fn is_xml_test() {
     let content_type = ContentType::parse("application/atom+xml").unwrap();
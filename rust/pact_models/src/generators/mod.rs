@@ -6,6 +6,7 @@ use std::convert::TryFrom;
 use std::fmt::{Debug, Display, Formatter};
 use std::hash::{Hash, Hasher};
 use std::mem;
+use std::net::{Ipv4Addr, Ipv6Addr};
 use std::ops::Index;
 use std::str::FromStr;
 
@@ -28,7 +29,9 @@ use crate::bodies::OptionalBody;
 use crate::expression_parser::{contains_expressions, DataType, DataValue, MapValueResolver, parse_expression};
 use crate::generators::datetime_expressions::{execute_date_expression, execute_datetime_expression, execute_time_expression};
 use crate::json_utils::{get_field_as_string, json_to_string, JsonToNum};
+use crate::jwt::{decode_jwt, encode_jwt, split_scheme};
 use crate::matchingrules::{Category, MatchingRuleCategory};
+use crate::matchingrules::formats::StringFormat;
 use crate::PactSpecification;
 use crate::path_exp::{DocPath, PathToken};
 use crate::time_utils::{parse_pattern, to_chrono_pattern};
@@ -145,7 +148,16 @@ pub enum Generator {
   /// Generates a URL with the mock server as the base URL
   MockServerURL(String, String),
   /// List of variants which can have embedded generators
-  ArrayContains(Vec<(usize, MatchingRuleCategory, HashMap<DocPath, Generator>)>)
+  ArrayContains(Vec<(usize, MatchingRuleCategory, HashMap<DocPath, Generator>)>),
+  /// Generates a value by randomly selecting one of the provided values
+  RandomChoice(Vec<Value>),
+  /// Generates a random string in a well-known format (email, URI, UUID, etc.)
+  Format(StringFormat),
+  /// Generates a JWT from the example token, applying the embedded generators to the decoded
+  /// token (i.e. `$.claims.sub`). The token is signed with HMAC SHA-256 if a secret is provided,
+  /// otherwise it is unsigned. The secret is written to the pact file in plain text (and so will be
+  /// published to a Pact Broker), so it must only ever be a test secret.
+  Jwt(HashMap<DocPath, Generator>, Option<String>)
 }
 
 impl Generator {
@@ -195,6 +207,16 @@ impl Generator {
         }
       }
       Generator::MockServerURL(example, regex) => Some(json!({ "type": "MockServerURL", "example": example, "regex": regex })),
+      Generator::RandomChoice(values) => Some(json!({ "type": "RandomChoice", "values": values })),
+      Generator::Format(format) => Some(json!({ "type": "Format", "format": format.to_string() })),
+      Generator::Jwt(generators, secret) => {
+        let mut json = json!({ "type": "Jwt", "generators": generators_map_to_json(generators) });
+        // The secret is stored in plain text, so it can only be a test secret
+        if let Some(secret) = secret {
+          json["secret"] = Value::String(secret.clone());
+        }
+        Some(json)
+      }
       _ => None
     }
   }
@@ -225,6 +247,38 @@ impl Generator {
           .map(|dt| DataType::from(dt.clone())))),
       "MockServerURL" => Some(Generator::MockServerURL(get_field_as_string("example", map).unwrap_or_default(),
                                                        get_field_as_string("regex", map).unwrap_or_default())),
+      "RandomChoice" => match map.get("values") {
+        Some(Value::Array(values)) if !values.is_empty() => Some(Generator::RandomChoice(values.clone())),
+        _ => {
+          warn!("RandomChoice generator requires a non-empty 'values' array");
+          None
+        }
+      },
+      "Format" => match map.get("format").map(|format| StringFormat::from_str(json_to_string(format).as_str())) {
+        Some(Ok(format)) => Some(Generator::Format(format)),
+        Some(Err(err)) => {
+          warn!("Format generator has an invalid format - {}", err);
+          None
+        }
+        None => {
+          warn!("Format generator requires a 'format' field");
+          None
+        }
+      },
+      "Jwt" => {
+        let mut generators = Generators::default();
+        let category = GeneratorCategory::BODY;
+        if let Some(Value::Object(generators_json)) = map.get("generators") {
+          for (key, value) in generators_json {
+            match (DocPath::new(key), value) {
+              (Ok(path), Value::Object(map)) => generators.parse_generator_from_map(&category, map, Some(path)),
+              _ => warn!("Ignoring invalid JWT generator JSON '{}' -> {:?}", key, value)
+            }
+          }
+        }
+        Some(Generator::Jwt(generators.categories.get(&category).cloned().unwrap_or_default(),
+          get_field_as_string("secret", map)))
+      },
       _ => {
         warn!("'{}' is not a valid generator type", gen_type);
         None
@@ -257,6 +311,9 @@ impl Generator {
       Generator::ProviderStateGenerator(_, _) => "ProviderStateGenerator",
       Generator::MockServerURL(_, _) => "MockServerURL",
       Generator::ArrayContains(_) => "ArrayContains",
+      Generator::RandomChoice(_) => "RandomChoice",
+      Generator::Format(_) => "Format",
+      Generator::Jwt(_, _) => "Jwt",
     }.to_string()
   }
 
@@ -310,6 +367,13 @@ impl Generator {
             (key.to_string(), gen.to_json().unwrap())
           }).collect())])
         }).collect()
+      },
+      Generator::RandomChoice(values) => hashmap!{ "values" => Value::Array(values.clone()) },
+      Generator::Format(format) => hashmap!{ "format" => Value::String(format.to_string()) },
+      Generator::Jwt(generators, secret) => if let Some(secret) = secret {
+        hashmap!{ "generators" => generators_map_to_json(generators), "secret" => Value::String(secret.clone()) }
+      } else {
+        hashmap!{ "generators" => generators_map_to_json(generators) }
       }
     }
   }
@@ -367,6 +431,17 @@ impl Hash for Generator {
         }
       }
       Generator::Uuid(format) => format.hash(state),
+      Generator::RandomChoice(values) => for value in values {
+        value.to_string().hash(state);
+      },
+      Generator::Format(format) => format.hash(state),
+      Generator::Jwt(generators, secret) => {
+        for (path, generator) in generators.iter().sorted_by_key(|(path, _)| path.to_string()) {
+          path.hash(state);
+          generator.hash(state);
+        }
+        secret.hash(state);
+      }
       _ => ()
     }
   }
@@ -387,6 +462,9 @@ impl PartialEq for Generator {
       (Generator::MockServerURL(ex1, re1), Generator::MockServerURL(ex2, re2)) => ex1 == ex2 && re1 == re2,
       (Generator::ArrayContains(variants1), Generator::ArrayContains(variants2)) => variants1 == variants2,
       (Generator::Uuid(format), Generator::Uuid(format2)) => format == format2,
+      (Generator::RandomChoice(values1), Generator::RandomChoice(values2)) => values1 == values2,
+      (Generator::Format(format1), Generator::Format(format2)) => format1 == format2,
+      (Generator::Jwt(generators1, secret1), Generator::Jwt(generators2, secret2)) => generators1 == generators2 && secret1 == secret2,
       _ => mem::discriminant(self) == mem::discriminant(other)
     }
   }
@@ -626,6 +704,17 @@ fn hash_and_partial_eq_for_matching_rule() {
   expect!(&ac7).to_not(be_equal_to(&ac5));
   expect!(&ac7).to_not(be_equal_to(&ac6));
   expect!(&ac7).to_not(be_equal_to(&ac1));
+
+  let jwt1 = Generator::Jwt(hashmap!{ DocPath::new_unwrap("$.claims.sub") => Generator::Uuid(None) }, None);
+  let jwt2 = Generator::Jwt(hashmap!{ DocPath::new_unwrap("$.claims.sub") => Generator::Uuid(None) }, Some("secret".into()));
+  let jwt3 = Generator::Jwt(hashmap!{}, None);
+
+  expect!(h(&jwt1)).to(be_equal_to(h(&jwt1)));
+  expect!(&jwt1).to(be_equal_to(&jwt1));
+  expect!(h(&jwt1)).to_not(be_equal_to(h(&jwt2)));
+  expect!(h(&jwt1)).to_not(be_equal_to(h(&jwt3)));
+  expect!(&jwt1).to_not(be_equal_to(&jwt2));
+  expect!(&jwt1).to_not(be_equal_to(&jwt3));
 }
 
 
@@ -1035,6 +1124,10 @@ impl GenerateValue<u16> for Generator {
           Ok(val) => u16::try_from(val),
           Err(err) => Err(err)
         },
+      Generator::RandomChoice(values) => values.choose(&mut rand::thread_rng())
+        .and_then(|value| value.as_u64())
+        .and_then(|value| u16::try_from(value).ok())
+        .ok_or_else(|| anyhow!("Could not generate a u16 value from {} using {:?}", value, self)),
       _ => Err(anyhow!("Could not generate a u16 value from {} using {:?}", value, self))
     }
   }
@@ -1044,6 +1137,56 @@ pub fn generate_ascii_string(size: usize) -> String {
   rand::thread_rng().sample_iter(&Alphanumeric).map(char::from).take(size).collect()
 }
 
+/// Generates a random UUID in the given format
+fn generate_uuid(format: UuidFormat) -> String {
+  match format {
+    UuidFormat::Simple => Uuid::new_v4().to_simple().to_string(),
+    UuidFormat::LowerCaseHyphenated => Uuid::new_v4().to_hyphenated().to_string(),
+    UuidFormat::UpperCaseHyphenated => Uuid::new_v4().to_hyphenated().to_string().to_uppercase(),
+    UuidFormat::Urn => Uuid::new_v4().to_urn().to_string()
+  }
+}
+
+/// Generates a random string in the given format. Email addresses, URIs and host names will use
+/// the example.com domain.
+fn generate_format(format: &StringFormat) -> String {
+  let mut rnd = rand::thread_rng();
+  let name = generate_ascii_string(10).to_lowercase();
+  match format {
+    StringFormat::Email => format!("{}@example.com", name),
+    StringFormat::Uri => format!("https://example.com/{}", name),
+    StringFormat::Hostname => format!("{}.example.com", name),
+    StringFormat::Ipv4 => Ipv4Addr::from(rnd.gen::<u32>()).to_string(),
+    StringFormat::Ipv6 => Ipv6Addr::from(rnd.gen::<u128>()).to_string(),
+    StringFormat::Uuid(format) => generate_uuid(format.unwrap_or_default()),
+    StringFormat::Duration => format!("P{}DT{}H{}M", rnd.gen_range(0..30), rnd.gen_range(0..24), rnd.gen_range(0..60))
+  }
+}
+
+/// Generates a new JWT from the example token by applying the generators to the decoded token.
+/// Any authentication scheme prefix (i.e. `Bearer `) of the example is kept.
+#[allow(clippy::borrowed_box)]
+fn generate_jwt(
+  example: &str,
+  generators: &HashMap<DocPath, Generator>,
+  secret: &Option<String>,
+  context: &HashMap<&str, Value>,
+  matcher: &Box<dyn VariantMatcher + Send + Sync>
+) -> anyhow::Result<String> {
+  let (scheme, _) = split_scheme(example);
+  let mut handler = JsonHandler { value: decode_jwt(example)? };
+  for (key, generator) in generators {
+    handler.apply_key(key, generator, context, matcher);
+  }
+  encode_jwt(&handler.value, secret.as_deref()).map(|token| format!("{}{}", scheme, token))
+}
+
+fn generators_map_to_json(generators: &HashMap<DocPath, Generator>) -> Value {
+  Value::Object(generators.iter()
+    .filter_map(|(path, generator)| generator.to_json().map(|json| (path.to_string(), json)))
+    .collect())
+}
+
 fn strip_anchors(regex: &str) -> &str {
   regex
     .strip_prefix('^').unwrap_or(regex)
@@ -1068,19 +1211,14 @@ fn replace_with_regex(example: &String, url: String, re: Regex) -> String {
 impl GenerateValue<String> for Generator {
   fn generate_value(
     &self,
-    _: &String,
+    value: &String,
     context: &HashMap<&str, Value>,
-    _matcher: &Box<dyn VariantMatcher + Send + Sync>
+    matcher: &Box<dyn VariantMatcher + Send + Sync>
   ) -> anyhow::Result<String> {
     let mut rnd = rand::thread_rng();
     let result = match self {
       Generator::RandomInt(min, max) => Ok(format!("{}", rnd.gen_range(*min..max.saturating_add(1)))),
-      Generator::Uuid(format) => match format.unwrap_or_default() {
-        UuidFormat::Simple => Ok(Uuid::new_v4().to_simple().to_string()),
-        UuidFormat::LowerCaseHyphenated => Ok(Uuid::new_v4().to_hyphenated().to_string()),
-        UuidFormat::UpperCaseHyphenated => Ok(Uuid::new_v4().to_hyphenated().to_string().to_uppercase()),
-        UuidFormat::Urn => Ok(Uuid::new_v4().to_urn().to_string())
-      },
+      Generator::Uuid(format) => Ok(generate_uuid(format.unwrap_or_default())),
       Generator::RandomDecimal(digits) => Ok(generate_decimal(*digits as usize)),
       Generator::RandomHexadecimal(digits) => Ok(generate_hexadecimal(*digits as usize)),
       Generator::RandomString(size) => Ok(generate_ascii_string(*size as usize)),
@@ -1173,7 +1311,12 @@ impl GenerateValue<String> for Generator {
       } else {
         Err(anyhow!("MockServerURL: can not generate a value as there is no mock server details in the test context"))
       },
-      Generator::ArrayContains(_) => Err(anyhow!("can only use ArrayContains with lists"))
+      Generator::ArrayContains(_) => Err(anyhow!("can only use ArrayContains with lists")),
+      Generator::RandomChoice(values) => values.choose(&mut rnd)
+        .map(json_to_string)
+        .ok_or_else(|| anyhow!("RandomChoice: there are no values to select from")),
+      Generator::Format(format) => Ok(generate_format(format)),
+      Generator::Jwt(generators, secret) => generate_jwt(value, generators, secret, context, matcher)
     };
     debug!("Generator = {:?}, Generated value = {:?}", self, result);
     result
@@ -1209,12 +1352,7 @@ impl GenerateValue<Value> for Generator {
         }
       },
       Generator::Uuid(format) => match value {
-        Value::String(_) => match format.unwrap_or_default() {
-          UuidFormat::Simple => Ok(json!(Uuid::new_v4().to_simple().to_string())),
-          UuidFormat::LowerCaseHyphenated => Ok(json!(Uuid::new_v4().to_hyphenated().to_string())),
-          UuidFormat::UpperCaseHyphenated => Ok(json!(Uuid::new_v4().to_hyphenated().to_string().to_uppercase())),
-          UuidFormat::Urn => Ok(json!(Uuid::new_v4().to_urn().to_string()))
-        },
+        Value::String(_) => Ok(json!(generate_uuid(format.unwrap_or_default()))),
         _ => Err(anyhow!("Could not generate a UUID from {}", value))
       },
       Generator::RandomDecimal(digits) => match value {
@@ -1340,6 +1478,17 @@ impl GenerateValue<Value> for Generator {
         }
         _ => Err(anyhow!("can only use ArrayContains with lists"))
       }
+      Generator::RandomChoice(values) => values.choose(&mut rand::thread_rng())
+        .cloned()
+        .ok_or_else(|| anyhow!("RandomChoice: there are no values to select from")),
+      Generator::Format(format) => match value {
+        Value::String(_) => Ok(json!(generate_format(format))),
+        _ => Err(anyhow!("Could not generate {} from {}", format.description(), value))
+      }
+      Generator::Jwt(generators, secret) => match value {
+        Value::String(token) => Ok(json!(generate_jwt(token, generators, secret, context, matcher)?)),
+        _ => Err(anyhow!("Could not generate a JWT from {}", value))
+      }
     };
     debug!("Generated value = {:?}", result);
     result
@@ -1640,6 +1789,39 @@ mod tests {
       be_some().value(Generator::ProviderStateGenerator("5".into(), Some(DataType::INTEGER))));
   }
 
+  #[test]
+  fn random_choice_generator_from_json_test() {
+    expect!(Generator::from_map("RandomChoice", &serde_json::Map::new())).to(be_none());
+    expect!(Generator::from_map("RandomChoice", &json!({ "values": [] }).as_object().unwrap())).to(be_none());
+    expect!(Generator::from_map("RandomChoice", &json!({ "values": ["AUD", "USD"] }).as_object().unwrap())).to(
+      be_some().value(Generator::RandomChoice(vec![json!("AUD"), json!("USD")])));
+  }
+
+  #[test]
+  fn format_generator_from_json_test() {
+    expect!(Generator::from_map("Format", &serde_json::Map::new())).to(be_none());
+    expect!(Generator::from_map("Format", &json!({ "format": "phone" }).as_object().unwrap())).to(be_none());
+    expect!(Generator::from_map("Format", &json!({ "format": "email" }).as_object().unwrap())).to(
+      be_some().value(Generator::Format(StringFormat::Email)));
+    expect!(Generator::from_map("Format", &json!({ "format": "uuid-urn" }).as_object().unwrap())).to(
+      be_some().value(Generator::Format(StringFormat::Uuid(Some(UuidFormat::Urn)))));
+  }
+
+  #[test]
+  fn jwt_generator_from_json_test() {
+    expect!(Generator::from_map("Jwt", &serde_json::Map::new())).to(
+      be_some().value(Generator::Jwt(hashmap!{}, None)));
+    expect!(Generator::from_map("Jwt", &json!({
+      "generators": {
+        "$.claims.sub": { "type": "Uuid" },
+        "$.claims.exp": { "type": "Unknown" }
+      },
+      "secret": "test-secret"
+    }).as_object().unwrap())).to(be_some().value(Generator::Jwt(hashmap!{
+      DocPath::new_unwrap("$.claims.sub") => Generator::Uuid(None)
+    }, Some("test-secret".to_string()))));
+  }
+
   #[test]
   fn generator_to_json_test() {
     expect!(Generator::RandomInt(5, 15).to_json().unwrap()).to(be_equal_to(json!({
@@ -1724,6 +1906,23 @@ mod tests {
       "example": "http://localhost:1234/path",
       "regex": "(.*)/path"
     })));
+    expect!(Generator::RandomChoice(vec![json!("AUD"), json!(100)]).to_json().unwrap()).to(be_equal_to(json!({
+      "type": "RandomChoice",
+      "values": ["AUD", 100]
+    })));
+    expect!(Generator::Format(StringFormat::Ipv6).to_json().unwrap()).to(be_equal_to(json!({
+      "type": "Format",
+      "format": "ipv6"
+    })));
+    expect!(Generator::Jwt(hashmap!{ DocPath::new_unwrap("$.claims.sub") => Generator::Uuid(None) }, None).to_json().unwrap()).to(be_equal_to(json!({
+      "type": "Jwt",
+      "generators": { "$.claims.sub": { "type": "Uuid" } }
+    })));
+    expect!(Generator::Jwt(hashmap!{}, Some("secret".to_string())).to_json().unwrap()).to(be_equal_to(json!({
+      "type": "Jwt",
+      "generators": {},
+      "secret": "secret"
+    })));
   }
 
   #[test]
@@ -1903,6 +2102,77 @@ mod tests {
     expect!(generated).to(be_err());
   }
 
+  #[test]
+  fn random_choice_generator_test() {
+    let generator = Generator::RandomChoice(vec![json!("AUD"), json!("USD"), json!("EUR")]);
+    for _ in 1..10 {
+      let generated = generator.generate_value(&"".to_string(), &hashmap!{}, &NoopVariantMatcher.boxed()).unwrap();
+      expect!(["AUD", "USD", "EUR"].contains(&generated.as_str())).to(be_true());
+    }
+
+    let generator = Generator::RandomChoice(vec![json!(200), json!(201)]);
+    let generated = generator.generate_value(&json!(204), &hashmap!{}, &NoopVariantMatcher.boxed()).unwrap();
+    expect!(generated == json!(200) || generated == json!(201)).to(be_true());
+    let generated: u16 = generator.generate_value(&204, &hashmap!{}, &NoopVariantMatcher.boxed()).unwrap();
+    expect!(generated == 200 || generated == 201).to(be_true());
+  }
+
+  #[test]
+  fn format_generator_test() {
+    let formats = vec![
+      StringFormat::Email,
+      StringFormat::Uri,
+      StringFormat::Hostname,
+      StringFormat::Ipv4,
+      StringFormat::Ipv6,
+      StringFormat::Uuid(None),
+      StringFormat::Uuid(Some(UuidFormat::Simple)),
+      StringFormat::Uuid(Some(UuidFormat::UpperCaseHyphenated)),
+      StringFormat::Uuid(Some(UuidFormat::Urn)),
+      StringFormat::Duration
+    ];
+    for format in formats {
+      let generator = Generator::Format(format);
+      let generated = generator.generate_value(&"".to_string(), &hashmap!{}, &NoopVariantMatcher.boxed()).unwrap();
+      expect!(format.validate(generated.as_str())).to(be_ok());
+      let generated = generator.generate_value(&json!("value"), &hashmap!{}, &NoopVariantMatcher.boxed()).unwrap();
+      expect!(format.validate(generated.as_str().unwrap())).to(be_ok());
+    }
+
+    let generator = Generator::Format(StringFormat::Email);
+    expect!(generator.generate_value(&json!(100), &hashmap!{}, &NoopVariantMatcher.boxed())).to(be_err());
+  }
+
+  #[test]
+  fn jwt_generator_test() {
+    let example = crate::jwt::encode_jwt(&json!({
+      "header": { "kid": "key-1" },
+      "claims": { "sub": "1234", "name": "Fred", "admin": false }
+    }), None).unwrap();
+    let generators = hashmap!{
+      DocPath::new_unwrap("$.claims.sub") => Generator::Uuid(None),
+      DocPath::new_unwrap("$.claims.admin") => Generator::RandomBoolean
+    };
+
+    let generator = Generator::Jwt(generators.clone(), None);
+    let generated = generator.generate_value(&example, &hashmap!{}, &NoopVariantMatcher.boxed()).unwrap();
+    let token = decode_jwt(generated.as_str()).unwrap();
+    expect!(generated.ends_with('.')).to(be_true());
+    expect!(&token["header"]).to(be_equal_to(&json!({ "alg": "none", "typ": "JWT", "kid": "key-1" })));
+    expect!(Uuid::parse_str(token["claims"]["sub"].as_str().unwrap())).to(be_ok());
+    expect!(token["claims"]["admin"].is_boolean()).to(be_true());
+    expect!(&token["claims"]["name"]).to(be_equal_to(&json!("Fred")));
+
+    let generator = Generator::Jwt(generators, Some("secret".to_string()));
+    let generated = generator.generate_value(&json!(format!("Bearer {}", example)), &hashmap!{}, &NoopVariantMatcher.boxed()).unwrap();
+    let generated = generated.as_str().unwrap();
+    expect!(generated.starts_with("Bearer ")).to(be_true());
+    expect!(&decode_jwt(generated).unwrap()["header"]["alg"]).to(be_equal_to(&json!("HS256")));
+
+    expect!(generator.generate_value(&"not a token".to_string(), &hashmap!{}, &NoopVariantMatcher.boxed())).to(be_err());
+    expect!(generator.generate_value(&json!(100), &hashmap!{}, &NoopVariantMatcher.boxed())).to(be_err());
+  }
+
   #[test]
   fn applies_the_generator_to_a_json_map_entry() {
     let map = json!({"a": 100, "b": "B", "c": "C"});
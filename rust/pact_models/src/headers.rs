@@ -1,2 +1,7 @@
 pub static PARAMETERISED_HEADERS: [&str; 2] = ["accept", "content-type"];
 pub static SINGLE_VALUE_HEADERS: [&str; 1] = ["date"];
+pub static COOKIE_HEADERS: [&str; 2] = ["cookie", "set-cookie"];
+pub static QUALITY_LIST_HEADERS: [&str; 4] = ["accept", "accept-charset", "accept-encoding", "accept-language"];
+pub static LIST_HEADERS: [&str; 16] = ["accept-ch", "accept-patch", "accept-ranges", "allow", "cache-control",
+  "cache-status", "connection", "if-match", "if-none-match", "link", "pragma", "prefer", "preference-applied",
+  "priority", "proxy-status", "vary"];
@@ -108,7 +108,11 @@ pub fn headers_from_json(request: &Value) -> Option<HashMap<String, Vec<String>>
     Some(Value::Object(m)) => {
       Some(m.iter().map(|(key, val)| {
         match val {
-          Value::String(s) => (key.clone(), s.clone().split(',').map(|v| v.trim().to_string()).collect()),
+          Value::String(s) => if key.eq_ignore_ascii_case("set-cookie") {
+            (key.clone(), split_set_cookie_header(s))
+          } else {
+            (key.clone(), s.clone().split(',').map(|v| v.trim().to_string()).collect())
+          },
           Value::Array(v) => (key.clone(), v.iter().map(|val| {
             match val {
               Value::String(s) => s.clone(),
@@ -123,7 +127,29 @@ pub fn headers_from_json(request: &Value) -> Option<HashMap<String, Vec<String>>
   }
 }
 
-/// Converts the headers map into a JSON struct
+/// Splits a Set-Cookie header value that combines multiple cookies with commas. A comma only
+/// starts a new cookie if it is followed by a `name=value` pair, so the commas in attributes like
+/// `Expires=Wed, 21 Oct 2015 07:28:00 GMT` are kept.
+fn split_set_cookie_header(value: &str) -> Vec<String> {
+  let mut cookies: Vec<String> = vec![];
+  for part in value.split(',') {
+    let starts_cookie = part.split(';').next()
+      .and_then(|pair| pair.split_once('='))
+      .map(|(name, _)| !name.trim().is_empty() && !name.trim().contains(char::is_whitespace))
+      .unwrap_or(false);
+    match cookies.last_mut() {
+      Some(cookie) if !starts_cookie => {
+        cookie.push(',');
+        cookie.push_str(part);
+      }
+      _ => cookies.push(part.to_string())
+    }
+  }
+  cookies.iter().map(|cookie| cookie.trim().to_string()).collect()
+}
+
+/// Converts the headers map into a JSON struct. Header values are written as a single string, as
+/// required by the V2 and V3 specifications.
 pub fn headers_to_json(headers: &HashMap<String, Vec<String>>) -> Value {
   json!(headers.iter().fold(BTreeMap::new(), |mut map, kv| {
     map.insert(kv.0.clone(), Value::String(kv.1.join(", ")));
@@ -226,6 +252,7 @@ pub fn is_empty(value: &Value) -> bool {
 mod tests {
   use expectest::expect;
   use expectest::prelude::*;
+  use maplit::*;
   use serde_json::json;
 
   use super::*;
@@ -347,6 +374,48 @@ mod tests {
     expect!(body).to(be_equal_to(OptionalBody::Present("{\"test\":true}".into(), Some("application/json".into()), None)));
   }
 
+  #[test]
+  fn headers_from_json_does_not_split_set_cookie_headers() {
+    let json = json!({
+      "headers": {
+        "Accept": "application/json, text/plain",
+        "Set-Cookie": "session=1234; Expires=Wed, 21 Oct 2015 07:28:00 GMT"
+      }
+    });
+    let headers = headers_from_json(&json);
+    expect!(headers).to(be_some().value(hashmap!{
+      "Accept".to_string() => vec!["application/json".to_string(), "text/plain".to_string()],
+      "Set-Cookie".to_string() => vec!["session=1234; Expires=Wed, 21 Oct 2015 07:28:00 GMT".to_string()]
+    }));
+  }
+
+  #[test]
+  fn headers_to_json_writes_header_values_as_a_string() {
+    let headers = hashmap!{
+      "Accept".to_string() => vec!["application/json".to_string(), "text/plain".to_string()],
+      "Set-Cookie".to_string() => vec!["session=1234; Expires=Wed, 21 Oct 2015 07:28:00 GMT".to_string(), "theme=dark".to_string()]
+    };
+    let json = json!({ "headers": headers_to_json(&headers) });
+    expect!(json.clone()).to(be_equal_to(json!({
+      "headers": {
+        "Accept": "application/json, text/plain",
+        "Set-Cookie": "session=1234; Expires=Wed, 21 Oct 2015 07:28:00 GMT, theme=dark"
+      }
+    })));
+    expect!(headers_from_json(&json)).to(be_some().value(headers));
+  }
+
+  #[test]
+  fn split_set_cookie_header_test() {
+    expect!(split_set_cookie_header("session=1234")).to(be_equal_to(vec!["session=1234".to_string()]));
+    expect!(split_set_cookie_header("a=1; Path=/,b=2, c=3; HttpOnly")).to(be_equal_to(vec![
+      "a=1; Path=/".to_string(), "b=2".to_string(), "c=3; HttpOnly".to_string()
+    ]));
+    expect!(split_set_cookie_header("a=1; Expires=Wed, 21 Oct 2015 07:28:00 GMT, b=x,y")).to(be_equal_to(vec![
+      "a=1; Expires=Wed, 21 Oct 2015 07:28:00 GMT".to_string(), "b=x,y".to_string()
+    ]));
+  }
+
   #[test]
   fn body_from_json_returns_missing_if_there_is_no_body() {
     let json : serde_json::Value = serde_json::from_str(r#"
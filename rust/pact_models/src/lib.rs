@@ -30,6 +30,7 @@ pub mod http_parts;
 pub mod request;
 pub mod response;
 pub mod headers;
+pub mod jwt;
 pub mod interaction;
 pub mod sync_interaction;
 pub mod message;
@@ -37,6 +37,10 @@
 //! | boolean     | Value must be a boolean                                                                               |                    | `matching(boolean, true)`                                                     |
 //! | server      | Value must match the semver specification                                                             |                    | `matching(semver, '1.0.0')`                                                   |
 //! | contentType | Value must be of the provided content type. This will preform a magic test on the bytes of the value. | Content type       | `matching(contentType, 'application/xml', '<?xml?><test/>')`                  |
+//! | numberRange | Value must be a number between the minimum and maximum (inclusive). Use `null` for an open bound.      | Min, Max           | `matching(numberRange, 1, 100, 50)`                                           |
+//! | stringLength| Value must be a string with a length between the minimum and maximum. Use `null` for an open bound.   | Min, Max           | `matching(stringLength, 1, 3, 'USD')`                                         |
+//! | oneOf       | Value must be one of the given values. The first value is used as the example.                        | Allowed values     | `matching(oneOf, 'AUD', 'EUR', 'USD')`                                        |
+//! | format      | Value must be a string in a well-known format (email, uri, hostname, ipv4, ipv6, uuid, duration)      | Format name        | `matching(format, 'email', 'a@b.com')`                                        |
 //!
 //! The final form is a reference to another key. This is used to setup type matching using an example value, and is normally
 //! used for collections. The name of the key must be a string value in single quotes.
@@ -72,12 +76,34 @@
 //!
 //! For example: `eachValue(matching(type, 100))`
 //!
+//! ### not(EXPRESSION)
+//!
+//! Configures a matching rule that the value must NOT match. Any example value of the expression is ignored.
+//!
+//! For example: `matching(type, 'Fred'), not(matching(regex, '^\s*$', ''))`
+//!
+//! ### anyOf(EXPRESSION, EXPRESSION, ...)
+//!
+//! Configures a set of matching rules where the value must match at least one of them. The first example value and
+//! generator of the expressions is used.
+//!
+//! For example: `anyOf(matching(null), matching(date, 'yyyy-MM-dd', '2000-01-01'))`
+//!
+//! ### allOf(EXPRESSION, EXPRESSION, ...)
+//!
+//! Configures a set of matching rules where the value must match all of them. The first example value and generator
+//! of the expressions is used.
+//!
+//! For example: `allOf(matching(stringLength, 1, 20, 'Fred'), not(matching(include, 'admin')))`
+//!
+//! `matching(null)` can be used with these expressions to define a value that must be null.
+//!
 //! ## Grammar
 //!
 //! There is a grammar for the definitions in [ANTLR4 format](https://github.com/pact-foundation/pact-plugins/blob/main/docs/matching-rule-definition.g4).
 //!
 
-use std::str::from_utf8;
+use std::str::{from_utf8, FromStr};
 
 use anyhow::{anyhow, Error};
 use ariadne::{Config, Label, Report, ReportKind, Source};
@@ -85,9 +111,12 @@ use bytes::{BufMut, BytesMut};
 use itertools::Either;
 use logos::{Lexer, Logos};
 use semver::Version;
+use serde_json::{json, Value};
 use tracing::{trace, warn};
 
 use crate::generators::Generator;
+use crate::json_utils::json_to_string;
+use crate::matchingrules::formats::StringFormat;
 use crate::matchingrules::MatchingRule;
 use crate::matchingrules::MatchingRule::NotEmpty;
 
@@ -206,6 +235,15 @@ enum MatcherDefinitionToken {
   #[token("eachValue")]
   EachValue,
 
+  #[token("not")]
+  Not,
+
+  #[token("anyOf")]
+  AnyOf,
+
+  #[token("allOf")]
+  AllOf,
+
   #[token("(")]
   LeftBracket,
 
@@ -224,7 +262,7 @@ enum MatcherDefinitionToken {
   #[regex("-?[0-9]+", |lex| lex.slice().parse())]
   Int(i64),
 
-  #[regex(r"-?[0-9]\.[0-9]+")]
+  #[regex(r"-?[0-9]+\.[0-9]+")]
   Decimal,
 
   #[regex(r"true|false")]
@@ -266,7 +304,9 @@ pub fn is_matcher_def(v: &str) -> bool {
     let next = lex.next();
     if let Some(token) = next {
       if token == MatcherDefinitionToken::Matching || token == MatcherDefinitionToken::NotEmpty ||
-        token == MatcherDefinitionToken::EachKey || token == MatcherDefinitionToken::EachValue {
+        token == MatcherDefinitionToken::EachKey || token == MatcherDefinitionToken::EachValue ||
+        token == MatcherDefinitionToken::Not || token == MatcherDefinitionToken::AnyOf ||
+        token == MatcherDefinitionToken::AllOf {
         true
       } else {
         false
@@ -314,6 +354,8 @@ fn matching_definition(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyh
 //           $value = new MatchingRuleDefinition(null, ValueType.Unknown, List.of((Either<MatchingRule, MatchingReference>) new Either.A(new EachValueMatcher($e.value))), null);
 //         }
 //       }
+//       | 'not' LEFT_BRACKET e=matchingDefinitionExp RIGHT_BRACKET { $value = new MatchingRuleDefinition(null, new NotMatcher($e.value), null); }
+//       | combinator=( 'anyOf' | 'allOf' ) LEFT_BRACKET e=matchingDefinitionExp ( COMMA e=matchingDefinitionExp )* RIGHT_BRACKET
 //     )
 //     ;
 fn matching_definition_exp(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::Result<MatchingRuleDefinition> {
@@ -350,6 +392,9 @@ fn matching_definition_exp(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) ->
     } else if token == MatcherDefinitionToken::EachValue {
       let definition = parse_each_value(lex, v)?;
       Ok(definition)
+    } else if token == MatcherDefinitionToken::Not || token == MatcherDefinitionToken::AnyOf ||
+      token == MatcherDefinitionToken::AllOf {
+      parse_combinator(lex, v, token)
     } else {
       let mut buffer = BytesMut::new().writer();
       let span = lex.span();
@@ -357,7 +402,7 @@ fn matching_definition_exp(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) ->
         .with_config(Config::default().with_color(false))
         .with_message(format!("Expected a type of matching rule definition, but got '{}'", lex.slice()))
         .with_label(Label::new(("expression", span)).with_message("Expected a matching rule definition here"))
-        .with_note("valid matching rule definitions are: matching, notEmpty, eachKey, eachValue")
+        .with_note("valid matching rule definitions are: matching, notEmpty, eachKey, eachValue, not, anyOf, allOf")
         .finish();
       report.write(("expression", Source::from(v)), &mut buffer)?;
       let message = from_utf8(&*buffer.get_ref())?.to_string();
@@ -370,7 +415,7 @@ fn matching_definition_exp(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) ->
       .with_config(Config::default().with_color(false))
       .with_message(format!("Expected a type of matching rule definition but got the end of the expression"))
       .with_label(Label::new(("expression", span)).with_message("Expected a matching rule definition here"))
-      .with_note("valid matching rule definitions are: matching, notEmpty, eachKey, eachValue")
+      .with_note("valid matching rule definitions are: matching, notEmpty, eachKey, eachValue, not, anyOf, allOf")
       .finish();
     report.write(("expression", Source::from(v)), &mut buffer)?;
     let message = from_utf8(&*buffer.get_ref())?.to_string();
@@ -462,6 +507,96 @@ fn parse_each_key(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::R
   }
 }
 
+// LEFT_BRACKET e=matchingDefinitionExp ( COMMA e=matchingDefinitionExp )* RIGHT_BRACKET
+fn parse_combinator(
+  lex: &mut Lexer<MatcherDefinitionToken>,
+  v: &str,
+  combinator: MatcherDefinitionToken
+) -> anyhow::Result<MatchingRuleDefinition> {
+  let next = lex.next()
+    .ok_or_else(|| end_of_expression(v, "an opening bracket"))?;
+  if next != MatcherDefinitionToken::LeftBracket {
+    let mut buffer = BytesMut::new().writer();
+    let span = lex.span();
+    let report = Report::build(ReportKind::Error, "expression", span.start)
+      .with_config(Config::default().with_color(false))
+      .with_message(format!("Expected an opening bracket, got '{}'", lex.slice()))
+      .with_label(Label::new(("expression", span)).with_message("Expected an opening bracket before this"))
+      .finish();
+    report.write(("expression", Source::from(v)), &mut buffer)?;
+    let message = from_utf8(&*buffer.get_ref())?.to_string();
+    return Err(anyhow!(message));
+  }
+
+  let mut definitions = vec![ matching_definition_exp(lex, v)? ];
+  loop {
+    let next = lex.next().ok_or_else(|| end_of_expression(v, "a closing bracket"))?;
+    if next == MatcherDefinitionToken::RightBracket {
+      break;
+    } else if next == MatcherDefinitionToken::Comma && combinator != MatcherDefinitionToken::Not {
+      definitions.push(matching_definition_exp(lex, v)?);
+    } else {
+      let mut buffer = BytesMut::new().writer();
+      let span = lex.span();
+      let report = Report::build(ReportKind::Error, "expression", span.start)
+        .with_config(Config::default().with_color(false))
+        .with_message(format!("Expected a closing bracket, got '{}'", lex.slice()))
+        .with_label(Label::new(("expression", span)).with_message("Expected a closing bracket before this"))
+        .finish();
+      report.write(("expression", Source::from(v)), &mut buffer)?;
+      let message = from_utf8(&*buffer.get_ref())?.to_string();
+      return Err(anyhow!(message));
+    }
+  }
+
+  let rules = definitions.iter()
+    .map(combined_rule)
+    .collect::<anyhow::Result<Vec<MatchingRule>>>()?;
+  if combinator == MatcherDefinitionToken::Not {
+    Ok(MatchingRuleDefinition {
+      value: "".to_string(),
+      value_type: ValueType::Unknown,
+      rules: vec![ Either::Left(MatchingRule::Not(Box::new(rules[0].clone()))) ],
+      generator: None
+    })
+  } else {
+    let value = definitions.iter()
+      .find(|definition| !definition.value.is_empty())
+      .map(|definition| definition.value.clone())
+      .unwrap_or_default();
+    let value_type = definitions.iter()
+      .fold(ValueType::Unknown, |acc, definition| acc.merge(definition.value_type));
+    let generator = definitions.iter().find_map(|definition| definition.generator.clone());
+    let rule = if combinator == MatcherDefinitionToken::AnyOf {
+      MatchingRule::AnyOf(rules)
+    } else {
+      MatchingRule::AllOf(rules)
+    };
+    Ok(MatchingRuleDefinition {
+      value,
+      value_type,
+      rules: vec![ Either::Left(rule) ],
+      generator
+    })
+  }
+}
+
+/// Converts the rules of a definition used in a combinator into a single matching rule
+fn combined_rule(definition: &MatchingRuleDefinition) -> anyhow::Result<MatchingRule> {
+  let mut rules = definition.rules.iter()
+    .map(|rule| match rule {
+      Either::Left(rule) => Ok(rule.clone()),
+      Either::Right(reference) => Err(anyhow!("References (matching($'{}')) can not be used with not, anyOf or allOf",
+        reference.name))
+    })
+    .collect::<anyhow::Result<Vec<MatchingRule>>>()?;
+  if rules.len() == 1 {
+    Ok(rules.remove(0))
+  } else {
+    Ok(MatchingRule::AllOf(rules))
+  }
+}
+
 // LEFT_BRACKET primitiveValue RIGHT_BRACKET
 fn parse_not_empty(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::Result<(String, ValueType)> {
   let next = lex.next().ok_or_else(|| anyhow!("expected '('"))?;
@@ -512,7 +647,12 @@ fn parse_matching(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::R
 //   | 'boolean' COMMA BOOLEAN_LITERAL { $rule = BooleanMatcher.INSTANCE; $value = $BOOLEAN_LITERAL.getText(); $type = ValueType.Boolean; }
 //   | 'semver' COMMA s=string { $rule = SemverMatcher.INSTANCE; $value = $s.contents; $type = ValueType.String; }
 //   | 'contentType' COMMA ct=string COMMA s=string { $rule = new ContentTypeMatcher($ct.contents); $value = $s.contents; $type = ValueType.Unknown; }
+//   | 'numberRange' COMMA min=optionalNumber COMMA max=optionalNumber COMMA val=( DECIMAL_LITERAL | INTEGER_LITERAL ) { $rule = new NumberRangeMatcher($min.value, $max.value); $value = $val.getText(); $type = ValueType.Number; }
+//   | 'stringLength' COMMA min=optionalInteger COMMA max=optionalInteger COMMA s=string { $rule = new StringLengthMatcher($min.value, $max.value); $value = $s.contents; $type = ValueType.String; }
+//   | 'oneOf' ( COMMA v=primitiveValue )+ { $rule = new OneOfMatcher($v.values); $value = $v.values[0]; }
+//   | 'format' COMMA f=string COMMA s=string { $rule = new FormatMatcher($f.contents); $value = $s.contents; $type = ValueType.String; }
 //   | DOLLAR ref=string { $reference = new MatchingReference($ref.contents); $type = ValueType.Unknown; }
+//   | 'null' { $rule = NullMatcher.INSTANCE; $type = ValueType.Unknown; }
 //   ;
 fn parse_matching_rule(lex: &mut logos::Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::Result<(String, ValueType, Option<MatchingRule>, Option<Generator>, Option<MatchingReference>)> {
   let next = lex.next()
@@ -532,6 +672,10 @@ fn parse_matching_rule(lex: &mut logos::Lexer<MatcherDefinitionToken>, v: &str)
       "boolean" => parse_boolean(lex, v),
       "contentType" => parse_content_type(lex, v),
       "semver" => parse_semver(lex, v),
+      "numberRange" => parse_number_range(lex, v),
+      "stringLength" => parse_string_length(lex, v),
+      "oneOf" => parse_one_of(lex, v),
+      "format" => parse_format(lex, v),
       _ => {
         let mut buffer = BytesMut::new().writer();
         let span = lex.span();
@@ -539,7 +683,7 @@ fn parse_matching_rule(lex: &mut logos::Lexer<MatcherDefinitionToken>, v: &str)
           .with_config(Config::default().with_color(false))
           .with_message(format!("Expected the type of matcher, got '{}'", lex.slice()))
           .with_label(Label::new(("expression", span)).with_message("This is not a valid matcher type"))
-          .with_note("Valid matchers are: equalTo, regex, type, datetime, date, time, include, number, integer, decimal, boolean, contentType, semver")
+          .with_note("Valid matchers are: equalTo, regex, type, datetime, date, time, include, number, integer, decimal, boolean, contentType, semver, numberRange, stringLength, oneOf, format")
           .finish();
         report.write(("expression", Source::from(v)), &mut buffer)?;
         let message = from_utf8(&*buffer.get_ref())?.to_string();
@@ -548,6 +692,8 @@ fn parse_matching_rule(lex: &mut logos::Lexer<MatcherDefinitionToken>, v: &str)
     }
   } else if next == MatcherDefinitionToken::Dollar {
     parse_reference(lex, v)
+  } else if next == MatcherDefinitionToken::Null {
+    Ok(("".to_string(), ValueType::Unknown, Some(MatchingRule::Null), None, None))
   } else {
     let mut buffer = BytesMut::new().writer();
     let span = lex.span();
@@ -589,6 +735,159 @@ fn parse_semver(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::Res
   }
 }
 
+// COMMA min=optionalNumber COMMA max=optionalNumber COMMA val=( DECIMAL_LITERAL | INTEGER_LITERAL ) { $value = $val.getText(); $type = ValueType.Number; }
+fn parse_number_range(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::Result<(String, ValueType, Option<MatchingRule>, Option<Generator>, Option<MatchingReference>)> {
+  parse_comma(lex, v)?;
+  let min = parse_optional_number(lex, v)?;
+  parse_comma(lex, v)?;
+  let max = parse_optional_number(lex, v)?;
+  if let (Some(min), Some(max)) = (min, max) {
+    if min > max {
+      return Err(anyhow!("numberRange minimum {} is greater than the maximum {}", min, max));
+    }
+  }
+  parse_comma(lex, v)?;
+  let next = lex.next().ok_or_else(|| end_of_expression(v, "a number"))?;
+  let (value, generator) = match next {
+    MatcherDefinitionToken::Int(_) => (lex.slice().to_string(), range_generator(min, max)),
+    MatcherDefinitionToken::Decimal => (lex.slice().to_string(), None),
+    _ => return Err(anyhow!("expected a number, got '{}'", lex.slice()))
+  };
+  Ok((value, ValueType::Number, Some(MatchingRule::NumberRange(min, max)), generator, None))
+}
+
+// COMMA min=optionalInteger COMMA max=optionalInteger COMMA s=string { $value = $s.contents; $type = ValueType.String; }
+fn parse_string_length(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::Result<(String, ValueType, Option<MatchingRule>, Option<Generator>, Option<MatchingReference>)> {
+  parse_comma(lex, v)?;
+  let min = parse_optional_length(lex, v)?;
+  parse_comma(lex, v)?;
+  let max = parse_optional_length(lex, v)?;
+  if let (Some(min), Some(max)) = (min, max) {
+    if min > max {
+      return Err(anyhow!("stringLength minimum {} is greater than the maximum {}", min, max));
+    }
+  }
+  parse_comma(lex, v)?;
+  let value = parse_string(lex, v)?;
+  let length = value.chars().count()
+    .max(min.unwrap_or_default())
+    .min(max.unwrap_or(usize::MAX))
+    .min(u16::MAX as usize);
+  Ok((value, ValueType::String, Some(MatchingRule::StringLength(min, max)),
+    Some(Generator::RandomString(length as u16)), None))
+}
+
+// ( COMMA v=primitiveValue )+ { $value = $v.values[0]; }
+fn parse_one_of(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::Result<(String, ValueType, Option<MatchingRule>, Option<Generator>, Option<MatchingReference>)> {
+  let mut values = vec![];
+  let mut value_type = ValueType::Unknown;
+  parse_comma(lex, v)?;
+  loop {
+    let next = lex.next().ok_or_else(|| end_of_expression(v, "a primitive value"))?;
+    let (value, item_type) = match next {
+      MatcherDefinitionToken::String => (json!(lex.slice().trim_matches('\'')), ValueType::String),
+      MatcherDefinitionToken::Int(i) => (json!(i), ValueType::Integer),
+      MatcherDefinitionToken::Decimal => (json!(lex.slice().parse::<f64>()?), ValueType::Decimal),
+      MatcherDefinitionToken::Boolean => (json!(lex.slice() == "true"), ValueType::Boolean),
+      MatcherDefinitionToken::Null => (Value::Null, ValueType::Unknown),
+      _ => return Err(anyhow!("expected a primitive value, got '{}'", lex.slice()))
+    };
+    values.push(value);
+    value_type = if values.len() == 1 { item_type } else { value_type.merge(item_type) };
+
+    if lex.remainder().trim_start().starts_with(',') {
+      parse_comma(lex, v)?;
+    } else {
+      break;
+    }
+  }
+  Ok((json_to_string(&values[0]), value_type, Some(MatchingRule::OneOf(values.clone())),
+    Some(Generator::RandomChoice(values)), None))
+}
+
+// COMMA f=string COMMA s=string { $rule = new FormatMatcher($f.contents); $value = $s.contents; $type = ValueType.String; }
+fn parse_format(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::Result<(String, ValueType, Option<MatchingRule>, Option<Generator>, Option<MatchingReference>)> {
+  parse_comma(lex, v)?;
+  let format = match StringFormat::from_str(parse_string(lex, v)?.as_str()) {
+    Ok(format) => format,
+    Err(err) => {
+      let mut buffer = BytesMut::new().writer();
+      let span = lex.span();
+      let report = Report::build(ReportKind::Error, "expression", span.start)
+        .with_config(Config::default().with_color(false))
+        .with_message(format!("Expected a string format, got {}", lex.slice()))
+        .with_label(Label::new(("expression", span)).with_message("This is not a valid format"))
+        .with_note(err.to_string())
+        .finish();
+      report.write(("expression", Source::from(v)), &mut buffer)?;
+      let message = from_utf8(&*buffer.get_ref())?.to_string();
+      return Err(anyhow!(message));
+    }
+  };
+  parse_comma(lex, v)?;
+  let value = parse_string(lex, v)?;
+
+  match format.validate(value.as_str()) {
+    Ok(_) => {
+      let generator = match format {
+        StringFormat::Uuid(uuid_format) => Generator::Uuid(uuid_format),
+        _ => Generator::Format(format)
+      };
+      Ok((value, ValueType::String, Some(MatchingRule::Format(format)), Some(generator), None))
+    },
+    Err(err) => {
+      let mut buffer = BytesMut::new().writer();
+      let span = lex.span();
+      let report = Report::build(ReportKind::Error, "expression", span.start)
+        .with_config(Config::default().with_color(false))
+        .with_message(err.to_string())
+        .with_label(Label::new(("expression", span)).with_message(format!("This is not {}", format.description())))
+        .finish();
+      report.write(("expression", Source::from(v)), &mut buffer)?;
+      let message = from_utf8(&*buffer.get_ref())?.to_string();
+      Err(anyhow!(message))
+    }
+  }
+}
+
+// optionalNumber returns [ Double value ] :
+//   v=( DECIMAL_LITERAL | INTEGER_LITERAL ) { $value = Double.parseDouble($v.getText()); }
+//   | 'null'
+//   ;
+fn parse_optional_number(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::Result<Option<f64>> {
+  let next = lex.next().ok_or_else(|| end_of_expression(v, "a number or null"))?;
+  match next {
+    MatcherDefinitionToken::Int(i) => Ok(Some(i as f64)),
+    MatcherDefinitionToken::Decimal => Ok(Some(lex.slice().parse::<f64>()?)),
+    MatcherDefinitionToken::Null => Ok(None),
+    _ => Err(anyhow!("expected a number or null, got '{}'", lex.slice()))
+  }
+}
+
+fn parse_optional_length(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::Result<Option<usize>> {
+  let next = lex.next().ok_or_else(|| end_of_expression(v, "a length or null"))?;
+  match next {
+    MatcherDefinitionToken::Int(i) if i >= 0 => Ok(Some(i as usize)),
+    MatcherDefinitionToken::Null => Ok(None),
+    _ => Err(anyhow!("expected a non-negative integer length or null, got '{}'", lex.slice()))
+  }
+}
+
+/// Creates a random integer generator for the number range. Open bounds will use a range of 10.
+fn range_generator(min: Option<f64>, max: Option<f64>) -> Option<Generator> {
+  let (min, max) = match (min.map(f64::ceil), max.map(f64::floor)) {
+    (Some(min), Some(max)) => (min, max),
+    (Some(min), None) => (min, min + 10.0),
+    (None, Some(max)) => (max - 10.0, max),
+    (None, None) => (0.0, 10.0)
+  };
+  if min <= max && min >= i32::MIN as f64 && max <= i32::MAX as f64 {
+    Some(Generator::RandomInt(min as i32, max as i32))
+  } else {
+    None
+  }
+}
+
 //     COMMA v=primitiveValue { $value = $v.value; $type = $v.type; } )
 fn parse_equality(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::Result<(String, ValueType, Option<MatchingRule>, Option<Generator>, Option<MatchingReference>)> {
   parse_comma(lex, v)?;
@@ -783,9 +1082,11 @@ fn end_of_expression(v: &str, expected: &str) -> Error {
 #[cfg(test)]
 mod test {
   use expectest::prelude::*;
+  use serde_json::json;
   use trim_margin::MarginTrimmable;
 
   use crate::generators::Generator::{Date, DateTime, Time};
+  use crate::generators::UuidFormat;
   use crate::matchingrules::MatchingRule;
   use crate::matchingrules::MatchingRule::{Regex, Type};
 
@@ -1009,6 +1310,132 @@ mod test {
       ));
   }
 
+  #[test]
+  fn parse_number_range_matcher() {
+    expect!(super::parse_matcher_def("matching(numberRange, 1, 100, 50)").unwrap()).to(
+      be_equal_to(MatchingRuleDefinition::new("50".to_string(),
+                                              ValueType::Number,
+                                              MatchingRule::NumberRange(Some(1.0), Some(100.0)),
+                                              Some(Generator::RandomInt(1, 100)))));
+    expect!(super::parse_matcher_def("matching(numberRange, null, 10.5, 1.25)").unwrap()).to(
+      be_equal_to(MatchingRuleDefinition::new("1.25".to_string(),
+                                              ValueType::Number,
+                                              MatchingRule::NumberRange(None, Some(10.5)),
+                                              None)));
+    expect!(super::parse_matcher_def("matching(numberRange, -5, null, 0)").unwrap()).to(
+      be_equal_to(MatchingRuleDefinition::new("0".to_string(),
+                                              ValueType::Number,
+                                              MatchingRule::NumberRange(Some(-5.0), None),
+                                              Some(Generator::RandomInt(-5, 5)))));
+    expect!(super::parse_matcher_def("matching(numberRange, 1, 100)")).to(be_err());
+    expect!(super::parse_matcher_def("matching(numberRange, 1, 100, '50')")).to(be_err());
+    expect!(super::parse_matcher_def("matching(numberRange, 100, 1, 50)")).to(be_err());
+  }
+
+  #[test]
+  fn parse_string_length_matcher() {
+    expect!(super::parse_matcher_def("matching(stringLength, 3, 3, 'USD')").unwrap()).to(
+      be_equal_to(MatchingRuleDefinition::new("USD".to_string(),
+                                              ValueType::String,
+                                              MatchingRule::StringLength(Some(3), Some(3)),
+                                              Some(Generator::RandomString(3)))));
+    expect!(super::parse_matcher_def("matching(stringLength, 5, null, 'abc')").unwrap()).to(
+      be_equal_to(MatchingRuleDefinition::new("abc".to_string(),
+                                              ValueType::String,
+                                              MatchingRule::StringLength(Some(5), None),
+                                              Some(Generator::RandomString(5)))));
+    expect!(super::parse_matcher_def("matching(stringLength, 1, 10, 100)")).to(be_err());
+    expect!(super::parse_matcher_def("matching(stringLength, 1.5, 10, 'abc')")).to(be_err());
+    expect!(super::parse_matcher_def("matching(stringLength, -1, 10, 'abc')")).to(be_err());
+    expect!(super::parse_matcher_def("matching(stringLength, 10, 1, 'abc')")).to(be_err());
+  }
+
+  #[test]
+  fn parse_one_of_matcher() {
+    let values = vec![json!("AUD"), json!("EUR"), json!("USD")];
+    expect!(super::parse_matcher_def("matching(oneOf, 'AUD', 'EUR', 'USD')").unwrap()).to(
+      be_equal_to(MatchingRuleDefinition::new("AUD".to_string(),
+                                              ValueType::String,
+                                              MatchingRule::OneOf(values.clone()),
+                                              Some(Generator::RandomChoice(values)))));
+    let values = vec![json!(200), json!(201), json!(204)];
+    expect!(super::parse_matcher_def("matching(oneOf, 200, 201, 204)").unwrap()).to(
+      be_equal_to(MatchingRuleDefinition::new("200".to_string(),
+                                              ValueType::Integer,
+                                              MatchingRule::OneOf(values.clone()),
+                                              Some(Generator::RandomChoice(values)))));
+    expect!(super::parse_matcher_def("matching(oneOf, 'AUD'), notEmpty('AUD')").unwrap().rules.len()).to(be_equal_to(2));
+    expect!(super::parse_matcher_def("matching(oneOf)")).to(be_err());
+    expect!(super::parse_matcher_def("matching(oneOf, 'AUD',)")).to(be_err());
+  }
+
+  #[test]
+  fn parse_format_matcher() {
+    expect!(super::parse_matcher_def("matching(format, 'email', 'a@b.com')").unwrap()).to(
+      be_equal_to(MatchingRuleDefinition::new("a@b.com".to_string(),
+                                              ValueType::String,
+                                              MatchingRule::Format(StringFormat::Email),
+                                              Some(Generator::Format(StringFormat::Email)))));
+    expect!(super::parse_matcher_def("matching(format, 'uuid-urn', 'urn:uuid:6fa459ea-ee8a-3ca4-894e-db77e160355e')").unwrap()).to(
+      be_equal_to(MatchingRuleDefinition::new("urn:uuid:6fa459ea-ee8a-3ca4-894e-db77e160355e".to_string(),
+                                              ValueType::String,
+                                              MatchingRule::Format(StringFormat::Uuid(Some(UuidFormat::Urn))),
+                                              Some(Generator::Uuid(Some(UuidFormat::Urn))))));
+    expect!(super::parse_matcher_def("matching(format, 'ipv4', 100)")).to(be_err());
+    expect!(super::parse_matcher_def("matching(format, 'phone', '555 1234')")).to(be_err());
+
+    expect!(as_string!(super::parse_matcher_def("matching(format, 'ipv4', '300.1.1.1')"))).to(
+      be_err().value(
+        "|Error: Expected '300.1.1.1' to be an IPv4 address
+            |   ╭─[expression:1:26]
+            |   │
+            | 1 │ matching(format, 'ipv4', '300.1.1.1')
+            |   ·                          ─────┬───── \u{0020}
+            |   ·                               ╰─────── This is not an IPv4 address
+            |───╯
+            |
+            ".trim_margin().unwrap()));
+  }
+
+  #[test]
+  fn parse_combinators() {
+    expect!(super::parse_matcher_def("anyOf(matching(null), matching(date, 'yyyy-MM-dd', '2000-01-01'))").unwrap()).to(
+      be_equal_to(MatchingRuleDefinition::new("2000-01-01".to_string(),
+                                              ValueType::String,
+                                              MatchingRule::AnyOf(vec![
+                                                MatchingRule::Null,
+                                                MatchingRule::Date("yyyy-MM-dd".to_string())
+                                              ]),
+                                              Some(Date(Some("yyyy-MM-dd".to_string()), None)))));
+    expect!(super::parse_matcher_def("matching(type, 'Fred'), not(matching(regex, '^\\s*$', ''))").unwrap()).to(
+      be_equal_to(MatchingRuleDefinition {
+        value: "Fred".to_string(),
+        value_type: ValueType::String,
+        rules: vec![
+          Either::Left(Type),
+          Either::Left(MatchingRule::Not(Box::new(Regex("^\\s*$".to_string()))))
+        ],
+        generator: None
+      }));
+    expect!(super::parse_matcher_def("allOf(matching(integer, 10), not(anyOf(matching(numberRange, null, 0, 0), matching(regex, '^13$', '13'))))").unwrap()).to(
+      be_equal_to(MatchingRuleDefinition::new("10".to_string(),
+                                              ValueType::Integer,
+                                              MatchingRule::AllOf(vec![
+                                                MatchingRule::Integer,
+                                                MatchingRule::Not(Box::new(MatchingRule::AnyOf(vec![
+                                                  MatchingRule::NumberRange(None, Some(0.0)),
+                                                  Regex("^13$".to_string())
+                                                ])))
+                                              ]),
+                                              None)));
+    expect!(super::is_matcher_def("anyOf(matching(null), matching(type, 'a'))")).to(be_true());
+    expect!(super::is_matcher_def("not(matching(type, 'a'))")).to(be_true());
+    expect!(super::parse_matcher_def("not(matching(type, 'a'), matching(type, 'b'))")).to(be_err());
+    expect!(super::parse_matcher_def("anyOf()")).to(be_err());
+    expect!(super::parse_matcher_def("anyOf(matching(type, 'a')")).to(be_err());
+    expect!(super::parse_matcher_def("anyOf(matching($'person'))")).to(be_err());
+  }
+
   #[test]
   fn parse_matching_rule_test() {
     let mut lex = super::MatcherDefinitionToken::lexer("type, '1.0.0')");
@@ -1057,7 +1484,7 @@ mod test {
             |   ·       ────┬─── \u{0020}
             |   ·           ╰───── This is not a valid matcher type
             |   ·\u{0020}
-            |   · Note: Valid matchers are: equalTo, regex, type, datetime, date, time, include, number, integer, decimal, boolean, contentType, semver
+            |   · Note: Valid matchers are: equalTo, regex, type, datetime, date, time, include, number, integer, decimal, boolean, contentType, semver, numberRange, stringLength, oneOf, format
             |───╯
             |
             ".trim_margin().unwrap()));
@@ -1177,7 +1604,7 @@ mod test {
             |   ·    │\u{0020}
             |   ·    ╰─ Expected a matching rule definition here
             |   ·\u{0020}
-            |   · Note: valid matching rule definitions are: matching, notEmpty, eachKey, eachValue
+            |   · Note: valid matching rule definitions are: matching, notEmpty, eachKey, eachValue, not, anyOf, allOf
             |───╯
             |
             ".trim_margin().unwrap()));
@@ -1192,7 +1619,7 @@ mod test {
             |   · ──────┬────── \u{0020}
             |   ·       ╰──────── Expected a matching rule definition here
             |   ·\u{0020}
-            |   · Note: valid matching rule definitions are: matching, notEmpty, eachKey, eachValue
+            |   · Note: valid matching rule definitions are: matching, notEmpty, eachKey, eachValue, not, anyOf, allOf
             |───╯
             |
             ".trim_margin().unwrap()));
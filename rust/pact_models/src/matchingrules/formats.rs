@@ -0,0 +1,235 @@
+//! Well-known string formats that can be matched with the format matching rule
+
+use std::fmt::{Display, Formatter};
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::generators::UuidFormat;
+
+lazy_static! {
+  static ref EMAIL_LOCAL_PART: Regex = Regex::new(
+    r"^[A-Za-z0-9!#$%&'*+/=?^_`{|}~-]+(\.[A-Za-z0-9!#$%&'*+/=?^_`{|}~-]+)*$").unwrap();
+  static ref HOSTNAME_LABEL: Regex = Regex::new(r"^[A-Za-z0-9]([A-Za-z0-9-]{0,61}[A-Za-z0-9])?$").unwrap();
+  static ref URI: Regex = Regex::new(
+    r"^[A-Za-z][A-Za-z0-9+.\-]*:([A-Za-z0-9\-._~:/?#\[\]@!$&'()*+,;=]|%[0-9A-Fa-f]{2})*$").unwrap();
+  static ref SIMPLE_UUID: Regex = Regex::new(r"^[0-9a-fA-F]{32}$").unwrap();
+  static ref LOWER_CASE_UUID: Regex = Regex::new(
+    r"^[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$").unwrap();
+  static ref UPPER_CASE_UUID: Regex = Regex::new(
+    r"^[0-9A-F]{8}-[0-9A-F]{4}-[0-9A-F]{4}-[0-9A-F]{4}-[0-9A-F]{12}$").unwrap();
+  static ref URN_UUID: Regex = Regex::new(
+    r"^urn:uuid:[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$").unwrap();
+  static ref DURATION: Regex = Regex::new(
+    r"^P(\d+Y)?(\d+M)?(\d+W)?(\d+D)?(T(\d+H)?(\d+M)?(\d+([.,]\d+)?S)?)?$").unwrap();
+}
+
+/// Well-known string formats
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, Copy)]
+pub enum StringFormat {
+  /// Email address (e.g. someone@example.com)
+  Email,
+  /// Absolute URI as per RFC 3986 (e.g. https://example.com/path?query)
+  Uri,
+  /// Host name as per RFC 1123 (e.g. www.example.com)
+  Hostname,
+  /// IPv4 address in dotted decimal form (e.g. 192.168.0.1)
+  Ipv4,
+  /// IPv6 address (e.g. 2001:db8::1)
+  Ipv6,
+  /// UUID in the given format, or any of the UUID formats if not specified
+  Uuid(Option<UuidFormat>),
+  /// ISO-8601 duration (e.g. P1DT2H30M)
+  Duration
+}
+
+impl StringFormat {
+  /// Validates that the value is in this format
+  pub fn validate(&self, value: &str) -> anyhow::Result<()> {
+    let valid = match self {
+      StringFormat::Email => is_email(value),
+      StringFormat::Uri => URI.is_match(value),
+      StringFormat::Hostname => is_hostname(value),
+      StringFormat::Ipv4 => Ipv4Addr::from_str(value).is_ok(),
+      StringFormat::Ipv6 => Ipv6Addr::from_str(value).is_ok(),
+      StringFormat::Uuid(format) => match format {
+        Some(UuidFormat::Simple) => SIMPLE_UUID.is_match(value),
+        Some(UuidFormat::LowerCaseHyphenated) => LOWER_CASE_UUID.is_match(value),
+        Some(UuidFormat::UpperCaseHyphenated) => UPPER_CASE_UUID.is_match(value),
+        Some(UuidFormat::Urn) => URN_UUID.is_match(value),
+        None => SIMPLE_UUID.is_match(value) || LOWER_CASE_UUID.is_match(value) ||
+          UPPER_CASE_UUID.is_match(value) || URN_UUID.is_match(value)
+      }
+      StringFormat::Duration => value != "P" && !value.ends_with('T') && DURATION.is_match(value)
+    };
+    if valid {
+      Ok(())
+    } else {
+      Err(anyhow!("Expected '{}' to be {}", value, self.description()))
+    }
+  }
+
+  /// Description of the format to use in messages
+  pub fn description(&self) -> &'static str {
+    match self {
+      StringFormat::Email => "an email address",
+      StringFormat::Uri => "a URI",
+      StringFormat::Hostname => "a hostname",
+      StringFormat::Ipv4 => "an IPv4 address",
+      StringFormat::Ipv6 => "an IPv6 address",
+      StringFormat::Uuid(format) => match format {
+        Some(UuidFormat::Simple) => "a simple UUID",
+        Some(UuidFormat::LowerCaseHyphenated) => "a lower-case hyphenated UUID",
+        Some(UuidFormat::UpperCaseHyphenated) => "an upper-case hyphenated UUID",
+        Some(UuidFormat::Urn) => "a URN UUID",
+        None => "a UUID"
+      }
+      StringFormat::Duration => "an ISO-8601 duration"
+    }
+  }
+}
+
+impl Display for StringFormat {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      StringFormat::Email => write!(f, "email"),
+      StringFormat::Uri => write!(f, "uri"),
+      StringFormat::Hostname => write!(f, "hostname"),
+      StringFormat::Ipv4 => write!(f, "ipv4"),
+      StringFormat::Ipv6 => write!(f, "ipv6"),
+      StringFormat::Uuid(format) => match format {
+        Some(format) => write!(f, "uuid-{}", format.to_string().to_lowercase()),
+        None => write!(f, "uuid")
+      }
+      StringFormat::Duration => write!(f, "duration")
+    }
+  }
+}
+
+impl FromStr for StringFormat {
+  type Err = anyhow::Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "email" => Ok(StringFormat::Email),
+      "uri" => Ok(StringFormat::Uri),
+      "hostname" => Ok(StringFormat::Hostname),
+      "ipv4" => Ok(StringFormat::Ipv4),
+      "ipv6" => Ok(StringFormat::Ipv6),
+      "uuid" => Ok(StringFormat::Uuid(None)),
+      "duration" => Ok(StringFormat::Duration),
+      _ => match s.strip_prefix("uuid-") {
+        Some("urn") => Ok(StringFormat::Uuid(Some(UuidFormat::Urn))),
+        Some(format) => UuidFormat::from_str(format)
+          .map(|format| StringFormat::Uuid(Some(format)))
+          .map_err(|_| anyhow!("'{}' is not a valid string format", s)),
+        None => Err(anyhow!("'{}' is not a valid string format (valid formats are email, uri, hostname, \
+          ipv4, ipv6, uuid, uuid-simple, uuid-lower-case-hyphenated, uuid-upper-case-hyphenated, uuid-urn \
+          and duration)", s))
+      }
+    }
+  }
+}
+
+fn is_hostname(value: &str) -> bool {
+  !value.is_empty() && value.len() <= 253 && value.split('.').all(|label| HOSTNAME_LABEL.is_match(label))
+}
+
+fn is_email(value: &str) -> bool {
+  match value.rsplit_once('@') {
+    Some((local, domain)) => local.len() <= 64 && EMAIL_LOCAL_PART.is_match(local) && is_hostname(domain),
+    None => false
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+
+  use crate::generators::UuidFormat;
+
+  use super::*;
+
+  #[test]
+  fn string_format_from_str() {
+    expect!(StringFormat::from_str("email")).to(be_ok().value(StringFormat::Email));
+    expect!(StringFormat::from_str("uuid")).to(be_ok().value(StringFormat::Uuid(None)));
+    expect!(StringFormat::from_str("uuid-simple")).to(be_ok().value(StringFormat::Uuid(Some(UuidFormat::Simple))));
+    expect!(StringFormat::from_str("uuid-urn")).to(be_ok().value(StringFormat::Uuid(Some(UuidFormat::Urn))));
+    expect!(StringFormat::from_str("uuid-other")).to(be_err());
+    expect!(StringFormat::from_str("phone")).to(be_err());
+
+    for format in [StringFormat::Email, StringFormat::Uri, StringFormat::Hostname, StringFormat::Ipv4,
+      StringFormat::Ipv6, StringFormat::Uuid(None), StringFormat::Uuid(Some(UuidFormat::Simple)),
+      StringFormat::Uuid(Some(UuidFormat::LowerCaseHyphenated)), StringFormat::Uuid(Some(UuidFormat::UpperCaseHyphenated)),
+      StringFormat::Uuid(Some(UuidFormat::Urn)), StringFormat::Duration] {
+      expect!(StringFormat::from_str(format.to_string().as_str())).to(be_ok().value(format));
+    }
+  }
+
+  #[test]
+  fn validate_email_hostname_and_uri() {
+    expect!(StringFormat::Email.validate("a@b.com")).to(be_ok());
+    expect!(StringFormat::Email.validate("first.last+tag@mail.example.com")).to(be_ok());
+    expect!(StringFormat::Email.validate("a.@b.com")).to(be_err());
+    expect!(StringFormat::Email.validate("ab.com")).to(be_err());
+    expect!(StringFormat::Email.validate("a@-b.com").unwrap_err().to_string()).to(
+      be_equal_to("Expected 'a@-b.com' to be an email address"));
+
+    expect!(StringFormat::Hostname.validate("www.example.com")).to(be_ok());
+    expect!(StringFormat::Hostname.validate("localhost")).to(be_ok());
+    expect!(StringFormat::Hostname.validate("www..example.com")).to(be_err());
+    expect!(StringFormat::Hostname.validate("under_score.com")).to(be_err());
+
+    expect!(StringFormat::Uri.validate("https://example.com/path?a=1&b=%20#frag")).to(be_ok());
+    expect!(StringFormat::Uri.validate("urn:isbn:0451450523")).to(be_ok());
+    expect!(StringFormat::Uri.validate("/relative/path")).to(be_err());
+    expect!(StringFormat::Uri.validate("http://example.com/a b")).to(be_err());
+    expect!(StringFormat::Uri.validate("http://example.com/%zz")).to(be_err());
+  }
+
+  #[test]
+  fn validate_ip_addresses() {
+    expect!(StringFormat::Ipv4.validate("192.168.0.1")).to(be_ok());
+    expect!(StringFormat::Ipv4.validate("256.1.1.1")).to(be_err());
+    expect!(StringFormat::Ipv4.validate("::1")).to(be_err());
+    expect!(StringFormat::Ipv6.validate("2001:db8::1")).to(be_ok());
+    expect!(StringFormat::Ipv6.validate("::ffff:192.168.0.1")).to(be_ok());
+    expect!(StringFormat::Ipv6.validate("192.168.0.1")).to(be_err());
+  }
+
+  #[test]
+  fn validate_uuids() {
+    let simple = "936DA01f9abd4d9d80c702af85c822a8";
+    let lower = "936da01f-9abd-4d9d-80c7-02af85c822a8";
+    let upper = "936DA01F-9ABD-4D9D-80C7-02AF85C822A8";
+    let urn = "urn:uuid:936da01f-9abd-4d9d-80c7-02af85c822a8";
+    for value in [simple, lower, upper, urn] {
+      expect!(StringFormat::Uuid(None).validate(value)).to(be_ok());
+    }
+    expect!(StringFormat::Uuid(None).validate("936da01f-9abd")).to(be_err());
+
+    expect!(StringFormat::Uuid(Some(UuidFormat::Simple)).validate(simple)).to(be_ok());
+    expect!(StringFormat::Uuid(Some(UuidFormat::Simple)).validate(lower)).to(be_err());
+    expect!(StringFormat::Uuid(Some(UuidFormat::LowerCaseHyphenated)).validate(lower)).to(be_ok());
+    expect!(StringFormat::Uuid(Some(UuidFormat::LowerCaseHyphenated)).validate(upper)).to(be_err());
+    expect!(StringFormat::Uuid(Some(UuidFormat::UpperCaseHyphenated)).validate(upper)).to(be_ok());
+    expect!(StringFormat::Uuid(Some(UuidFormat::Urn)).validate(urn)).to(be_ok());
+    expect!(StringFormat::Uuid(Some(UuidFormat::Urn)).validate(lower).unwrap_err().to_string()).to(
+      be_equal_to("Expected '936da01f-9abd-4d9d-80c7-02af85c822a8' to be a URN UUID"));
+  }
+
+  #[test]
+  fn validate_durations() {
+    for value in ["P1Y2M3DT4H5M6S", "P3W", "PT0.5S", "P1D", "PT36H"] {
+      expect!(StringFormat::Duration.validate(value)).to(be_ok());
+    }
+    for value in ["P", "PT", "P1DT", "1D", "P1H", "PT1D", "P1.5D"] {
+      expect!(StringFormat::Duration.validate(value)).to(be_err());
+    }
+  }
+}
@@ -0,0 +1,204 @@
+//! Static checks for matching rules that can never be applied (dead rules). A matching rule is
+//! dead if its path does not match any path in the example body, headers or query parameters of
+//! the request, response or message it is configured on. These normally result from the example
+//! being changed without updating the matching rules.
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use itertools::Itertools;
+use serde_json::Value;
+
+use crate::http_parts::HttpPart;
+use crate::matchingrules::{Category, RuleList};
+use crate::path_exp::DocPath;
+
+/// Matching rule with a path that does not match anything in the example
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeadMatchingRule {
+  /// Category of the matching rule
+  pub category: Category,
+  /// Path expression of the matching rule
+  pub path: DocPath,
+  /// Matching rules configured for the path
+  pub rules: RuleList
+}
+
+impl Display for DeadMatchingRule {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    let category: String = self.category.clone().into();
+    write!(f, "Matching rule for {} path '{}' does not match anything in the example", category, self.path)
+  }
+}
+
+/// Checks all the matching rules configured on the HTTP part (request, response or message)
+/// against the example body, headers and query parameters, and returns any rules whose paths do
+/// not match anything. Only JSON bodies are checked, and rules for the path, status and metadata
+/// are ignored.
+pub fn dead_matching_rules(
+  part: &dyn HttpPart,
+  query: Option<&HashMap<String, Vec<String>>>
+) -> Vec<DeadMatchingRule> {
+  let mut dead_rules = vec![];
+
+  for (category, rules) in &part.matching_rules().rules {
+    let is_dead: Box<dyn Fn(&DocPath) -> bool> = match category {
+      Category::BODY | Category::CONTENTS => {
+        let body = part.body();
+        if body.is_present() {
+          if part.content_type().unwrap_or_default().is_json() {
+            match body.value().map(|value| serde_json::from_slice::<Value>(&value)) {
+              Some(Ok(json)) => {
+                let paths = json_paths(&json);
+                Box::new(move |path| !paths.iter().any(|p| {
+                  let p = p.iter().map(|s| s.as_str()).collect_vec();
+                  path.matches_path_exactly(p.as_slice())
+                }))
+              }
+              _ => continue
+            }
+          } else {
+            continue
+          }
+        } else {
+          Box::new(|_| true)
+        }
+      }
+      Category::HEADER => {
+        let headers = part.headers().clone().unwrap_or_default();
+        Box::new(move |path| match path.first_field() {
+          Some(name) => !headers.keys().any(|key| key.eq_ignore_ascii_case(name)),
+          None => false
+        })
+      }
+      Category::QUERY => {
+        let query = query.cloned().unwrap_or_default();
+        Box::new(move |path| match path.first_field() {
+          Some(name) => !query.contains_key(name),
+          None => false
+        })
+      }
+      _ => continue
+    };
+
+    for (path, rule_list) in &rules.rules {
+      if is_dead(path) {
+        dead_rules.push(DeadMatchingRule {
+          category: category.clone(),
+          path: path.clone(),
+          rules: rule_list.clone()
+        });
+      }
+    }
+  }
+
+  dead_rules.sort_by(|a, b| a.category.cmp(&b.category)
+    .then_with(|| a.path.to_string().cmp(&b.path.to_string())));
+  dead_rules
+}
+
+/// Returns all the paths in the JSON document, including the root
+fn json_paths(json: &Value) -> Vec<Vec<String>> {
+  let mut paths = vec![];
+  collect_json_paths(json, &mut vec!["$".to_string()], &mut paths);
+  paths
+}
+
+fn collect_json_paths(json: &Value, path: &mut Vec<String>, paths: &mut Vec<Vec<String>>) {
+  paths.push(path.clone());
+  match json {
+    Value::Object(map) => for (key, value) in map {
+      path.push(key.clone());
+      collect_json_paths(value, path, paths);
+      path.pop();
+    }
+    Value::Array(items) => for (index, value) in items.iter().enumerate() {
+      path.push(index.to_string());
+      collect_json_paths(value, path, paths);
+      path.pop();
+    }
+    _ => {}
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use maplit::hashmap;
+  use serde_json::json;
+
+  use crate::bodies::OptionalBody;
+  use crate::matchingrules;
+  use crate::matchingrules::MatchingRule;
+  use crate::request::Request;
+  use crate::response::Response;
+
+  use super::*;
+
+  #[test]
+  fn dead_matching_rules_returns_rules_that_do_not_match_the_example() {
+    let request = Request {
+      query: Some(hashmap!{ "page".to_string() => vec!["1".to_string()] }),
+      headers: Some(hashmap!{ "Content-Type".to_string() => vec!["application/json".to_string()] }),
+      body: OptionalBody::from(&json!({
+        "id": 100,
+        "items": [ { "name": "a" } ]
+      })),
+      matching_rules: matchingrules! {
+        "body" => {
+          "$.id" => [ MatchingRule::Integer ],
+          "$.items" => [ MatchingRule::MinType(1) ],
+          "$.items[*].name" => [ MatchingRule::Type ],
+          "$.items[*].price" => [ MatchingRule::Decimal ],
+          "$.name" => [ MatchingRule::Type ]
+        },
+        "header" => {
+          "content-type" => [ MatchingRule::Regex("application/.*json".to_string()) ],
+          "X-Request-Id" => [ MatchingRule::Type ]
+        },
+        "query" => {
+          "page" => [ MatchingRule::Integer ],
+          "size" => [ MatchingRule::Integer ]
+        },
+        "path" => {
+          "" => [ MatchingRule::Type ]
+        }
+      },
+      .. Request::default()
+    };
+
+    let dead_rules = dead_matching_rules(&request, request.query.as_ref());
+    expect!(dead_rules.iter().map(|rule| (rule.category.clone(), rule.path.to_string())).collect_vec()).to(be_equal_to(vec![
+      (Category::HEADER, "X-Request-Id".to_string()),
+      (Category::QUERY, "size".to_string()),
+      (Category::BODY, "$.items[*].price".to_string()),
+      (Category::BODY, "$.name".to_string())
+    ]));
+    expect!(dead_rules[3].to_string()).to(be_equal_to(
+      "Matching rule for body path '$.name' does not match anything in the example"));
+  }
+
+  #[test]
+  fn dead_matching_rules_with_a_missing_body() {
+    let response = Response {
+      matching_rules: matchingrules! {
+        "body" => { "$.id" => [ MatchingRule::Integer ] }
+      },
+      .. Response::default()
+    };
+    let dead_rules = dead_matching_rules(&response, None);
+    expect!(dead_rules.iter().map(|rule| rule.path.to_string()).collect_vec()).to(be_equal_to(vec!["$.id".to_string()]));
+  }
+
+  #[test]
+  fn dead_matching_rules_ignores_bodies_that_are_not_json() {
+    let response = Response {
+      body: OptionalBody::Present("<id>100</id>".into(), Some("application/xml".into()), None),
+      matching_rules: matchingrules! {
+        "body" => { "$.id" => [ MatchingRule::Integer ] }
+      },
+      .. Response::default()
+    };
+    expect!(dead_matching_rules(&response, None)).to(be_equal_to(vec![]));
+  }
+}
@@ -12,6 +12,7 @@ use anyhow::{anyhow, Context as _};
 #[cfg(test)] use expectest::prelude::*;
 use itertools::Either;
 use maplit::hashmap;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Map, Value};
 use tracing::{error, trace};
 
@@ -19,9 +20,12 @@ use crate::{HttpStatus, PactSpecification};
 use crate::generators::{Generator, GeneratorCategory, Generators};
 use crate::json_utils::{json_to_num, json_to_string};
 use crate::matchingrules::expressions::{MatchingReference, MatchingRuleDefinition, ValueType};
+use crate::matchingrules::formats::StringFormat;
 use crate::path_exp::DocPath;
 
 pub mod expressions;
+pub mod formats;
+pub mod lint;
 
 fn generator_from_json(json: &Map<String, Value>) -> Option<Generator> {
   if let Some(generator_json) = json.get("generator") {
@@ -59,8 +63,59 @@ fn rules_from_json(attributes: &Map<String, Value>) -> anyhow::Result<Vec<Either
   }
 }
 
+fn nested_rules_from_json(matcher: &str, attributes: &Map<String, Value>) -> anyhow::Result<Vec<MatchingRule>> {
+  match attributes.get("rules") {
+    Some(Value::Array(rules)) if !rules.is_empty() => rules.iter()
+      .map(|rule| MatchingRule::from_json(rule)
+        .with_context(|| format!("{} matcher 'rules' field contains an invalid matching rule", matcher)))
+      .collect(),
+    Some(_) => Err(anyhow!("{} matcher 'rules' field must be a non-empty Array", matcher)),
+    None => Err(anyhow!("{} matcher missing 'rules' field", matcher))
+  }
+}
+
+/// Parses a bound of the StringLength matcher, which must be a non-negative integer if present
+fn string_length_bound(attributes: &Map<String, Value>, field: &str) -> anyhow::Result<Option<usize>> {
+  let bound = match attributes.get(field) {
+    None | Some(Value::Null) => return Ok(None),
+    Some(Value::Number(num)) => num.as_u64().map(|num| num as usize),
+    Some(Value::String(s)) => usize::from_str(s).ok(),
+    Some(_) => None
+  };
+  bound.map(Some)
+    .ok_or_else(|| anyhow!("StringLength matcher '{}' field must be a non-negative integer", field))
+}
+
+/// Tolerance used when matching numbers approximately
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum NumberTolerance {
+  /// Actual number must be within the given amount of the expected number
+  Absolute(f64),
+  /// Actual number must be within the given fraction of the expected number (i.e. 0.01 is 1%)
+  Relative(f64)
+}
+
+impl NumberTolerance {
+  /// Returns the largest allowed difference between the expected number and an actual number
+  pub fn allowed_difference(&self, expected: f64) -> f64 {
+    match self {
+      NumberTolerance::Absolute(tolerance) => *tolerance,
+      NumberTolerance::Relative(tolerance) => tolerance * expected.abs()
+    }
+  }
+}
+
+impl Display for NumberTolerance {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      NumberTolerance::Absolute(tolerance) => write!(f, "{}", tolerance),
+      NumberTolerance::Relative(tolerance) => write!(f, "a relative tolerance of {}", tolerance)
+    }
+  }
+}
+
 /// Set of all matching rules
-#[derive(Debug, Clone, Eq)]
+#[derive(Debug, Clone)]
 pub enum MatchingRule {
   /// Matcher using equals
   Equality,
@@ -94,6 +149,8 @@ pub enum MatchingRule {
   ContentType(String),
   /// Match array items in any order against a list of variants
   ArrayContains(Vec<(usize, MatchingRuleCategory, HashMap<DocPath, Generator>)>),
+  /// Match arrays that contain the same items in any order (and no other items)
+  EqualsIgnoreOrder,
   /// Matcher for values in a map, ignoring the keys
   Values,
   /// Matches boolean values (booleans and the string values `true` and `false`)
@@ -107,7 +164,28 @@ pub enum MatchingRule {
   /// Matcher for keys in a map
   EachKey(MatchingRuleDefinition),
   /// Matcher for values in a collection. This delegates to the Values matcher for maps.
-  EachValue(MatchingRuleDefinition)
+  EachValue(MatchingRuleDefinition),
+  /// Match if the value is a number within the (inclusive) minimum and maximum bounds
+  NumberRange(Option<f64>, Option<f64>),
+  /// Match if the value is a string with a length (in characters) within the minimum and maximum bounds
+  StringLength(Option<usize>, Option<usize>),
+  /// Match if the value is one of the given values
+  OneOf(Vec<Value>),
+  /// Match if the value does not match the given rule
+  Not(Box<MatchingRule>),
+  /// Match if the value matches at least one of the given rules
+  AnyOf(Vec<MatchingRule>),
+  /// Match if the value matches all of the given rules
+  AllOf(Vec<MatchingRule>),
+  /// Match if the value validates against the JSON Schema
+  JsonSchema(Value),
+  /// Match if the value is a string in a well-known format (email, URI, UUID, etc.)
+  Format(StringFormat),
+  /// Match if the value is a number within the tolerance of the expected number
+  Approximately(NumberTolerance),
+  /// Match if the value is a JWT, applying the nested rules to the decoded token (i.e. `$.claims.sub`).
+  /// The signature of the token is not verified.
+  Jwt(MatchingRuleCategory)
 }
 
 impl MatchingRule {
@@ -191,6 +269,7 @@ impl MatchingRule {
           json
         }).collect::<Vec<Value>>()
       }),
+      MatchingRule::EqualsIgnoreOrder => json!({ "match": "ignore-order" }),
       MatchingRule::Values => json!({ "match": "values" }),
       MatchingRule::StatusCode(status) => json!({ "match": "statusCode", "status": status.to_json() }),
       MatchingRule::NotEmpty => json!({ "match": "notEmpty" }),
@@ -233,6 +312,43 @@ impl MatchingRule {
 
         Value::Object(map.clone())
       }
+      MatchingRule::NumberRange(min, max) => {
+        let mut json = json!({ "match": "numberRange" });
+        if let Some(min) = min {
+          json["min"] = number_to_json(*min);
+        }
+        if let Some(max) = max {
+          json["max"] = number_to_json(*max);
+        }
+        json
+      }
+      MatchingRule::StringLength(min, max) => {
+        let mut json = json!({ "match": "stringLength" });
+        if let Some(min) = min {
+          json["min"] = json!(*min as u64);
+        }
+        if let Some(max) = max {
+          json["max"] = json!(*max as u64);
+        }
+        json
+      }
+      MatchingRule::OneOf(values) => json!({ "match": "oneOf", "values": values }),
+      MatchingRule::Not(rule) => json!({ "match": "not", "rule": rule.to_json() }),
+      MatchingRule::AnyOf(rules) => json!({
+        "match": "anyOf",
+        "rules": rules.iter().map(|rule| rule.to_json()).collect::<Vec<Value>>()
+      }),
+      MatchingRule::AllOf(rules) => json!({
+        "match": "allOf",
+        "rules": rules.iter().map(|rule| rule.to_json()).collect::<Vec<Value>>()
+      }),
+      MatchingRule::JsonSchema(schema) => json!({ "match": "jsonSchema", "schema": schema }),
+      MatchingRule::Format(format) => json!({ "match": "format", "format": format.to_string() }),
+      MatchingRule::Approximately(tolerance) => match tolerance {
+        NumberTolerance::Absolute(tolerance) => json!({ "match": "approximately", "absolute": number_to_json(*tolerance) }),
+        NumberTolerance::Relative(tolerance) => json!({ "match": "approximately", "relative": number_to_json(*tolerance) })
+      }
+      MatchingRule::Jwt(rules) => json!({ "match": "jwt", "rules": rules.to_v3_json() })
     }
   }
 
@@ -272,13 +388,24 @@ impl MatchingRule {
       MatchingRule::Null => "null",
       MatchingRule::ContentType(_) => "content-type",
       MatchingRule::ArrayContains(_) => "array-contains",
+      MatchingRule::EqualsIgnoreOrder => "ignore-order",
       MatchingRule::Values => "values",
       MatchingRule::Boolean => "boolean",
       MatchingRule::StatusCode(_) => "status-code",
       MatchingRule::NotEmpty => "not-empty",
       MatchingRule::Semver => "semver",
       MatchingRule::EachKey(_) => "each-key",
-      MatchingRule::EachValue(_) => "each-value"
+      MatchingRule::EachValue(_) => "each-value",
+      MatchingRule::NumberRange(_, _) => "number-range",
+      MatchingRule::StringLength(_, _) => "string-length",
+      MatchingRule::OneOf(_) => "one-of",
+      MatchingRule::Not(_) => "not",
+      MatchingRule::AnyOf(_) => "any-of",
+      MatchingRule::AllOf(_) => "all-of",
+      MatchingRule::JsonSchema(_) => "json-schema",
+      MatchingRule::Format(_) => "format",
+      MatchingRule::Approximately(_) => "approximately",
+      MatchingRule::Jwt(_) => "jwt"
     }.to_string()
   }
 
@@ -308,6 +435,7 @@ impl MatchingRule {
           }).collect())])
         }).collect()
       },
+      MatchingRule::EqualsIgnoreOrder => empty,
       MatchingRule::Values => empty,
       MatchingRule::Boolean => empty,
       MatchingRule::StatusCode(sc) => hashmap!{ "status" => sc.to_json() },
@@ -330,6 +458,38 @@ impl MatchingRule {
 
         map
       }
+      MatchingRule::NumberRange(min, max) => {
+        let mut map = hashmap!{};
+        if let Some(min) = min {
+          map.insert("min", number_to_json(*min));
+        }
+        if let Some(max) = max {
+          map.insert("max", number_to_json(*max));
+        }
+        map
+      }
+      MatchingRule::StringLength(min, max) => {
+        let mut map = hashmap!{};
+        if let Some(min) = min {
+          map.insert("min", json!(min));
+        }
+        if let Some(max) = max {
+          map.insert("max", json!(max));
+        }
+        map
+      }
+      MatchingRule::OneOf(values) => hashmap!{ "values" => Value::Array(values.clone()) },
+      MatchingRule::Not(rule) => hashmap!{ "rule" => rule.to_json() },
+      MatchingRule::AnyOf(rules) | MatchingRule::AllOf(rules) => hashmap!{
+        "rules" => Value::Array(rules.iter().map(|rule| rule.to_json()).collect())
+      },
+      MatchingRule::JsonSchema(schema) => hashmap!{ "schema" => schema.clone() },
+      MatchingRule::Format(format) => hashmap!{ "format" => Value::String(format.to_string()) },
+      MatchingRule::Approximately(tolerance) => match tolerance {
+        NumberTolerance::Absolute(tolerance) => hashmap!{ "absolute" => number_to_json(*tolerance) },
+        NumberTolerance::Relative(tolerance) => hashmap!{ "relative" => number_to_json(*tolerance) }
+      }
+      MatchingRule::Jwt(rules) => hashmap!{ "rules" => rules.to_v3_json() }
     }
   }
 
@@ -463,6 +623,80 @@ impl MatchingRule {
         };
         Ok(MatchingRule::EachValue(definition))
       }
+      "numberRange" | "number-range" => {
+        let min = attributes.get("min").and_then(|min| min.as_f64());
+        let max = attributes.get("max").and_then(|max| max.as_f64());
+        if min.is_none() && max.is_none() {
+          Err(anyhow!("NumberRange matcher requires a numeric 'min' or 'max' field"))
+        } else if matches!((min, max), (Some(min), Some(max)) if min > max) {
+          Err(anyhow!("NumberRange matcher 'min' field must not be greater than the 'max' field"))
+        } else {
+          Ok(MatchingRule::NumberRange(min, max))
+        }
+      }
+      "stringLength" | "string-length" => {
+        let min = string_length_bound(&attributes, "min")?;
+        let max = string_length_bound(&attributes, "max")?;
+        if min.is_none() && max.is_none() {
+          Err(anyhow!("StringLength matcher requires a 'min' or 'max' field"))
+        } else if matches!((min, max), (Some(min), Some(max)) if min > max) {
+          Err(anyhow!("StringLength matcher 'min' field must not be greater than the 'max' field"))
+        } else {
+          Ok(MatchingRule::StringLength(min, max))
+        }
+      }
+      "oneOf" | "one-of" => match attributes.get("values") {
+        Some(Value::Array(values)) if !values.is_empty() => Ok(MatchingRule::OneOf(values.clone())),
+        Some(_) => Err(anyhow!("OneOf matcher 'values' field must be a non-empty Array")),
+        None => Err(anyhow!("OneOf matcher missing 'values' field"))
+      }
+      "not" => match attributes.get("rule") {
+        Some(rule) => {
+          let rule = MatchingRule::from_json(rule)
+            .context("Not matcher 'rule' field is not a valid matching rule")?;
+          Ok(MatchingRule::Not(Box::new(rule)))
+        }
+        None => Err(anyhow!("Not matcher missing 'rule' field"))
+      }
+      "format" => match attributes.get("format") {
+        Some(format) => Ok(MatchingRule::Format(StringFormat::from_str(json_to_string(format).as_str())?)),
+        None => Err(anyhow!("Format matcher missing 'format' field"))
+      }
+      "jsonSchema" | "json-schema" => match attributes.get("schema") {
+        Some(schema) if schema.is_object() || schema.is_boolean() => Ok(MatchingRule::JsonSchema(schema.clone())),
+        Some(_) => Err(anyhow!("JsonSchema matcher 'schema' field must be an Object or a Boolean")),
+        None => Err(anyhow!("JsonSchema matcher missing 'schema' field"))
+      }
+      "ignore-order" | "equalsIgnoreOrder" => if attributes.contains_key("min") || attributes.contains_key("max") {
+        Err(anyhow!("EqualsIgnoreOrder matcher does not support 'min' or 'max' fields"))
+      } else {
+        Ok(MatchingRule::EqualsIgnoreOrder)
+      }
+      "approximately" => {
+        let tolerance = match (attributes.get("absolute"), attributes.get("relative")) {
+          (Some(absolute), None) => NumberTolerance::Absolute(absolute.as_f64()
+            .ok_or_else(|| anyhow!("Approximately matcher 'absolute' field must be a number"))?),
+          (None, Some(relative)) => NumberTolerance::Relative(relative.as_f64()
+            .ok_or_else(|| anyhow!("Approximately matcher 'relative' field must be a number"))?),
+          (Some(_), Some(_)) => return Err(anyhow!("Approximately matcher can only have one of the 'absolute' or 'relative' fields")),
+          (None, None) => return Err(anyhow!("Approximately matcher requires an 'absolute' or 'relative' field"))
+        };
+        match tolerance {
+          NumberTolerance::Absolute(value) | NumberTolerance::Relative(value) if value < 0.0 =>
+            Err(anyhow!("Approximately matcher tolerance must not be negative")),
+          _ => Ok(MatchingRule::Approximately(tolerance))
+        }
+      }
+      "jwt" => {
+        let mut category = MatchingRuleCategory::empty("body");
+        if let Some(rules) = attributes.get("rules") {
+          category.add_rules_from_json(rules)
+            .with_context(|| format!("Unable to parse JWT matching rules: {:?}", rules))?;
+        }
+        Ok(MatchingRule::Jwt(category))
+      }
+      "anyOf" | "any-of" => Ok(MatchingRule::AnyOf(nested_rules_from_json("AnyOf", &attributes)?)),
+      "allOf" | "all-of" => Ok(MatchingRule::AllOf(nested_rules_from_json("AllOf", &attributes)?)),
       _ => Err(anyhow!("{} is not a valid matching rule type", rule_type)),
     }
   }
@@ -476,6 +710,11 @@ impl MatchingRule {
     }
   }
 
+  /// If this matching rule combines other matching rules (not, anyOf or allOf)
+  pub fn is_combinator(&self) -> bool {
+    matches!(self, MatchingRule::Not(_) | MatchingRule::AnyOf(_) | MatchingRule::AllOf(_))
+  }
+
   /// If this matcher should cascade to children
   pub fn can_cascade(&self) -> bool {
     match self {
@@ -513,6 +752,38 @@ impl Hash for MatchingRule {
           }
         }
       }
+      MatchingRule::NumberRange(min, max) => {
+        min.map(f64::to_bits).hash(state);
+        max.map(f64::to_bits).hash(state);
+      }
+      MatchingRule::StringLength(min, max) => {
+        min.hash(state);
+        max.hash(state);
+      }
+      MatchingRule::OneOf(values) => {
+        for value in values {
+          value.to_string().hash(state);
+        }
+      }
+      MatchingRule::Not(rule) => rule.hash(state),
+      MatchingRule::AnyOf(rules) | MatchingRule::AllOf(rules) => {
+        for rule in rules {
+          rule.hash(state);
+        }
+      }
+      MatchingRule::JsonSchema(schema) => schema.to_string().hash(state),
+      MatchingRule::Format(format) => format.hash(state),
+      MatchingRule::Approximately(tolerance) => match tolerance {
+        NumberTolerance::Absolute(value) => {
+          0.hash(state);
+          value.to_bits().hash(state);
+        }
+        NumberTolerance::Relative(value) => {
+          1.hash(state);
+          value.to_bits().hash(state);
+        }
+      },
+      MatchingRule::Jwt(rules) => rules.hash(state),
       _ => ()
     }
   }
@@ -531,11 +802,40 @@ impl PartialEq for MatchingRule {
       (MatchingRule::Include(str1), MatchingRule::Include(str2)) => str1 == str2,
       (MatchingRule::ContentType(str1), MatchingRule::ContentType(str2)) => str1 == str2,
       (MatchingRule::ArrayContains(variants1), MatchingRule::ArrayContains(variants2)) => variants1 == variants2,
+      (MatchingRule::NumberRange(min1, max1), MatchingRule::NumberRange(min2, max2)) =>
+        min1.map(f64::to_bits) == min2.map(f64::to_bits) && max1.map(f64::to_bits) == max2.map(f64::to_bits),
+      (MatchingRule::StringLength(min1, max1), MatchingRule::StringLength(min2, max2)) => min1 == min2 && max1 == max2,
+      (MatchingRule::OneOf(values1), MatchingRule::OneOf(values2)) => values1 == values2,
+      (MatchingRule::Not(rule1), MatchingRule::Not(rule2)) => rule1 == rule2,
+      (MatchingRule::AnyOf(rules1), MatchingRule::AnyOf(rules2)) => rules1 == rules2,
+      (MatchingRule::AllOf(rules1), MatchingRule::AllOf(rules2)) => rules1 == rules2,
+      (MatchingRule::JsonSchema(schema1), MatchingRule::JsonSchema(schema2)) => schema1 == schema2,
+      (MatchingRule::Format(format1), MatchingRule::Format(format2)) => format1 == format2,
+      (MatchingRule::Approximately(tolerance1), MatchingRule::Approximately(tolerance2)) => match (tolerance1, tolerance2) {
+        (NumberTolerance::Absolute(value1), NumberTolerance::Absolute(value2)) => value1.to_bits() == value2.to_bits(),
+        (NumberTolerance::Relative(value1), NumberTolerance::Relative(value2)) => value1.to_bits() == value2.to_bits(),
+        _ => false
+      },
+      (MatchingRule::Jwt(rules1), MatchingRule::Jwt(rules2)) => rules1 == rules2,
       _ => mem::discriminant(self) == mem::discriminant(other)
     }
   }
 }
 
+// Implemented manually as the NumberRange and Approximately matchers have floating point values.
+// These are compared by their bits in PartialEq (the same as in Hash), so a NaN value is equal to
+// itself and the equality is reflexive.
+impl Eq for MatchingRule {}
+
+/// Converts a number to JSON, using an integer value if the number has no fractional part
+fn number_to_json(number: f64) -> Value {
+  if number.fract() == 0.0 && number.abs() < i64::MAX as f64 {
+    json!(number as i64)
+  } else {
+    json!(number)
+  }
+}
+
 #[cfg(test)]
 fn h(rule: &MatchingRule) -> u64 {
   let mut hasher = DefaultHasher::new();
@@ -749,6 +1049,88 @@ fn hash_and_partial_eq_for_matching_rule() {
   expect!(&ac7).to_not(be_equal_to(&ac5));
   expect!(&ac7).to_not(be_equal_to(&ac6));
   expect!(&ac7).to_not(be_equal_to(&ac1));
+
+  let range1 = MatchingRule::NumberRange(Some(1.0), Some(10.0));
+  let range2 = MatchingRule::NumberRange(Some(1.0), None);
+
+  expect!(h(&range1)).to(be_equal_to(h(&range1)));
+  expect!(&range1).to(be_equal_to(&range1));
+  expect!(h(&range1)).to_not(be_equal_to(h(&range2)));
+  expect!(&range1).to_not(be_equal_to(&range2));
+
+  let range3 = MatchingRule::NumberRange(Some(f64::NAN), None);
+  expect!(h(&range3)).to(be_equal_to(h(&range3)));
+  expect!(&range3).to(be_equal_to(&range3));
+
+  let approx1 = MatchingRule::Approximately(NumberTolerance::Absolute(0.5));
+  let approx2 = MatchingRule::Approximately(NumberTolerance::Relative(0.5));
+  let approx3 = MatchingRule::Approximately(NumberTolerance::Absolute(f64::NAN));
+
+  expect!(h(&approx1)).to(be_equal_to(h(&approx1)));
+  expect!(&approx1).to(be_equal_to(&approx1));
+  expect!(h(&approx1)).to_not(be_equal_to(h(&approx2)));
+  expect!(&approx1).to_not(be_equal_to(&approx2));
+  expect!(h(&approx3)).to(be_equal_to(h(&approx3)));
+  expect!(&approx3).to(be_equal_to(&approx3));
+
+  let length1 = MatchingRule::StringLength(Some(1), Some(10));
+  let length2 = MatchingRule::StringLength(None, Some(10));
+
+  expect!(h(&length1)).to(be_equal_to(h(&length1)));
+  expect!(&length1).to(be_equal_to(&length1));
+  expect!(h(&length1)).to_not(be_equal_to(h(&length2)));
+  expect!(&length1).to_not(be_equal_to(&length2));
+
+  let one_of1 = MatchingRule::OneOf(vec![json!("AUD"), json!("USD")]);
+  let one_of2 = MatchingRule::OneOf(vec![json!("AUD"), json!("EUR")]);
+
+  expect!(h(&one_of1)).to(be_equal_to(h(&one_of1)));
+  expect!(&one_of1).to(be_equal_to(&one_of1));
+  expect!(h(&one_of1)).to_not(be_equal_to(h(&one_of2)));
+  expect!(&one_of1).to_not(be_equal_to(&one_of2));
+
+  let not1 = MatchingRule::Not(Box::new(MatchingRule::Null));
+  let not2 = MatchingRule::Not(Box::new(MatchingRule::Integer));
+
+  expect!(h(&not1)).to(be_equal_to(h(&not1)));
+  expect!(&not1).to(be_equal_to(&not1));
+  expect!(h(&not1)).to_not(be_equal_to(h(&not2)));
+  expect!(&not1).to_not(be_equal_to(&not2));
+
+  let any_of = MatchingRule::AnyOf(vec![MatchingRule::Null, MatchingRule::Integer]);
+  let all_of = MatchingRule::AllOf(vec![MatchingRule::Null, MatchingRule::Integer]);
+  let any_of2 = MatchingRule::AnyOf(vec![MatchingRule::Null, MatchingRule::Decimal]);
+
+  expect!(h(&any_of)).to(be_equal_to(h(&any_of)));
+  expect!(&any_of).to(be_equal_to(&any_of));
+  expect!(h(&any_of)).to_not(be_equal_to(h(&all_of)));
+  expect!(&any_of).to_not(be_equal_to(&all_of));
+  expect!(h(&any_of)).to_not(be_equal_to(h(&any_of2)));
+  expect!(&any_of).to_not(be_equal_to(&any_of2));
+
+  let schema1 = MatchingRule::JsonSchema(json!({ "type": "string" }));
+  let schema2 = MatchingRule::JsonSchema(json!({ "type": "integer" }));
+
+  expect!(h(&schema1)).to(be_equal_to(h(&schema1)));
+  expect!(&schema1).to(be_equal_to(&schema1));
+  expect!(h(&schema1)).to_not(be_equal_to(h(&schema2)));
+  expect!(&schema1).to_not(be_equal_to(&schema2));
+
+  let format1 = MatchingRule::Format(StringFormat::Email);
+  let format2 = MatchingRule::Format(StringFormat::Uuid(None));
+
+  expect!(h(&format1)).to(be_equal_to(h(&format1)));
+  expect!(&format1).to(be_equal_to(&format1));
+  expect!(h(&format1)).to_not(be_equal_to(h(&format2)));
+  expect!(&format1).to_not(be_equal_to(&format2));
+
+  let jwt1 = MatchingRule::Jwt(MatchingRuleCategory::empty("body"));
+  let jwt2 = MatchingRule::Jwt(MatchingRuleCategory::equality("body"));
+
+  expect!(h(&jwt1)).to(be_equal_to(h(&jwt1)));
+  expect!(&jwt1).to(be_equal_to(&jwt1));
+  expect!(h(&jwt1)).to_not(be_equal_to(h(&jwt2)));
+  expect!(&jwt1).to_not(be_equal_to(&jwt2));
 }
 
 /// Enumeration to define how to combine rules
@@ -1934,6 +2316,90 @@ mod tests {
     expect!(MatchingRule::from_json(&json)).to(be_ok().value(
       MatchingRule::StatusCode(HttpStatus::StatusCodes(vec![200, 201, 204]))
     ));
+
+    expect!(MatchingRule::from_json(&json!({ "match": "numberRange", "min": 1, "max": 9.5 }))).to(
+      be_ok().value(MatchingRule::NumberRange(Some(1.0), Some(9.5))));
+    expect!(MatchingRule::from_json(&json!({ "match": "number-range", "max": 100 }))).to(
+      be_ok().value(MatchingRule::NumberRange(None, Some(100.0))));
+    expect!(MatchingRule::from_json(&json!({ "match": "numberRange" }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "numberRange", "min": 10, "max": 1 }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "stringLength", "min": 1, "max": 10 }))).to(
+      be_ok().value(MatchingRule::StringLength(Some(1), Some(10))));
+    expect!(MatchingRule::from_json(&json!({ "match": "string-length", "min": "2" }))).to(
+      be_ok().value(MatchingRule::StringLength(Some(2), None)));
+    expect!(MatchingRule::from_json(&json!({ "match": "stringLength" }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "stringLength", "min": 1.5 }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "stringLength", "max": -1 }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "stringLength", "min": 10, "max": 1 }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "stringLength", "min": 3, "max": 3 }))).to(
+      be_ok().value(MatchingRule::StringLength(Some(3), Some(3))));
+    expect!(MatchingRule::from_json(&json!({ "match": "oneOf", "values": ["AUD", "USD", 100] }))).to(
+      be_ok().value(MatchingRule::OneOf(vec![json!("AUD"), json!("USD"), json!(100)])));
+    expect!(MatchingRule::from_json(&json!({ "match": "one-of", "values": [] }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "oneOf" }))).to(be_err());
+
+    expect!(MatchingRule::from_json(&json!({ "match": "not", "rule": { "match": "regex", "regex": "^\\s*$" } }))).to(
+      be_ok().value(MatchingRule::Not(Box::new(MatchingRule::Regex("^\\s*$".to_string())))));
+    expect!(MatchingRule::from_json(&json!({ "match": "not" }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "not", "rule": { "match": "stuff" } }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({
+      "match": "anyOf",
+      "rules": [ { "match": "null" }, { "match": "date", "format": "yyyy-MM-dd" } ]
+    }))).to(be_ok().value(MatchingRule::AnyOf(vec![MatchingRule::Null, MatchingRule::Date("yyyy-MM-dd".to_string())])));
+    expect!(MatchingRule::from_json(&json!({
+      "match": "all-of",
+      "rules": [ { "match": "type" }, { "match": "not", "rule": { "match": "include", "value": "x" } } ]
+    }))).to(be_ok().value(MatchingRule::AllOf(vec![MatchingRule::Type,
+      MatchingRule::Not(Box::new(MatchingRule::Include("x".to_string())))])));
+    expect!(MatchingRule::from_json(&json!({ "match": "anyOf", "rules": [] }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "allOf" }))).to(be_err());
+
+    expect!(MatchingRule::from_json(&json!({ "match": "jsonSchema", "schema": { "type": "string" } }))).to(
+      be_ok().value(MatchingRule::JsonSchema(json!({ "type": "string" }))));
+    expect!(MatchingRule::from_json(&json!({ "match": "json-schema", "schema": true }))).to(
+      be_ok().value(MatchingRule::JsonSchema(json!(true))));
+    expect!(MatchingRule::from_json(&json!({ "match": "jsonSchema", "schema": "string" }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "jsonSchema" }))).to(be_err());
+
+    expect!(MatchingRule::from_json(&json!({ "match": "format", "format": "email" }))).to(
+      be_ok().value(MatchingRule::Format(StringFormat::Email)));
+    expect!(MatchingRule::from_json(&json!({ "match": "format", "format": "uuid-upper-case-hyphenated" }))).to(
+      be_ok().value(MatchingRule::Format(StringFormat::Uuid(Some(UuidFormat::UpperCaseHyphenated)))));
+    expect!(MatchingRule::from_json(&json!({ "match": "format", "format": "phone" }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "format" }))).to(be_err());
+
+    expect!(MatchingRule::from_json(&json!({ "match": "ignore-order" }))).to(
+      be_ok().value(MatchingRule::EqualsIgnoreOrder));
+    expect!(MatchingRule::from_json(&json!({ "match": "equalsIgnoreOrder" }))).to(
+      be_ok().value(MatchingRule::EqualsIgnoreOrder));
+    expect!(MatchingRule::from_json(&json!({ "match": "ignore-order", "min": 2 }))).to(be_err());
+
+    expect!(MatchingRule::from_json(&json!({ "match": "approximately", "absolute": 0.01 }))).to(
+      be_ok().value(MatchingRule::Approximately(NumberTolerance::Absolute(0.01))));
+    expect!(MatchingRule::from_json(&json!({ "match": "approximately", "relative": 0.05 }))).to(
+      be_ok().value(MatchingRule::Approximately(NumberTolerance::Relative(0.05))));
+    expect!(MatchingRule::from_json(&json!({ "match": "approximately" }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "approximately", "absolute": 1, "relative": 0.1 }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "approximately", "absolute": "1" }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "approximately", "relative": -0.1 }))).to(be_err());
+
+    expect!(MatchingRule::from_json(&json!({
+      "match": "jwt",
+      "rules": {
+        "$.claims.sub": { "matchers": [ { "match": "regex", "regex": "\\d+" } ] },
+        "$.claims.exp": { "matchers": [ { "match": "integer" } ] }
+      }
+    }))).to(be_ok().value(MatchingRule::Jwt(matchingrules_list! {
+      "body";
+      "$.claims.sub" => [ MatchingRule::Regex("\\d+".to_string()) ],
+      "$.claims.exp" => [ MatchingRule::Integer ]
+    })));
+    expect!(MatchingRule::from_json(&json!({ "match": "jwt" }))).to(
+      be_ok().value(MatchingRule::Jwt(MatchingRuleCategory::empty("body"))));
+    expect!(MatchingRule::from_json(&json!({
+      "match": "jwt",
+      "rules": { "$.claims.sub": { "matchers": [ { "match": "unknown" } ] } }
+    }))).to(be_err());
   }
 
   #[test]
@@ -1948,6 +2414,66 @@ mod tests {
         "match": "statusCode",
         "status": [400, 401, 404]
       })));
+    expect!(MatchingRule::NumberRange(Some(1.0), Some(9.5)).to_json()).to(
+      be_equal_to(json!({
+        "match": "numberRange",
+        "min": 1,
+        "max": 9.5
+      })));
+    expect!(MatchingRule::NumberRange(None, Some(100.0)).to_json()).to(
+      be_equal_to(json!({
+        "match": "numberRange",
+        "max": 100
+      })));
+    expect!(MatchingRule::StringLength(Some(1), None).to_json()).to(
+      be_equal_to(json!({
+        "match": "stringLength",
+        "min": 1
+      })));
+    expect!(MatchingRule::OneOf(vec![json!("AUD"), json!("USD")]).to_json()).to(
+      be_equal_to(json!({
+        "match": "oneOf",
+        "values": ["AUD", "USD"]
+      })));
+    expect!(MatchingRule::AnyOf(vec![MatchingRule::Null, MatchingRule::Not(Box::new(MatchingRule::Integer))]).to_json()).to(
+      be_equal_to(json!({
+        "match": "anyOf",
+        "rules": [
+          { "match": "null" },
+          { "match": "not", "rule": { "match": "integer" } }
+        ]
+      })));
+    expect!(MatchingRule::JsonSchema(json!({ "type": "object", "required": ["id"] })).to_json()).to(
+      be_equal_to(json!({
+        "match": "jsonSchema",
+        "schema": { "type": "object", "required": ["id"] }
+      })));
+    expect!(MatchingRule::Format(StringFormat::Uuid(Some(UuidFormat::Urn))).to_json()).to(
+      be_equal_to(json!({
+        "match": "format",
+        "format": "uuid-urn"
+      })));
+    expect!(MatchingRule::EqualsIgnoreOrder.to_json()).to(
+      be_equal_to(json!({
+        "match": "ignore-order"
+      })));
+    expect!(MatchingRule::Approximately(NumberTolerance::Absolute(0.5)).to_json()).to(
+      be_equal_to(json!({
+        "match": "approximately",
+        "absolute": 0.5
+      })));
+    expect!(MatchingRule::Approximately(NumberTolerance::Relative(1.0)).to_json()).to(
+      be_equal_to(json!({
+        "match": "approximately",
+        "relative": 1
+      })));
+    expect!(MatchingRule::Jwt(matchingrules_list! { "body"; "$.claims.exp" => [ MatchingRule::Integer ] }).to_json()).to(
+      be_equal_to(json!({
+        "match": "jwt",
+        "rules": {
+          "$.claims.exp": { "combine": "AND", "matchers": [ { "match": "integer" } ] }
+        }
+      })));
   }
 
   #[test]
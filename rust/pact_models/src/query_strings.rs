@@ -3,7 +3,7 @@ use std::str::from_utf8;
 
 use hex::FromHex;
 use itertools::Itertools;
-use serde_json::Value;
+use serde_json::{Map, Value};
 use tracing::{error, trace, warn};
 
 use crate::PactSpecification;
@@ -179,6 +179,154 @@ pub fn query_to_json(query: HashMap<String, Vec<String>>, spec_version: &PactSpe
   }
 }
 
+/// Key in the comments of a V4 interaction that sets how the query parameters of the request are
+/// structured (i.e. `"queryStructure": "nested"`)
+pub const QUERY_STRUCTURE_COMMENT_KEY: &str = "queryStructure";
+
+/// How the query parameters of a request are structured
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum QueryStructure {
+  /// Query parameters are a flat map of parameter names to values (the default)
+  #[default]
+  Flat,
+  /// Query parameter names using the Rails and qs conventions (`filter[status]=open`, `ids[]=1&ids[]=2`) are
+  /// parsed into a JSON-like tree, which is matched in the same way as a JSON body
+  Nested
+}
+
+impl QueryStructure {
+  /// Returns the query structure set in the comments of a V4 interaction, defaulting to `Flat`
+  pub fn from_comments(comments: &HashMap<String, Value>) -> QueryStructure {
+    match comments.get(QUERY_STRUCTURE_COMMENT_KEY) {
+      Some(Value::String(s)) if s == "nested" => QueryStructure::Nested,
+      _ => QueryStructure::Flat
+    }
+  }
+
+  /// Converts this query structure to the JSON value stored in the interaction comments
+  pub fn to_json(&self) -> Value {
+    match self {
+      QueryStructure::Flat => Value::String("flat".to_string()),
+      QueryStructure::Nested => Value::String("nested".to_string())
+    }
+  }
+}
+
+/// Splits a query parameter name into the path of keys (i.e. `filter[status]` -> `filter`, `status`).
+/// An empty key (`ids[]`) appends to a list. Names that are not well formed are returned as is.
+fn parse_query_key(key: &str) -> Vec<String> {
+  match key.find('[') {
+    Some(index) if index > 0 && key.ends_with(']') => {
+      let mut segments = vec![key[..index].to_string()];
+      for segment in key[index + 1..key.len() - 1].split("][") {
+        if segment.contains('[') || segment.contains(']') {
+          return vec![key.to_string()];
+        }
+        segments.push(segment.to_string());
+      }
+      segments
+    }
+    _ => vec![key.to_string()]
+  }
+}
+
+fn insert_query_value(node: &mut Value, segments: &[String], value: &str) {
+  if let Some((segment, rest)) = segments.split_first() {
+    if segment.is_empty() {
+      if !node.is_array() {
+        *node = Value::Array(vec![]);
+      }
+      if let Value::Array(items) = node {
+        if rest.is_empty() {
+          items.push(Value::String(value.to_string()));
+        } else {
+          let mut item = Value::Null;
+          insert_query_value(&mut item, rest, value);
+          items.push(item);
+        }
+      }
+    } else {
+      if !node.is_object() {
+        *node = Value::Object(Map::new());
+      }
+      if let Value::Object(map) = node {
+        let child = map.entry(segment.clone()).or_insert(Value::Null);
+        if rest.is_empty() {
+          match child {
+            Value::Array(items) => items.push(Value::String(value.to_string())),
+            Value::String(existing) => *child = Value::Array(vec![Value::String(existing.clone()),
+              Value::String(value.to_string())]),
+            _ => *child = Value::String(value.to_string())
+          }
+        } else {
+          insert_query_value(child, rest, value);
+        }
+      }
+    }
+  }
+}
+
+/// Objects where all the keys are indices (`items[0][name]`) are converted into lists
+fn indexed_objects_to_lists(value: Value) -> Value {
+  match value {
+    Value::Object(map) => if !map.is_empty() && map.keys().all(|key| key.parse::<usize>().is_ok()) {
+      Value::Array(map.into_iter()
+        .sorted_by_key(|(key, _)| key.parse::<usize>().unwrap_or_default())
+        .map(|(_, value)| indexed_objects_to_lists(value))
+        .collect())
+    } else {
+      Value::Object(map.into_iter().map(|(key, value)| (key, indexed_objects_to_lists(value))).collect())
+    },
+    Value::Array(items) => Value::Array(items.into_iter().map(indexed_objects_to_lists).collect()),
+    _ => value
+  }
+}
+
+/// Converts a query parameter map into a JSON-like tree, using the Rails and qs conventions for the
+/// parameter names. For example, `filter[status]=open&filter[owner]=me&ids[]=1&ids[]=2` becomes
+/// `{"filter": {"status": "open", "owner": "me"}, "ids": ["1", "2"]}`. Parameters that are repeated
+/// without brackets are also converted into a list.
+pub fn structured_query(query: &HashMap<String, Vec<String>>) -> Value {
+  let mut tree = Value::Object(Map::new());
+  for (key, values) in query.iter().sorted_by(|a, b| Ord::cmp(&a.0, &b.0)) {
+    let segments = parse_query_key(key);
+    for value in values {
+      insert_query_value(&mut tree, &segments, value);
+    }
+  }
+  indexed_objects_to_lists(tree)
+}
+
+fn flatten_query_value(key: &str, value: &Value, query: &mut HashMap<String, Vec<String>>) {
+  match value {
+    Value::Object(map) => for (child_key, child) in map {
+      flatten_query_value(&format!("{}[{}]", key, child_key), child, query);
+    },
+    Value::Array(items) => for (index, item) in items.iter().enumerate() {
+      match item {
+        Value::Object(_) | Value::Array(_) => flatten_query_value(&format!("{}[{}]", key, index), item, query),
+        _ => flatten_query_value(&format!("{}[]", key), item, query)
+      }
+    },
+    Value::String(s) => query.entry(key.to_string()).or_default().push(s.clone()),
+    Value::Null => query.entry(key.to_string()).or_default().push(String::default()),
+    _ => query.entry(key.to_string()).or_default().push(value.to_string())
+  }
+}
+
+/// Converts a JSON-like tree of query parameters back into a query parameter map. Nested keys are
+/// written as `filter[status]`, lists of values as `ids[]` and lists of objects as `items[0][name]`.
+pub fn flatten_structured_query(query: &Value) -> HashMap<String, Vec<String>> {
+  let mut result = HashMap::new();
+  match query {
+    Value::Object(map) => for (key, value) in map {
+      flatten_query_value(key, value, &mut result);
+    },
+    _ => warn!("Structured query parameters must be a JSON object, ignoring '{}'", query)
+  }
+  result
+}
+
 #[cfg(test)]
 mod tests {
   use std::collections::HashMap;
@@ -186,7 +334,9 @@ mod tests {
   use expectest::prelude::*;
   use maplit::hashmap;
 
-  use crate::query_strings::parse_query_string;
+  use serde_json::json;
+
+  use crate::query_strings::{flatten_structured_query, parse_query_string, QueryStructure, structured_query};
 
   #[test]
   fn parse_query_string_test() {
@@ -247,4 +397,52 @@ mod tests {
     let result = parse_query_string(&query);
     expect!(result).to(be_some().value(expected));
   }
+
+  #[test]
+  fn structured_query_parses_nested_and_list_parameters() {
+    let query = parse_query_string("filter[status]=open&filter[owner]=me&ids[]=1&ids[]=2&page=3&tag=a&tag=b\
+      &items[1][name]=second&items[0][name]=first&items[0][qty]=2&bad[key=x").unwrap();
+    expect!(structured_query(&query)).to(be_equal_to(json!({
+      "filter": { "status": "open", "owner": "me" },
+      "ids": ["1", "2"],
+      "page": "3",
+      "tag": ["a", "b"],
+      "items": [
+        { "name": "first", "qty": "2" },
+        { "name": "second" }
+      ],
+      "bad[key": "x"
+    })));
+  }
+
+  #[test]
+  fn flatten_structured_query_round_trips() {
+    let tree = json!({
+      "filter": { "status": "open", "owner": ["me", "you"] },
+      "items": [ { "name": "first" }, { "name": "second" } ],
+      "page": 3
+    });
+    let query = flatten_structured_query(&tree);
+    expect!(query.clone()).to(be_equal_to(hashmap!{
+      "filter[status]".to_string() => vec!["open".to_string()],
+      "filter[owner][]".to_string() => vec!["me".to_string(), "you".to_string()],
+      "items[0][name]".to_string() => vec!["first".to_string()],
+      "items[1][name]".to_string() => vec!["second".to_string()],
+      "page".to_string() => vec!["3".to_string()]
+    }));
+    expect!(structured_query(&query)).to(be_equal_to(json!({
+      "filter": { "status": "open", "owner": ["me", "you"] },
+      "items": [ { "name": "first" }, { "name": "second" } ],
+      "page": "3"
+    })));
+  }
+
+  #[test]
+  fn query_structure_from_comments() {
+    expect!(QueryStructure::from_comments(&hashmap!{})).to(be_equal_to(QueryStructure::Flat));
+    expect!(QueryStructure::from_comments(&hashmap!{ "queryStructure".to_string() => json!("nested") }))
+      .to(be_equal_to(QueryStructure::Nested));
+    expect!(QueryStructure::from_comments(&hashmap!{ "queryStructure".to_string() => QueryStructure::Flat.to_json() }))
+      .to(be_equal_to(QueryStructure::Flat));
+  }
 }
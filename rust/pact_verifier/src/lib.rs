@@ -9,7 +9,7 @@ use std::fmt;
 use std::fs;
 use std::path::Path;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use ansi_term::*;
 use ansi_term::Colour::*;
@@ -39,7 +39,7 @@ use tracing::{debug, debug_span, error, info, Instrument, trace};
 
 pub use callback_executors::NullRequestFilterExecutor;
 use callback_executors::RequestFilterExecutor;
-use pact_matching::{match_response, Mismatch};
+use pact_matching::{match_response, match_response_with_explanation, Mismatch};
 use pact_matching::logging::LOG_ID;
 use pact_matching::metrics::{MetricEvent, send_metrics};
 
@@ -50,7 +50,7 @@ use crate::pact_broker::{Link, PactVerificationContext, publish_verification_res
 pub use crate::pact_broker::{ConsumerVersionSelector, PactsForVerificationRequest};
 use crate::provider_client::make_provider_request;
 use crate::request_response::process_request_response_result;
-use crate::verification_result::VerificationExecutionResult;
+use crate::verification_result::{InteractionExecutionResult, PactExecutionResult, VerificationExecutionResult};
 
 mod provider_client;
 pub mod pact_broker;
@@ -259,25 +259,41 @@ async fn verify_response_from_provider<F: RequestFilterExecutor>(
   options: &VerificationOptions<F>,
   client: &Client,
   verification_context: &HashMap<&str, Value>
-) -> Result<Option<String>, MismatchResult> {
+) -> InteractionVerificationResult {
   let expected_response = &interaction.response;
   let request = pact_matching::generate_request(&interaction.request, &GeneratorTestMode::Provider, &verification_context).await;
   match make_provider_request(provider, &request, options, client).await {
     Ok(ref actual_response) => {
-      let mismatches = match_response(expected_response.clone(), actual_response.clone(), pact, &interaction.boxed()).await;
+      let (mismatches, output) = if options.explain {
+        let (mismatches, explanation) = match_response_with_explanation(expected_response.clone(),
+          actual_response.clone(), pact, &interaction.boxed()).await;
+        let mut output = vec!["Match explanation:".to_string()];
+        output.extend(explanation.to_string().lines().map(|line| format!("  {}", line)));
+        let unused_rules = explanation.unused_rules(&expected_response.matching_rules);
+        if !unused_rules.is_empty() {
+          output.push("Matching rules that were not applied:".to_string());
+          output.extend(unused_rules.iter().map(|(category, path)| {
+            let category: String = category.clone().into();
+            format!("  [{}] {}", category, path)
+          }));
+        }
+        (mismatches, output)
+      } else {
+        (match_response(expected_response.clone(), actual_response.clone(), pact, &interaction.boxed()).await, vec![])
+      };
       if mismatches.is_empty() {
-        Ok(interaction.id.clone())
+        Ok((interaction.id.clone(), output))
       } else {
-        Err(MismatchResult::Mismatches {
+        Err((MismatchResult::Mismatches {
           mismatches,
           expected: interaction.boxed(),
           actual: Box::new(SynchronousHttp { response: actual_response.clone(), .. SynchronousHttp::default() }),
           interaction_id: interaction.id.clone()
-        })
+        }, output))
       }
     },
     Err(err) => {
-      Err(MismatchResult::Error(err.to_string(), interaction.id.clone()))
+      Err((MismatchResult::Error(err.to_string(), interaction.id.clone()), vec![]))
     }
   }
 }
@@ -334,10 +350,7 @@ async fn verify_interaction<'a, F: RequestFilterExecutor, S: ProviderStateExecut
     trace!("Verifying interaction via {}", transport.key);
     verify_interaction_using_transport(transport, provider, interaction, pact, options, &client, &provider_states_context).await
   } else {
-    verify_v3_interaction(provider, interaction, &pact, options, &client, &provider_states_context)
-      .await
-      .map(|r| (r, vec![]))
-      .map_err(|e| (e, vec![]))
+    verify_v3_interaction(provider, interaction, &pact, options, &client, &provider_states_context).await
   };
 
   if !interaction.provider_states().is_empty() && provider_state_executor.teardown() {
@@ -424,10 +437,7 @@ async fn verify_interaction_using_transport<'a, F: RequestFilterExecutor>(
       }
     }
   } else {
-    verify_v3_interaction(provider, interaction, pact, options, client, config)
-      .await
-      .map(|r| (r, vec![]))
-      .map_err(|e| (e, vec![]))
+    verify_v3_interaction(provider, interaction, pact, options, client, config).await
   }
 }
 
@@ -439,8 +449,8 @@ async fn verify_v3_interaction<'a, F: RequestFilterExecutor>(
   options: &VerificationOptions<F>,
   client: &Arc<Client>,
   provider_states_context: &HashMap<&str, Value>
-) -> Result<Option<String>, MismatchResult> {
-  let mut result = Err(MismatchResult::Error("No interaction was verified".into(), interaction.id().clone()));
+) -> InteractionVerificationResult {
+  let mut result = Err((MismatchResult::Error("No interaction was verified".into(), interaction.id().clone()), vec![]));
 
   // Verify an HTTP interaction
   if let Some(interaction) = interaction.as_v4_http() {
@@ -450,12 +460,16 @@ async fn verify_v3_interaction<'a, F: RequestFilterExecutor>(
   // Verify an asynchronous message (single shot)
   if interaction.is_message() {
     trace!("Verifying an asynchronous message (single shot)");
-    result = verify_message_from_provider(provider, pact, &interaction.boxed(), options, &client, &provider_states_context).await;
+    result = verify_message_from_provider(provider, pact, &interaction.boxed(), options, &client, &provider_states_context).await
+      .map(|r| (r, vec![]))
+      .map_err(|e| (e, vec![]));
   }
   // Verify a synchronous message (request/response)
   if let Some(message) = interaction.as_v4_sync_message() {
     trace!("Verifying a synchronous message (request/response)");
-    result = verify_sync_message_from_provider(provider, pact, message, options, &client, &provider_states_context).await;
+    result = verify_sync_message_from_provider(provider, pact, message, options, &client, &provider_states_context).await
+      .map(|r| (r, vec![]))
+      .map_err(|e| (e, vec![]));
   }
 
   result
@@ -716,7 +730,13 @@ pub struct VerificationOptions<F> where F: RequestFilterExecutor {
   /// Timeout in ms for verification requests and state callbacks
   pub request_timeout: u64,
   /// Custom headers to be added to the requests to the provider
-  pub custom_headers: HashMap<String, String>
+  pub custom_headers: HashMap<String, String>,
+  /// Maximum number of interactions to verify at the same time. Interactions that share a provider
+  /// state are always verified one after the other. Defaults to 1 (verify them one at a time)
+  pub max_concurrency: usize,
+  /// Include an explanation of the matching rules that were applied at each path when matching
+  /// the responses from the provider in the output
+  pub explain: bool
 }
 
 impl <F: RequestFilterExecutor> Default for VerificationOptions<F> {
@@ -725,7 +745,9 @@ impl <F: RequestFilterExecutor> Default for VerificationOptions<F> {
       request_filter: None,
       disable_ssl_verification: false,
       request_timeout: 5000,
-      custom_headers: Default::default()
+      custom_headers: Default::default(),
+      max_concurrency: 1,
+      explain: false
     }
   }
 }
@@ -825,7 +847,9 @@ pub async fn verify_provider_async<F: RequestFilterExecutor, S: ProviderStateExe
               Some(context) => context.verification_properties.pending,
               None => false
             };
-            let verify_result = verify_pact_internal(
+            let consumer_name = pact.consumer().name.clone();
+            let provider_name = pact.provider().name.clone();
+            let verify_result = verify_pact_with_durations(
               &provider_info,
               &filter,
               pact,
@@ -834,7 +858,14 @@ pub async fn verify_provider_async<F: RequestFilterExecutor, S: ProviderStateExe
               pending
             ).await;
             match verify_result {
-              Ok(result) => {
+              Ok((result, durations)) => {
+                verification_result.pact_results.push(PactExecutionResult {
+                  consumer: consumer_name.clone(),
+                  provider: provider_name.clone(),
+                  interactions: result.results.iter().zip(durations)
+                    .map(|(result, duration)| InteractionExecutionResult::new(result, duration))
+                    .collect()
+                });
                 for result in &result.results {
                   results.push((result.interaction_id.clone(), result.result.clone()));
                   if let Err(error) = &result.result {
@@ -851,6 +882,17 @@ pub async fn verify_provider_async<F: RequestFilterExecutor, S: ProviderStateExe
                 }
               }
               Err(err) => {
+                verification_result.pact_results.push(PactExecutionResult {
+                  consumer: consumer_name.clone(),
+                  provider: provider_name.clone(),
+                  interactions: vec![InteractionExecutionResult {
+                    description: "Could not verify the provided pact".to_string(),
+                    interaction_id: None,
+                    result: Err((&MismatchResult::Error(err.to_string(), None)).into()),
+                    pending,
+                    duration: Duration::default()
+                  }]
+                });
                 if pending {
                   pending_errors.push(("Could not verify the provided pact".to_string(),
                                        MismatchResult::Error(err.to_string(), None)));
@@ -1105,22 +1147,32 @@ pub async fn verify_pact_internal<'a, F: RequestFilterExecutor, S: ProviderState
   provider_state_executor: &Arc<S>,
   pending: bool
 ) -> anyhow::Result<VerificationResult> {
-  let interactions = pact.interactions();
+  verify_pact_with_durations(provider_info, filter, pact, options, provider_state_executor, pending).await
+    .map(|(result, _)| result)
+}
+
+/// Verifies the Pact, also returning the time taken to verify each interaction in the same order
+/// as the results
+async fn verify_pact_with_durations<'a, F: RequestFilterExecutor, S: ProviderStateExecutor>(
+  provider_info: &ProviderInfo,
+  filter: &FilterInfo,
+  pact: Box<dyn Pact + Send + Sync + 'a>,
+  options: &VerificationOptions<F>,
+  provider_state_executor: &Arc<S>,
+  pending: bool
+) -> anyhow::Result<(VerificationResult, Vec<Duration>)> {
+  let interactions = pact.interactions().into_iter()
+    .filter(|interaction| filter_interaction(interaction.as_ref(), filter))
+    .collect_vec();
   let mut output = vec![];
 
-  let results: Vec<(Box<dyn Interaction + Send + Sync>, Result<(Option<String>, Vec<String>), (MismatchResult, Vec<String>)>)> =
-    futures::stream::iter(interactions.iter().map(|i| (&pact, i)))
-    .filter(|(_, interaction)| futures::future::ready(filter_interaction(interaction.as_ref(), filter)))
-    .then( |(pact, interaction)| async move {
-      let interaction_desc = interaction.description();
-      (interaction.boxed(), verify_interaction(provider_info, interaction.as_ref(), &pact.boxed(), options, provider_state_executor)
-        .instrument(debug_span!("verify_interaction", interaction = interaction_desc.as_str())).await)
-    })
-    .collect()
-    .await;
+  let results = verify_interactions(provider_info, pact.as_ref(), &interactions, options,
+    provider_state_executor).await;
 
   let mut errors: Vec<VerificationInteractionResult> = vec![];
-  for (interaction, match_result) in results {
+  let mut durations = vec![];
+  for (interaction, match_result, duration) in results {
+    durations.push(duration);
     let mut description = format!("Verifying a pact between {} and {}",
       pact.consumer().name.clone(), pact.provider().name.clone());
 
@@ -1196,7 +1248,81 @@ pub async fn verify_pact_internal<'a, F: RequestFilterExecutor, S: ProviderState
 
   output.push(String::default());
 
-  Ok(VerificationResult { results: errors, output: output.clone() })
+  Ok((VerificationResult { results: errors, output: output.clone() }, durations))
+}
+
+/// Result of verifying a single interaction, along with any output collected
+type InteractionVerificationResult = Result<(Option<String>, Vec<String>), (MismatchResult, Vec<String>)>;
+
+/// Verifies the interactions, returning the results in the same order as the interactions. If the
+/// maximum concurrency is more than one, the interactions are grouped into batches by provider
+/// state, and the batches are run concurrently.
+async fn verify_interactions<'a, F: RequestFilterExecutor, S: ProviderStateExecutor>(
+  provider_info: &ProviderInfo,
+  pact: &(dyn Pact + Send + Sync + 'a),
+  interactions: &[Box<dyn Interaction + Send + Sync>],
+  options: &VerificationOptions<F>,
+  provider_state_executor: &Arc<S>
+) -> Vec<(Box<dyn Interaction + Send + Sync>, InteractionVerificationResult, Duration)> {
+  let batches = if options.max_concurrency > 1 {
+    group_interactions_by_provider_state(interactions)
+  } else {
+    vec![(0..interactions.len()).collect()]
+  };
+  debug!("Verifying {} interaction(s) in {} batch(es) with a maximum concurrency of {}",
+    interactions.len(), batches.len(), options.max_concurrency.max(1));
+
+  let mut results: Vec<(usize, InteractionVerificationResult, Duration)> =
+    futures::stream::iter(batches)
+    .map(|batch| async move {
+      let mut batch_results = vec![];
+      for index in batch {
+        let interaction = &interactions[index];
+        let interaction_desc = interaction.description();
+        let start = Instant::now();
+        let result = verify_interaction(provider_info, interaction.as_ref(), &pact.boxed(), options, provider_state_executor)
+          .instrument(debug_span!("verify_interaction", interaction = interaction_desc.as_str())).await;
+        batch_results.push((index, result, start.elapsed()));
+      }
+      futures::stream::iter(batch_results)
+    })
+    .buffer_unordered(options.max_concurrency.max(1))
+    .flatten()
+    .collect()
+    .await;
+
+  results.sort_by_key(|(index, _, _)| *index);
+  results.into_iter()
+    .map(|(index, result, duration)| (interactions[index].boxed(), result, duration))
+    .collect()
+}
+
+/// Groups the interactions (by index) so that any interactions that share a provider state end up
+/// in the same group. Interactions without provider states each get their own group.
+fn group_interactions_by_provider_state(interactions: &[Box<dyn Interaction + Send + Sync>]) -> Vec<Vec<usize>> {
+  let mut groups: Vec<(Vec<String>, Vec<usize>)> = vec![];
+  for (index, interaction) in interactions.iter().enumerate() {
+    let states = interaction.provider_states().iter()
+      .map(|state| state.name.clone())
+      .collect_vec();
+    let (sharing, mut others): (Vec<_>, Vec<_>) = groups.into_iter()
+      .partition(|(group_states, _)| states.iter().any(|state| group_states.contains(state)));
+    let (mut group_states, mut group_interactions) = sharing.into_iter()
+      .fold((vec![], vec![]), |(mut acc_states, mut acc_interactions), (s, i)| {
+        acc_states.extend(s);
+        acc_interactions.extend(i);
+        (acc_states, acc_interactions)
+      });
+    group_states.extend(states);
+    group_interactions.push(index);
+    group_interactions.sort_unstable();
+    others.push((group_states, group_interactions));
+    groups = others;
+  }
+
+  let mut groups = groups.into_iter().map(|(_, group)| group).collect_vec();
+  groups.sort_by_key(|group| group.first().cloned());
+  groups
 }
 
 fn process_comments(interaction: Box<dyn V4Interaction>, output: &mut Vec<String>) {
@@ -918,10 +918,90 @@ async fn publish_provider_branch(
       }
       Ok(())
     },
-    Err(_) => Err(PactBrokerError::LinkError("Can't publish provider branch as there is no 'pb:branch-version' link. Please ugrade to Pact Broker version 2.86.0 or later for branch support".to_string()))
+    Err(_) => Err(PactBrokerError::LinkError("Can't publish provider branch as there is no 'pb:branch-version' link. Please upgrade to Pact Broker version 2.86.0 or later for branch support".to_string()))
   }
 }
 
+/// Publishes the Pact files to the Pact Broker using the "pb:publish-contracts" link. The pacts are
+/// grouped by consumer, and each group is published for the consumer version, along with any
+/// branch, tags and build URL. Returns the responses from the broker.
+pub async fn publish_pacts(
+  broker_url: &str,
+  auth: Option<HttpAuth>,
+  pacts: &[Value],
+  version: &str,
+  branch: Option<String>,
+  tags: Vec<String>,
+  build_url: Option<String>
+) -> Result<Vec<serde_json::Value>, PactBrokerError> {
+  let hal_client = HALClient::with_url(broker_url, auth);
+  let path_info = hal_client.clone().fetch("/").await?;
+  let hal_client = hal_client.update_path_info(path_info);
+  let publish_link = hal_client.find_link("pb:publish-contracts")
+    .map_err(|_| PactBrokerError::LinkError("Can't publish pacts as there is no 'pb:publish-contracts' link. Please upgrade to Pact Broker version 2.86.0 or later".to_string()))?;
+  let publish_url = hal_client.clone().parse_link_url(&publish_link, &hashmap!{})?;
+
+  let mut responses = vec![];
+  let pacts_by_consumer = pacts.iter()
+    .sorted_by_key(|pact| pacticipant_name(pact, "consumer"))
+    .group_by(|pact| pacticipant_name(pact, "consumer"));
+  for (consumer, pacts) in &pacts_by_consumer {
+    let pacts = pacts.collect::<Vec<_>>();
+    let payload = build_publish_payload(&consumer, &pacts, version, branch.clone(), &tags, build_url.clone());
+    match hal_client.post_json(publish_url.as_str(), payload.to_string().as_str()).await {
+      Ok(response) => {
+        debug!("Published {} pact(s) for consumer {} version {}", pacts.len(), consumer, version);
+        responses.push(response);
+      },
+      Err(err) => {
+        error!("Failed to publish pacts for consumer {} version {}", consumer, version);
+        return Err(err);
+      }
+    }
+  }
+  Ok(responses)
+}
+
+fn pacticipant_name(pact_json: &Value, pacticipant: &str) -> String {
+  pact_json.get(pacticipant)
+    .and_then(|value| value.get("name"))
+    .map(as_string)
+    .unwrap_or_default()
+}
+
+fn build_publish_payload(
+  consumer: &str,
+  pacts: &[&Value],
+  version: &str,
+  branch: Option<String>,
+  tags: &[String],
+  build_url: Option<String>
+) -> serde_json::Value {
+  let mut json = json!({
+    "pacticipantName": consumer,
+    "pacticipantVersionNumber": version,
+    "tags": tags,
+    "contracts": pacts.iter().map(|pact| json!({
+      "consumerName": consumer,
+      "providerName": pacticipant_name(pact, "provider"),
+      "specification": "pact",
+      "contentType": "application/json",
+      "content": base64::encode(pact.to_string())
+    })).collect::<Vec<_>>()
+  });
+  let json_obj = json.as_object_mut().unwrap();
+
+  if let Some(branch) = branch {
+    json_obj.insert("branch".into(), json!(branch));
+  }
+
+  if let Some(build_url) = build_url {
+    json_obj.insert("buildUrl".into(), json!(build_url));
+  }
+
+  json
+}
+
 #[skip_serializing_none]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -1990,4 +2070,118 @@ mod tests {
     expect!(json.to_string()).to(be_equal_to(
       "{\"href\":\"1234\",\"templated\":true,\"title\":\"title\"}"));
   }
+
+  #[test]
+  fn build_publish_payload_test() {
+    let pact = json!({
+      "consumer": { "name": "Consumer" },
+      "provider": { "name": "Provider" },
+      "interactions": []
+    });
+    let payload = super::build_publish_payload("Consumer", &[&pact], "1.0.0", None, &[], None);
+    expect!(payload).to(be_equal_to(json!({
+      "pacticipantName": "Consumer",
+      "pacticipantVersionNumber": "1.0.0",
+      "tags": [],
+      "contracts": [
+        {
+          "consumerName": "Consumer",
+          "providerName": "Provider",
+          "specification": "pact",
+          "contentType": "application/json",
+          "content": base64::encode(pact.to_string())
+        }
+      ]
+    })));
+
+    let payload = super::build_publish_payload("Consumer", &[&pact], "1.0.0", Some("main".to_string()),
+      &["dev".to_string(), "prod".to_string()], Some("http://build-url".to_string()));
+    expect!(payload.get("branch")).to(be_some().value(&json!("main")));
+    expect!(payload.get("tags")).to(be_some().value(&json!(["dev", "prod"])));
+    expect!(payload.get("buildUrl")).to(be_some().value(&json!("http://build-url")));
+  }
+
+  #[tokio::test]
+  async fn publish_pacts_posts_the_pacts_to_the_publish_contracts_link() {
+    let pact = json!({
+      "consumer": { "name": "Consumer" },
+      "provider": { "name": "Provider" },
+      "interactions": []
+    });
+    let content = base64::encode(pact.to_string());
+    let pact_broker = PactBuilder::new("RustPactVerifier", "PactBroker")
+      .interaction("a request to the pact broker root", "", |mut i| async move {
+        i.request
+          .path("/")
+          .header("Accept", "application/hal+json")
+          .header("Accept", "application/json");
+        i.response
+          .header("Content-Type", "application/hal+json")
+          .json_body(json_pattern!({
+            "_links": {
+              "pb:publish-contracts": {
+                "href": like!("http://localhost/contracts/publish"),
+                "title": like!("Publish contracts")
+              }
+            }
+          }));
+        i
+      })
+      .await
+      .interaction("a request to publish contracts", "", |mut i| async move {
+        i.request
+          .post()
+          .path("/contracts/publish")
+          .json_body(json_pattern!({
+            "pacticipantName": "Consumer",
+            "pacticipantVersionNumber": "1.0.0",
+            "branch": "main",
+            "tags": ["dev"],
+            "buildUrl": "http://build-url",
+            "contracts": [
+              {
+                "consumerName": "Consumer",
+                "providerName": "Provider",
+                "specification": "pact",
+                "contentType": "application/json",
+                "content": content
+              }
+            ]
+          }));
+        i.response
+          .header("Content-Type", "application/hal+json")
+          .json_body(json_pattern!({
+            "notices": each_like!({ "type": "success", "text": "Pact published" })
+          }));
+        i
+      })
+      .await
+      .start_mock_server(None);
+
+    let result = super::publish_pacts(pact_broker.url().as_str(), None, &[pact], "1.0.0",
+      Some("main".to_string()), vec!["dev".to_string()], Some("http://build-url".to_string())).await;
+    expect!(result.map(|responses| responses.len())).to(be_ok().value(1));
+  }
+
+  #[tokio::test]
+  async fn publish_pacts_returns_an_error_if_the_broker_does_not_support_publishing_contracts() {
+    let pact_broker = PactBuilder::new("RustPactVerifier", "PactBroker")
+      .interaction("a request to an old pact broker root", "", |mut i| async move {
+        i.request.path("/");
+        i.response
+          .header("Content-Type", "application/hal+json")
+          .json_body(json_pattern!({
+            "_links": {
+              "pb:latest-version": { "href": like!("http://localhost/pacticipants/{pacticipant}/latest-version") }
+            }
+          }));
+        i
+      })
+      .await
+      .start_mock_server(None);
+
+    let result = super::publish_pacts(pact_broker.url().as_str(), None, &[json!({})], "1.0.0",
+      None, vec![], None).await;
+    expect!(result).to(be_err());
+  }
 }
@@ -7,7 +7,7 @@ use anyhow::anyhow;
 use futures::future::*;
 use http::{HeaderMap, HeaderValue, Method};
 use http::header::{HeaderName, InvalidHeaderName, InvalidHeaderValue};
-use http::header::CONTENT_TYPE;
+use http::header::{CONTENT_TYPE, SET_COOKIE};
 use http::method::InvalidMethod;
 use itertools::Itertools;
 use maplit::hashmap;
@@ -126,7 +126,11 @@ fn extract_headers(headers: &HeaderMap) -> Option<HashMap<String, Vec<String>>>
        (name.as_str().into(), parsed_vals.iter().cloned()
             .filter(|val| val.is_ok())
             .map(|val| val.unwrap_or_default())
-            .flat_map(|val| val.split(",").map(|v| v.to_string()).collect::<Vec<String>>())
+            .flat_map(|val| if name == SET_COOKIE {
+              vec![val]
+            } else {
+              val.split(",").map(|v| v.to_string()).collect::<Vec<String>>()
+            })
             .map(|val| val.trim().to_string())
             .collect())
       })
@@ -10,18 +10,26 @@ use serde_json::json;
 
 use pact_consumer::*;
 use pact_consumer::prelude::*;
+use pact_models::bodies::OptionalBody;
 use pact_models::Consumer;
+use pact_models::interaction::Interaction;
+use pact_models::matchingrules;
+use pact_models::matchingrules::MatchingRule;
 use pact_models::pact::Pact;
 use pact_models::PACT_RUST_VERSION;
 use pact_models::provider_states::*;
 use pact_models::sync_interaction::RequestResponseInteraction;
 use pact_models::sync_pact::RequestResponsePact;
+use pact_models::v4::http_parts::{HttpRequest, HttpResponse};
+use pact_models::v4::interaction::V4Interaction;
+use pact_models::v4::pact::V4Pact;
+use pact_models::v4::synch_http::SynchronousHttp;
 
 use crate::callback_executors::HttpRequestProviderStateExecutor;
 use crate::pact_broker::Link;
-use crate::PactSource;
+use crate::{NullRequestFilterExecutor, PactSource, ProviderInfo, VerificationOptions};
 
-use super::{execute_state_change, filter_consumers, filter_interaction, FilterInfo};
+use super::{execute_state_change, filter_consumers, filter_interaction, FilterInfo, group_interactions_by_provider_state, verify_response_from_provider};
 
 #[test]
 fn if_no_interaction_filter_is_defined_returns_true() {
@@ -129,6 +137,34 @@ fn if_a_consumer_filter_is_defined_returns_true_if_the_consumer_name_does_match(
   expect!(filter_consumers(&consumers, &result)).to(be_true());
 }
 
+#[test]
+fn group_interactions_by_provider_state_puts_interactions_with_shared_states_together() {
+  let interaction = |description: &str, states: &[&str]| -> Box<dyn Interaction + Send + Sync> {
+    Box::new(RequestResponseInteraction {
+      description: description.to_string(),
+      provider_states: states.iter().map(|state| ProviderState::default(state)).collect(),
+      .. RequestResponseInteraction::default()
+    })
+  };
+  let interactions = [
+    interaction("0", &["a"]),
+    interaction("1", &[]),
+    interaction("2", &["b"]),
+    interaction("3", &["c", "a"]),
+    interaction("4", &[]),
+    interaction("5", &["b", "c"]),
+    interaction("6", &["d"])
+  ];
+
+  expect!(group_interactions_by_provider_state(&interactions)).to(be_equal_to(vec![
+    vec![0, 2, 3, 5],
+    vec![1],
+    vec![4],
+    vec![6]
+  ]));
+  expect!(group_interactions_by_provider_state(&[])).to(be_equal_to(Vec::<Vec<usize>>::new()));
+}
+
 #[tokio::test]
 async fn test_state_change_with_parameters() {
   try_init().unwrap_or(());
@@ -513,3 +549,57 @@ async fn test_fetch_pact_from_url_with_links() {
   let source = &first_result.clone().unwrap();
   expect(source.2.to_string().starts_with("PactBroker(")).to(be_true());
 }
+
+#[tokio::test]
+async fn verify_response_from_provider_includes_an_explanation_if_explain_is_enabled() {
+  try_init().unwrap_or(());
+
+  let server = PactBuilder::new("RustPactVerifier", "SomeRunningProvider")
+    .interaction("a request for an order", "", |mut i| async move {
+      i.request.path("/orders/1");
+      i.response.status(200);
+      i.response.header("Content-Type", "application/json");
+      i.response.body("{\"id\":100}");
+      i
+    })
+    .await
+    .start_mock_server(None);
+
+  let provider = ProviderInfo {
+    host: "127.0.0.1".to_string(),
+    port: server.url().port(),
+    .. ProviderInfo::default()
+  };
+  let interaction = SynchronousHttp {
+    request: HttpRequest { path: "/orders/1".to_string(), .. HttpRequest::default() },
+    response: HttpResponse {
+      headers: Some(hashmap!{ "Content-Type".to_string() => vec!["application/json".to_string()] }),
+      body: OptionalBody::from("{\"id\":200}"),
+      matching_rules: matchingrules! {
+        "body" => {
+          "$.id" => [ MatchingRule::Integer ],
+          "$.name" => [ MatchingRule::Type ]
+        }
+      },
+      .. HttpResponse::default()
+    },
+    .. SynchronousHttp::default()
+  };
+  let pact = V4Pact { interactions: vec![interaction.boxed_v4()], .. V4Pact::default() }.boxed();
+  let options: VerificationOptions<NullRequestFilterExecutor> = VerificationOptions {
+    explain: true,
+    .. VerificationOptions::default()
+  };
+  let client = reqwest::Client::new();
+
+  let result = verify_response_from_provider(&provider, &interaction, &pact, &options, &client, &hashmap!{}).await;
+  let (_, output) = result.unwrap();
+  expect!(output.first().cloned()).to(be_some().value("Match explanation:"));
+  expect!(output.iter().any(|line| line == "  [body] $.id")).to(be_true());
+  expect!(output.iter().any(|line| line == "      $.id (weight 8): {\"match\":\"integer\"}")).to(be_true());
+  let unused = output.iter().skip_while(|line| *line != "Matching rules that were not applied:").cloned().collect::<Vec<_>>();
+  expect!(unused).to(be_equal_to(vec![
+    "Matching rules that were not applied:".to_string(),
+    "  [body] $.name".to_string()
+  ]));
+}
@@ -1,11 +1,14 @@
 //! Structs for storing and returning the result of the verification execution
 
 use std::collections::HashMap;
+use std::time::Duration;
 
 use itertools::Itertools;
 use serde_json::{json, Value};
 
-use pact_matching::Mismatch;
+use pact_matching::{DiffConfig, Mismatch};
+use pact_models::bodies::OptionalBody;
+use pact_models::interaction::Interaction;
 
 /// Main struct for returning the verification execution result
 #[derive(Debug, Clone)]
@@ -20,6 +23,8 @@ pub struct VerificationExecutionResult {
   pub pending_errors: Vec<(String, MismatchResult)>,
   /// Errors that occurred that are not considered pending
   pub errors: Vec<(String, MismatchResult)>,
+  /// Results of verifying each Pact, in the order they were verified
+  pub pact_results: Vec<PactExecutionResult>
 }
 
 impl VerificationExecutionResult {
@@ -30,7 +35,48 @@ impl VerificationExecutionResult {
       notices: vec![],
       output: vec![],
       pending_errors: vec![],
-      errors: vec![]
+      errors: vec![],
+      pact_results: vec![]
+    }
+  }
+}
+
+/// Result of verifying all the interactions of a Pact
+#[derive(Debug, Clone)]
+pub struct PactExecutionResult {
+  /// Name of the consumer of the Pact
+  pub consumer: String,
+  /// Name of the provider of the Pact
+  pub provider: String,
+  /// Results of verifying each interaction, in the order they appear in the Pact
+  pub interactions: Vec<InteractionExecutionResult>
+}
+
+/// Result of verifying a single interaction
+#[derive(Debug, Clone)]
+pub struct InteractionExecutionResult {
+  /// Description of the interaction, including any provider states
+  pub description: String,
+  /// Interaction ID if fetched from a pact broker
+  pub interaction_id: Option<String>,
+  /// Result of the verification
+  pub result: Result<(), MismatchResult>,
+  /// If the Pact or interaction is pending
+  pub pending: bool,
+  /// Time taken to verify the interaction
+  pub duration: Duration
+}
+
+impl InteractionExecutionResult {
+  /// Creates the execution result from the result of verifying the interaction, and the time
+  /// taken to verify it
+  pub(crate) fn new(result: &crate::VerificationInteractionResult, duration: Duration) -> Self {
+    InteractionExecutionResult {
+      description: result.description.clone(),
+      interaction_id: result.interaction_id.clone(),
+      result: result.result.as_ref().map(|_| ()).map_err(|err| err.into()),
+      pending: result.pending,
+      duration
     }
   }
 }
@@ -75,6 +121,9 @@ pub enum MismatchResult {
   Mismatches {
     /// Mismatches that occurred
     mismatches: Vec<Mismatch>,
+    /// RFC 6902 JSON Patch that will transform the actual body into the expected one, if there
+    /// were any JSON body mismatches
+    json_patch: Option<Value>,
     /// Interaction ID if fetched from a pact broker
     interaction_id: Option<String>
   },
@@ -90,9 +139,10 @@ pub enum MismatchResult {
 impl From<&crate::MismatchResult> for MismatchResult {
   fn from(result: &crate::MismatchResult) -> Self {
     match result {
-      crate::MismatchResult::Mismatches { mismatches, interaction_id, .. } => {
+      crate::MismatchResult::Mismatches { mismatches, expected, actual, interaction_id } => {
         MismatchResult::Mismatches {
           mismatches: mismatches.clone(),
+          json_patch: body_json_patch(mismatches, expected.as_ref(), actual.as_ref()),
           interaction_id: interaction_id.clone()
         }
       }
@@ -106,15 +156,37 @@ impl From<&crate::MismatchResult> for MismatchResult {
   }
 }
 
+/// Returns the JSON Patch for any body mismatches between the expected and actual response or
+/// message contents
+fn body_json_patch(mismatches: &[Mismatch], expected: &dyn Interaction, actual: &dyn Interaction) -> Option<Value> {
+  let bodies: Option<(OptionalBody, OptionalBody)> = if let (Some(expected), Some(actual)) = (expected.as_v4_http(), actual.as_v4_http()) {
+    Some((expected.response.body, actual.response.body))
+  } else if let (Some(expected), Some(actual)) = (expected.as_v4_sync_message(), actual.as_v4_sync_message()) {
+    expected.response.first().zip(actual.response.first())
+      .map(|(expected, actual)| (expected.contents.clone(), actual.contents.clone()))
+  } else if let (Some(expected), Some(actual)) = (expected.as_v4_async_message(), actual.as_v4_async_message()) {
+    Some((expected.contents.contents, actual.contents.contents))
+  } else {
+    None
+  };
+  bodies.and_then(|(expected, actual)| {
+    pact_matching::body_json_patch(&expected, &actual, mismatches, DiffConfig::AllowUnexpectedKeys)
+  })
+}
+
 impl Into<Value> for &MismatchResult {
   fn into(self) -> Value {
     match self {
-      MismatchResult::Mismatches { mismatches, interaction_id } => {
-        json!({
+      MismatchResult::Mismatches { mismatches, json_patch, interaction_id } => {
+        let mut json = json!({
           "type": "mismatches",
           "mismatches": mismatches.iter().map(|i| i.to_json()).collect_vec(),
           "interactionId": interaction_id.clone().unwrap_or_default()
-        })
+        });
+        if let Some(json_patch) = json_patch {
+          json["jsonPatch"] = json_patch.clone();
+        }
+        json
       }
       MismatchResult::Error { error, interaction_id } => {
         json!({
@@ -140,6 +212,9 @@ mod tests {
   use serde_json::{json, Value};
 
   use pact_matching::Mismatch;
+  use pact_models::bodies::OptionalBody;
+  use pact_models::v4::http_parts::HttpResponse;
+  use pact_models::v4::synch_http::SynchronousHttp;
 
   use crate::VerificationExecutionResult;
 
@@ -156,6 +231,7 @@ mod tests {
           mismatch: "Expected 100 but got 200".to_string()
         }
       ],
+      json_patch: None,
       interaction_id: None
     };
     let json: Value = mismatch.into();
@@ -185,6 +261,35 @@ mod tests {
     })));
   }
 
+  #[test]
+  fn match_result_with_body_mismatches_includes_a_json_patch() {
+    let expected = SynchronousHttp {
+      response: HttpResponse { body: OptionalBody::from(&json!({ "id": 100 })), .. HttpResponse::default() },
+      .. SynchronousHttp::default()
+    };
+    let actual = SynchronousHttp {
+      response: HttpResponse { body: OptionalBody::from(&json!({ "id": 200 })), .. HttpResponse::default() },
+      .. SynchronousHttp::default()
+    };
+    let result = crate::MismatchResult::Mismatches {
+      mismatches: vec![
+        Mismatch::BodyMismatch {
+          path: "$.id".to_string(),
+          expected: Some("100".into()),
+          actual: Some("200".into()),
+          mismatch: "Expected 100 but got 200".to_string()
+        }
+      ],
+      expected: Box::new(expected),
+      actual: Box::new(actual),
+      interaction_id: None
+    };
+    let json: Value = MismatchResult::from(&result).into();
+    expect!(&json["jsonPatch"]).to(be_equal_to(&json!([
+      { "op": "replace", "path": "/id", "value": 100 }
+    ])));
+  }
+
   #[test]
   fn verification_execution_result_to_json() {
     let result = VerificationExecutionResult {
@@ -217,7 +322,8 @@ mod tests {
             interaction_id: None
           }
         )
-      ]
+      ],
+      pact_results: vec![]
     };
     let json: Value = result.into();
     expect!(json).to(be_equal_to(json!({
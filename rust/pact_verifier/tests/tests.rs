@@ -230,3 +230,61 @@ async fn verifying_a_pact_with_min_type_matcher_and_child_arrays() {
 
   expect!(result.unwrap().results.get(0).unwrap().result.as_ref()).to(be_ok());
 }
+
+#[tokio::test]
+async fn verifying_a_pact_with_concurrency_returns_the_results_in_interaction_order() {
+  try_init().unwrap_or(());
+
+  let mut pact_builder = PactBuilder::new_v4("consumer", "ConcurrentService");
+  for index in 0..6 {
+    pact_builder.interaction(format!("request {}", index).as_str(), "", |mut i| async move {
+      i.test_name("verifying_a_pact_with_concurrency_returns_the_results_in_interaction_order");
+      if index % 2 == 0 {
+        i.given("shared state");
+      }
+      i.request.method("GET");
+      i.request.path(format!("/data/{}", index));
+      i.response.ok().content_type("application/json").json_body(json_pattern!({
+        "index": index
+      }));
+      i
+    }).await;
+  }
+  let pact = pact_builder.build();
+  let server = pact_builder.start_mock_server(None);
+
+  let provider = ProviderInfo {
+    name: "ConcurrentService".to_string(),
+    host: "127.0.0.1".to_string(),
+    port: server.url().port(),
+    .. ProviderInfo::default()
+  };
+
+  let options: VerificationOptions<NullRequestFilterExecutor> = VerificationOptions {
+    max_concurrency: 4,
+    .. VerificationOptions::default()
+  };
+  let provider_states = Arc::new(DummyProviderStateExecutor{});
+
+  let result = verify_pact_internal(
+    &provider,
+    &FilterInfo::None,
+    pact,
+    &options,
+    &provider_states,
+    false
+  ).await.unwrap();
+
+  let descriptions = result.results.iter()
+    .map(|result| result.description.split(" - ").last().unwrap_or_default().to_string())
+    .collect::<Vec<_>>();
+  expect!(descriptions).to(be_equal_to(["request 0", "request 1", "request 2", "request 3", "request 4", "request 5"]
+    .iter().map(|d| d.to_string()).collect::<Vec<_>>()));
+  expect!(result.results.iter().all(|result| result.result.is_ok())).to(be_true());
+  let interaction_output = result.output.iter()
+    .filter(|line| line.starts_with("  request"))
+    .cloned()
+    .collect::<Vec<_>>();
+  expect!(interaction_output).to(be_equal_to(["  request 0", "  request 1", "  request 2", "  request 3", "  request 4", "  request 5"]
+    .iter().map(|d| d.to_string()).collect::<Vec<_>>()));
+}
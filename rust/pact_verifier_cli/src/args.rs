@@ -9,6 +9,14 @@ fn integer_value(v: String) -> Result<(), String> {
   v.parse::<u64>().map(|_| ()).map_err(|e| format!("'{}' is not a valid integer value: {}", v, e) )
 }
 
+fn concurrency_value(v: String) -> Result<(), String> {
+  match v.parse::<usize>() {
+    Ok(0) => Err(format!("'{}' is not a valid concurrency value: must be at least 1", v)),
+    Ok(_) => Ok(()),
+    Err(e) => Err(format!("'{}' is not a valid concurrency value: {}", v, e))
+  }
+}
+
 pub(crate) fn setup_app<'a, 'b>(program: String, version: &'b str) -> App<'a, 'b> {
   App::new(program)
     .version(version)
@@ -233,6 +241,17 @@ pub(crate) fn setup_app<'a, 'b>(program: String, version: &'b str) -> App<'a, 'b
       .empty_values(false)
       .validator(integer_value)
       .help("Sets the HTTP request timeout in milliseconds for requests to the target API and for state change requests."))
+    .arg(Arg::with_name("max-concurrency")
+      .long("max-concurrency")
+      .alias("parallel")
+      .takes_value(true)
+      .empty_values(false)
+      .validator(concurrency_value)
+      .help("Maximum number of interactions to verify at the same time (defaults to 1). Interactions that share a provider state will still be verified one after the other."))
+    .arg(Arg::with_name("explain")
+      .long("explain")
+      .takes_value(false)
+      .help("Include an explanation of the matching rules that were applied at each path when matching the responses in the output"))
     .arg(Arg::with_name("json-file")
       .short("j")
       .long("json")
@@ -242,6 +261,14 @@ pub(crate) fn setup_app<'a, 'b>(program: String, version: &'b str) -> App<'a, 'b
       .number_of_values(1)
       .empty_values(false)
       .help("Generate a JSON report of the verification"))
+    .arg(Arg::with_name("junit-file")
+      .long("junit")
+      .takes_value(true)
+      .use_delimiter(false)
+      .multiple(false)
+      .number_of_values(1)
+      .empty_values(false)
+      .help("Generate a JUnit XML report of the verification"))
     .arg(Arg::with_name("custom-header")
       .long("header")
       .takes_value(true)
@@ -253,7 +280,7 @@ pub(crate) fn setup_app<'a, 'b>(program: String, version: &'b str) -> App<'a, 'b
 
 #[cfg(test)]
 mod test {
-  use super::{integer_value, port_value};
+  use super::{concurrency_value, integer_value, port_value};
   use expectest::prelude::*;
 
   #[test]
@@ -267,4 +294,12 @@ mod test {
     expect!(integer_value("3000000".to_string())).to(be_ok());
     expect!(integer_value("1234x".to_string())).to(be_err());
   }
+
+  #[test]
+  fn validates_concurrency_value() {
+    expect!(concurrency_value("4".to_string())).to(be_ok());
+    expect!(concurrency_value("0".to_string())).to(be_err());
+    expect!(concurrency_value("-1".to_string())).to(be_err());
+    expect!(concurrency_value("x".to_string())).to(be_err());
+  }
 }
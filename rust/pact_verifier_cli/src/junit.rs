@@ -0,0 +1,192 @@
+//! Support for writing the verification result as a JUnit XML report
+
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::Write;
+use std::time::Duration;
+
+use tracing::debug;
+
+use pact_verifier::verification_result::{
+  InteractionExecutionResult,
+  MismatchResult,
+  PactExecutionResult,
+  VerificationExecutionResult
+};
+
+/// Writes the verification result to the given file as a JUnit XML report. Each Pact is written
+/// as a test suite, with a test case for each interaction.
+pub(crate) fn write_junit_report(result: &VerificationExecutionResult, file_name: &str) -> anyhow::Result<()> {
+  debug!("Writing JUnit XML result of the verification to '{file_name}'");
+  let mut f = File::create(file_name)?;
+  f.write_all(generate_junit_report(result).as_bytes())?;
+  Ok(())
+}
+
+fn generate_junit_report(result: &VerificationExecutionResult) -> String {
+  let interactions = || result.pact_results.iter().flat_map(|pact| pact.interactions.iter());
+  let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+  let _ = writeln!(xml, "<testsuites name=\"pact_verifier_cli\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{}\">",
+    interactions().count(),
+    interactions().filter(|i| is_failure(i)).count(),
+    interactions().filter(|i| is_error(i)).count(),
+    interactions().filter(|i| is_skipped(i)).count(),
+    format_duration(interactions().map(|i| i.duration).sum()));
+  for pact in &result.pact_results {
+    write_test_suite(&mut xml, pact);
+  }
+  xml.push_str("</testsuites>\n");
+  xml
+}
+
+fn write_test_suite(xml: &mut String, pact: &PactExecutionResult) {
+  let name = format!("Verifying a pact between {} and {}", pact.consumer, pact.provider);
+  let _ = writeln!(xml, "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{}\">",
+    escape(&name),
+    pact.interactions.len(),
+    pact.interactions.iter().filter(|i| is_failure(i)).count(),
+    pact.interactions.iter().filter(|i| is_error(i)).count(),
+    pact.interactions.iter().filter(|i| is_skipped(i)).count(),
+    format_duration(pact.interactions.iter().map(|i| i.duration).sum()));
+  let class_name = format!("{}.{}", pact.consumer, pact.provider);
+  for interaction in &pact.interactions {
+    let test_name = interaction.description.strip_prefix(name.as_str())
+      .map(|d| d.trim_start_matches(" - ").trim())
+      .unwrap_or(interaction.description.as_str());
+    let _ = write!(xml, "    <testcase name=\"{}\" classname=\"{}\" time=\"{}\"",
+      escape(test_name), escape(&class_name), format_duration(interaction.duration));
+    match &interaction.result {
+      Ok(_) => xml.push_str("/>\n"),
+      Err(err) => {
+        xml.push_str(">\n");
+        let (message, details) = mismatch_details(err);
+        if interaction.pending {
+          let _ = writeln!(xml, "      <skipped message=\"{}\">{}</skipped>", escape(&format!("Pending: {}", message)),
+            escape(&details));
+        } else if let MismatchResult::Error { .. } = err {
+          let _ = writeln!(xml, "      <error message=\"{}\" type=\"error\">{}</error>", escape(&message), escape(&details));
+        } else {
+          let _ = writeln!(xml, "      <failure message=\"{}\" type=\"mismatches\">{}</failure>", escape(&message),
+            escape(&details));
+        }
+        xml.push_str("    </testcase>\n");
+      }
+    }
+  }
+  xml.push_str("  </testsuite>\n");
+}
+
+fn mismatch_details(result: &MismatchResult) -> (String, String) {
+  match result {
+    MismatchResult::Mismatches { mismatches, .. } => {
+      let message = format!("{} mismatch(es)", mismatches.len());
+      let details = mismatches.iter()
+        .map(|mismatch| format!("{}: {}", mismatch.summary(), mismatch.description()))
+        .collect::<Vec<_>>()
+        .join("\n");
+      (message, details)
+    }
+    MismatchResult::Error { error, .. } => (error.clone(), error.clone())
+  }
+}
+
+fn is_failure(interaction: &InteractionExecutionResult) -> bool {
+  !interaction.pending && matches!(interaction.result, Err(MismatchResult::Mismatches { .. }))
+}
+
+fn is_error(interaction: &InteractionExecutionResult) -> bool {
+  !interaction.pending && matches!(interaction.result, Err(MismatchResult::Error { .. }))
+}
+
+fn is_skipped(interaction: &InteractionExecutionResult) -> bool {
+  interaction.pending && interaction.result.is_err()
+}
+
+fn format_duration(duration: Duration) -> String {
+  format!("{:.3}", duration.as_secs_f64())
+}
+
+fn escape(value: &str) -> String {
+  value.chars()
+    .filter(|ch| !ch.is_control() || *ch == '\n' || *ch == '\t')
+    .fold(String::with_capacity(value.len()), |mut s, ch| {
+      match ch {
+        '&' => s.push_str("&amp;"),
+        '<' => s.push_str("&lt;"),
+        '>' => s.push_str("&gt;"),
+        '"' => s.push_str("&quot;"),
+        '\'' => s.push_str("&apos;"),
+        _ => s.push(ch)
+      }
+      s
+    })
+}
+
+#[cfg(test)]
+mod tests {
+  use std::time::Duration;
+
+  use expectest::prelude::*;
+
+  use pact_verifier::verification_result::{
+    InteractionExecutionResult,
+    MismatchResult,
+    PactExecutionResult,
+    VerificationExecutionResult
+  };
+
+  use super::{escape, generate_junit_report};
+
+  #[test]
+  fn escape_test() {
+    expect!(escape("a < b & \"c\" > 'd'")).to(be_equal_to("a &lt; b &amp; &quot;c&quot; &gt; &apos;d&apos;"));
+    expect!(escape("\u{1b}[31mred\u{1b}[0m")).to(be_equal_to("[31mred[0m"));
+  }
+
+  #[test]
+  fn generate_junit_report_test() {
+    let mut result = VerificationExecutionResult::new();
+    result.pact_results.push(PactExecutionResult {
+      consumer: "Consumer".to_string(),
+      provider: "Provider".to_string(),
+      interactions: vec![
+        InteractionExecutionResult {
+          description: "Verifying a pact between Consumer and Provider - a request".to_string(),
+          interaction_id: None,
+          result: Ok(()),
+          pending: false,
+          duration: Duration::from_millis(10)
+        },
+        InteractionExecutionResult {
+          description: "Verifying a pact between Consumer and Provider Given a user exists - a request for a user".to_string(),
+          interaction_id: None,
+          result: Err(MismatchResult::Error { error: "Connection refused".to_string(), interaction_id: None }),
+          pending: false,
+          duration: Duration::from_millis(1)
+        },
+        InteractionExecutionResult {
+          description: "Verifying a pact between Consumer and Provider - a pending request".to_string(),
+          interaction_id: None,
+          result: Err(MismatchResult::Mismatches { mismatches: vec![], json_patch: None, interaction_id: None }),
+          pending: true,
+          duration: Duration::from_millis(1500)
+        }
+      ]
+    });
+
+    expect!(generate_junit_report(&result)).to(be_equal_to(
+r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="pact_verifier_cli" tests="3" failures="0" errors="1" skipped="1" time="1.511">
+  <testsuite name="Verifying a pact between Consumer and Provider" tests="3" failures="0" errors="1" skipped="1" time="1.511">
+    <testcase name="a request" classname="Consumer.Provider" time="0.010"/>
+    <testcase name="Given a user exists - a request for a user" classname="Consumer.Provider" time="0.001">
+      <error message="Connection refused" type="error">Connection refused</error>
+    </testcase>
+    <testcase name="a pending request" classname="Consumer.Provider" time="1.500">
+      <skipped message="Pending: 0 mismatch(es)"></skipped>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#));
+  }
+}
@@ -20,6 +20,8 @@
 //! FLAGS:
 //!         --disable-ssl-verification    Disables validation of SSL certificates
 //!         --enable-pending              Enables Pending Pacts
+//!         --explain                     Include an explanation of the matching rules that were applied at each path
+//!                                       when matching the responses in the output
 //!         --help                        Prints help information
 //!         --publish                     Enables publishing of verification results back to the Pact Broker. Requires the
 //!                                       broker-url and provider-version parameters.
@@ -66,9 +68,14 @@
 //!             Allow pacts that don't match given consumer selectors (or tags) to  be verified, without causing the overall
 //!             task to fail. For more information, see https://pact.io/wip
 //!     -j, --json <json-file>                                              Generate a JSON report of the verification
+//!         --junit <junit-file>                                            Generate a JUnit XML report of the verification
 //!     -l, --loglevel <loglevel>
 //!             Log level (defaults to warn) [possible values: error, warn, info, debug,
 //!             trace, none]
+//!         --max-concurrency <max-concurrency>
+//!             Maximum number of interactions to verify at the same time (defaults to 1). Interactions that share a
+//!             provider state will still be verified one after the other.
+//!
 //!         --password <password>
 //!             Password to use when fetching pacts from URLS [env: PACT_BROKER_PASSWORD=]
 //!
@@ -163,6 +170,28 @@
 //!
 //! This option will cause the verifier to also make a tear down request after the main request is made. It will receive a second field in the body or a query parameter named `action` with the value `teardown`.
 //!
+//! ### Reports
+//!
+//! #### `-j, --json <json-file>`
+//!
+//! Writes a JSON report of the verification result to the given file.
+//!
+//! #### `--junit <junit-file>`
+//!
+//! Writes a JUnit XML report of the verification result to the given file. Each pact (consumer and provider pair) is written as a test suite, with a test case for each interaction that was verified, including the time taken to verify it. Mismatches are reported as failures, errors (like failed state change requests) as errors, and failures of pending pacts or interactions are reported as skipped.
+//!
+//! ### Explaining the matching
+//!
+//! #### `--explain`
+//!
+//! Includes an explanation of how the matching rules were applied when matching the responses from the provider in the output. For every path that was matched (status, headers and each path in the body), this shows all the candidate matching rules along with their calculated weights, the matching rule that was selected and the outcome. This is useful when a match fails (or passes) unexpectedly.
+//!
+//! ### Verifying interactions concurrently
+//!
+//! #### `--max-concurrency <max-concurrency>`
+//!
+//! By default, the interactions are verified one at a time. This option allows up to the given number of interactions to be verified at the same time (`--parallel` can also be used). Interactions that share a provider state (by name) are always verified one after the other, but interactions with different provider states or no provider states may be verified at the same time, so only use this option if your provider state change handlers don't interfere with each other. The output is still reported in the order of the interactions in the pact file.
+//!
 //! ## Example run
 //!
 //! This will verify all the pacts for the `happy_provider` found in the pact broker (running on localhost) against the provider running on localhost port 5050. Only the pacts for the consumers `Consumer` and `Consumer2` will be verified.
@@ -291,6 +320,7 @@ use pact_verifier::selectors::{consumer_tags_to_selectors, json_to_selectors};
 use pact_verifier::verification_result::VerificationExecutionResult;
 
 mod args;
+mod junit;
 
 /// Handles the command line arguments from the running process
 pub async fn handle_cli(version: &str) -> Result<(), i32> {
@@ -365,7 +395,10 @@ async fn handle_matches(matches: &clap::ArgMatches<'_>) -> Result<(), i32> {
     disable_ssl_verification: matches.is_present("disable-ssl-verification"),
     request_timeout: matches.value_of("request-timeout")
       .map(|t| t.parse::<u64>().unwrap_or(5000)).unwrap_or(5000),
-    custom_headers
+    custom_headers,
+    max_concurrency: matches.value_of("max-concurrency")
+      .map(|v| v.parse::<usize>().unwrap_or(1)).unwrap_or(1),
+    explain: matches.is_present("explain")
   };
 
   let publish_options = if matches.is_present("publish") {
@@ -411,6 +444,13 @@ async fn handle_matches(matches: &clap::ArgMatches<'_>) -> Result<(), i32> {
         }
       }
 
+      if let Some(junit_file) = matches.value_of("junit-file") {
+        if let Err(err) = junit::write_junit_report(&result, junit_file) {
+          error!("Failed to write JUnit XML report to '{junit_file}' - {err}");
+          return Err(2)
+        }
+      }
+
       if result.result { Ok(()) } else { Err(1) }
     })
 }